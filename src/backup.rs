@@ -1,7 +1,8 @@
 use anyhow::{anyhow, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use crate::config::get_config_path;
 use crate::shell_integration::ShellType;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tokio::fs;
@@ -25,6 +26,8 @@ pub enum BackupReason {
     InitForceRecreate,
     InitConflictResolution,
     ManualBackup,
+    ConfigMigration,
+    InitFromRemote,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -56,6 +59,73 @@ pub enum ShellChangeAction {
     Replace,
 }
 
+/// Backup retention policy, written as `keep-n:<n>`, `keep-days:<d>` or `keep-size:<mb>`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(try_from = "String", into = "String")]
+#[schemars(with = "String")]
+pub enum RetentionPolicy {
+    /// Keep the N most recent backups
+    KeepN(usize),
+    /// Keep backups created within the last D days
+    KeepDays(u32),
+    /// Delete the oldest backups until the total size is at most this many MB
+    KeepSize(u64),
+}
+
+impl RetentionPolicy {
+    /// Build a policy from its kind (`keep-n`, `keep-days`, `keep-size`) and value
+    pub fn from_parts(kind: &str, value: &str) -> Result<Self> {
+        let invalid = || anyhow!("Invalid value for {}: '{}' (expected a non-negative number)", kind, value);
+        match kind {
+            "keep-n" => match value.parse().map_err(|_| invalid())? {
+                0 => Err(anyhow!("keep-n must keep at least one backup")),
+                n => Ok(Self::KeepN(n)),
+            },
+            "keep-days" => Ok(Self::KeepDays(value.parse().map_err(|_| invalid())?)),
+            "keep-size" => Ok(Self::KeepSize(value.parse().map_err(|_| invalid())?)),
+            _ => Err(anyhow!(
+                "Unknown retention policy '{}' (expected keep-n, keep-days or keep-size)",
+                kind
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for RetentionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetentionPolicy::KeepN(n) => write!(f, "keep-n:{}", n),
+            RetentionPolicy::KeepDays(days) => write!(f, "keep-days:{}", days),
+            RetentionPolicy::KeepSize(mb) => write!(f, "keep-size:{}", mb),
+        }
+    }
+}
+
+impl std::str::FromStr for RetentionPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (kind, value) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid retention policy '{}' (expected e.g. keep-days:30)", s))?;
+        Self::from_parts(kind.trim(), value.trim())
+    }
+}
+
+impl TryFrom<String> for RetentionPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<RetentionPolicy> for String {
+    fn from(policy: RetentionPolicy) -> Self {
+        policy.to_string()
+    }
+}
+
 impl BackupMetadata {
     pub fn new() -> Self {
         Self {
@@ -76,6 +146,8 @@ impl std::fmt::Display for BackupReason {
             BackupReason::InitForceRecreate => write!(f, "Init Force Recreate"),
             BackupReason::InitConflictResolution => write!(f, "Init Conflict Resolution"),
             BackupReason::ManualBackup => write!(f, "Manual Backup"),
+            BackupReason::ConfigMigration => write!(f, "Config Migration"),
+            BackupReason::InitFromRemote => write!(f, "Init From Remote"),
         }
     }
 }
@@ -102,8 +174,11 @@ pub async fn load_backup_metadata() -> Result<BackupMetadata> {
     }
     
     let content = fs::read_to_string(&metadata_path).await?;
-    let metadata: BackupMetadata = serde_json::from_str(&content)
-        .map_err(|e| anyhow!("Failed to parse backup metadata: {}", e))?;
+    let metadata: BackupMetadata = serde_json::from_str(&content).map_err(|e| {
+        anyhow::Error::new(e).context(crate::error::PmError::BackupCorrupted {
+            path: metadata_path.clone(),
+        })
+    })?;
     
     Ok(metadata)
 }
@@ -263,6 +338,47 @@ pub async fn get_latest_backup() -> Result<Option<BackupEntry>> {
     Ok(latest)
 }
 
+/// Total size in bytes of the files stored for a backup
+pub fn backup_entry_size(entry: &BackupEntry) -> u64 {
+    entry
+        .files
+        .iter()
+        .filter_map(|file| std::fs::metadata(&file.backup_path).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Backups that `policy` would delete, newest first
+pub fn apply_retention_policy(
+    backups: &[BackupEntry],
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<BackupEntry> {
+    let mut sorted = backups.to_vec();
+    sorted.sort_by_key(|backup| std::cmp::Reverse(backup.timestamp));
+
+    match *policy {
+        RetentionPolicy::KeepN(keep) => sorted.into_iter().skip(keep).collect(),
+        RetentionPolicy::KeepDays(days) => {
+            let cutoff = now - Duration::days(days as i64);
+            sorted.into_iter().filter(|backup| backup.timestamp < cutoff).collect()
+        }
+        RetentionPolicy::KeepSize(max_mb) => {
+            let max_bytes = max_mb.saturating_mul(1024 * 1024);
+            let mut total: u64 = sorted.iter().map(backup_entry_size).sum();
+            let mut to_remove = Vec::new();
+            // Drop the oldest backups first until the rest fits
+            while total > max_bytes {
+                let Some(oldest) = sorted.pop() else { break };
+                total -= backup_entry_size(&oldest);
+                to_remove.push(oldest);
+            }
+            to_remove.reverse();
+            to_remove
+        }
+    }
+}
+
 /// Check if backup directory exists and is accessible
 pub async fn is_backup_system_available() -> bool {
     match get_backup_dir() {
@@ -294,6 +410,8 @@ mod tests {
         assert_eq!(BackupReason::InitForceRecreate.to_string(), "Init Force Recreate");
         assert_eq!(BackupReason::InitConflictResolution.to_string(), "Init Conflict Resolution");
         assert_eq!(BackupReason::ManualBackup.to_string(), "Manual Backup");
+        assert_eq!(BackupReason::ConfigMigration.to_string(), "Config Migration");
+        assert_eq!(BackupReason::InitFromRemote.to_string(), "Init From Remote");
     }
     
     #[test]
@@ -307,4 +425,47 @@ mod tests {
         let fish_path = Path::new("/home/user/.config/fish/functions/pm.fish");
         assert!(matches!(determine_file_type(fish_path), BackupFileType::ShellIntegration(ShellType::Fish)));
     }
+
+    fn backup_at(now: DateTime<Utc>, days_ago: i64, backup_path: PathBuf) -> BackupEntry {
+        BackupEntry {
+            timestamp: now - Duration::days(days_ago),
+            id: format!("backup-{}", days_ago),
+            reason: BackupReason::ManualBackup,
+            files: vec![BackupFile {
+                original_path: PathBuf::from("config.yml"),
+                backup_path,
+                file_type: BackupFileType::Config,
+            }],
+            shell_changes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_apply_retention_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = Utc::now();
+        let backups: Vec<BackupEntry> = [3, 0, 10]
+            .into_iter()
+            .map(|days_ago| {
+                let path = temp_dir.path().join(format!("{}.yml", days_ago));
+                std::fs::write(&path, vec![0u8; 1024 * 1024]).unwrap();
+                backup_at(now, days_ago, path)
+            })
+            .collect();
+        let ids = |removed: Vec<BackupEntry>| removed.into_iter().map(|b| b.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(apply_retention_policy(&backups, &RetentionPolicy::KeepN(1), now)), ["backup-3", "backup-10"]);
+        assert_eq!(ids(apply_retention_policy(&backups, &RetentionPolicy::KeepDays(5), now)), ["backup-10"]);
+        assert_eq!(ids(apply_retention_policy(&backups, &RetentionPolicy::KeepSize(2), now)), ["backup-10"]);
+        assert!(apply_retention_policy(&backups, &RetentionPolicy::KeepSize(3), now).is_empty());
+    }
+
+    #[test]
+    fn test_retention_policy_parsing() {
+        assert_eq!("keep-days:30".parse::<RetentionPolicy>().unwrap(), RetentionPolicy::KeepDays(30));
+        assert_eq!(RetentionPolicy::KeepSize(100).to_string(), "keep-size:100");
+        assert!("keep-weeks:2".parse::<RetentionPolicy>().is_err());
+        assert!("keep-n".parse::<RetentionPolicy>().is_err());
+        assert!("keep-n:0".parse::<RetentionPolicy>().is_err());
+    }
 }
\ No newline at end of file
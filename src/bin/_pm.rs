@@ -1,6 +1,6 @@
 use clap::{CommandFactory, Parser};
 use pm::{
-    handle_command, handle_config_error, Cli, Commands, PmError, init
+    handle_command, handle_config_error, is_cancellation, Cli, Commands, init
 };
 
 #[tokio::main]
@@ -32,12 +32,9 @@ async fn main() {
 
     // Handle commands with dev mode adjustments
     if let Err(e) = handle_command_dev(command).await {
-        // Check if this is a user cancellation (Ctrl-C)
-        if let Some(pm_error) = e.downcast_ref::<PmError>() {
-            if matches!(pm_error, PmError::OperationCancelled) {
-                // Gracefully exit on cancellation
-                std::process::exit(0);
-            }
+        // Gracefully exit when the user cancelled a prompt (Esc / Ctrl-C)
+        if is_cancellation(&e) {
+            std::process::exit(0);
         }
         handle_config_error(e);
     }
@@ -46,7 +43,13 @@ async fn main() {
 /// Handle commands with development mode adjustments
 async fn handle_command_dev(command: &Commands) -> anyhow::Result<()> {
     match command {
-        Commands::Init { skip, replace } => {
+        Commands::Init {
+            skip,
+            replace,
+            update: false,
+            force_unlock: false,
+            from: None,
+        } => {
             // Development mode is always enabled for _pm
             init::handle_init(*skip, *replace, true).await
         }
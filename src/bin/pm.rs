@@ -1,6 +1,6 @@
 use clap::{CommandFactory, Parser};
 use pm::{
-    handle_command, handle_config_error, Cli, PmError
+    handle_command, handle_config_error, is_cancellation, Cli
 };
 
 #[tokio::main]
@@ -25,12 +25,9 @@ async fn main() {
 
     // Handle commands
     if let Err(e) = handle_command(command).await {
-        // Check if this is a user cancellation (Ctrl-C)
-        if let Some(pm_error) = e.downcast_ref::<PmError>() {
-            if matches!(pm_error, PmError::OperationCancelled) {
-                // Gracefully exit on cancellation
-                std::process::exit(0);
-            }
+        // Gracefully exit when the user cancelled a prompt (Esc / Ctrl-C)
+        if is_cancellation(&e) {
+            std::process::exit(0);
         }
         handle_config_error(e);
     }
@@ -1,5 +1,11 @@
-use crate::backup::{load_backup_metadata, save_backup_metadata, get_backup_dir};
+use crate::backup::{
+    apply_retention_policy, backup_entry_size, get_backup_dir, load_backup_metadata,
+    save_backup_metadata, BackupEntry, BackupMetadata, RetentionPolicy,
+};
+use crate::config::load_config;
 use crate::display::*;
+use crate::error::{handle_inquire_error, PmError};
+use crate::utils::format_size;
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
@@ -98,9 +104,9 @@ pub async fn handle_backup_restore(backup_id: &str, force: bool) -> Result<()> {
     
     // Confirmation
     if !force {
-        let confirmed = Confirm::new("Do you want to restore this backup?")
+        let confirmed = handle_inquire_error(Confirm::new("Do you want to restore this backup?")
             .with_default(false)
-            .prompt()?;
+            .prompt())?;
         
         if !confirmed {
             println!("🚫 Backup restore cancelled");
@@ -159,70 +165,115 @@ pub async fn handle_backup_restore(backup_id: &str, force: bool) -> Result<()> {
 
 /// Clean old backups (keep most recent N)
 pub async fn handle_backup_clean(keep_count: usize, force: bool) -> Result<()> {
-    let mut metadata = load_backup_metadata().await?;
-    
-    if metadata.backups.len() <= keep_count {
-        println!("📦 No backups to clean (current: {}, keep: {})", 
-            metadata.backups.len(), keep_count);
+    handle_backup_rotate(Some(RetentionPolicy::KeepN(keep_count)), force).await
+}
+
+/// Delete backups according to a retention policy (defaults to `settings.backup_retention_policy`)
+pub async fn handle_backup_rotate(policy: Option<RetentionPolicy>, force: bool) -> Result<()> {
+    let policy = match policy {
+        Some(policy) => policy,
+        None => match load_config().await?.settings.backup_retention_policy {
+            Some(policy) => policy,
+            None => {
+                display_error("No retention policy", "pass --policy or set settings.backup_retention_policy");
+                println!(
+                    "💡 Example: {} backup rotate --policy keep-days 30",
+                    crate::utils::get_binary_name()
+                );
+                return Err(PmError::ValidationFailed("no retention policy configured".to_string()).into());
+            }
+        },
+    };
+
+    let metadata = load_backup_metadata().await?;
+    let to_remove = apply_retention_policy(&metadata.backups, &policy, Utc::now());
+
+    if to_remove.is_empty() {
+        println!("📦 No backups to clean (current: {}, policy: {})",
+            metadata.backups.len(), policy);
         return Ok(());
     }
-    
-    // Sort by timestamp (newest first)
-    metadata.backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-    
-    let to_remove = metadata.backups.split_off(keep_count);
-    
-    println!("🗑️  Found {} backups to clean (keeping {} most recent)", 
-        to_remove.len(), keep_count);
-    
+
+    println!("🗑️  Found {} backups to clean (policy: {})",
+        to_remove.len(), policy);
+
     // Show what will be removed
     for backup in &to_remove {
         let age = format_backup_age(&backup.timestamp);
-        println!("   📦 {} ({}, {})", backup.id, backup.reason, age);
+        println!("   📦 {} ({}, {}, {})", backup.id, backup.reason, age,
+            format_size(backup_entry_size(backup)));
     }
-    
+
     // Confirmation
     if !force {
-        let confirmed = Confirm::new(&format!("Delete {} old backups?", to_remove.len()))
+        let confirmed = handle_inquire_error(Confirm::new(&format!("Delete {} old backups?", to_remove.len()))
             .with_default(false)
-            .prompt()?;
-        
+            .prompt())?;
+
         if !confirmed {
             println!("🚫 Backup cleanup cancelled");
             return Ok(());
         }
     }
-    
+
+    remove_backups(metadata, &to_remove).await
+}
+
+/// Apply `settings.backup_retention_policy` after a backup was recorded, without prompting
+///
+/// The backup `created_id` that was just recorded is never removed.
+pub async fn apply_configured_retention_policy(created_id: &str) -> Result<()> {
+    let Ok(config) = load_config().await else {
+        return Ok(());
+    };
+    let Some(policy) = config.settings.backup_retention_policy else {
+        return Ok(());
+    };
+
+    let metadata = load_backup_metadata().await?;
+    let mut to_remove = apply_retention_policy(&metadata.backups, &policy, Utc::now());
+    to_remove.retain(|backup| backup.id != created_id);
+    if to_remove.is_empty() {
+        return Ok(());
+    }
+
+    println!("🗑️  Retention policy {}: removing {} old backups", policy, to_remove.len());
+    remove_backups(metadata, &to_remove).await
+}
+
+/// Delete backup directories and drop them from the metadata
+async fn remove_backups(mut metadata: BackupMetadata, to_remove: &[BackupEntry]) -> Result<()> {
     let mut removed_count = 0;
     let mut failed_count = 0;
-    
+
     // Remove backup directories
-    for backup in &to_remove {
+    for backup in to_remove {
         let backup_dir = get_backup_dir()?.join(&backup.id);
         if backup_dir.exists() {
             if let Err(e) = fs::remove_dir_all(&backup_dir).await {
                 display_warning(&format!("Failed to remove backup {}: {}", backup.id, e));
                 failed_count += 1;
-            } else {
-                println!("🗑️ Removed backup: {}", backup.id);
-                removed_count += 1;
+                continue;
             }
+            println!("🗑️ Removed backup: {}", backup.id);
         } else {
             println!("⚠️  Backup directory not found: {}", backup_dir.display());
-            removed_count += 1; // Still count as removed from metadata
         }
+        // Also counted when the directory is already gone, since the metadata entry is dropped
+        removed_count += 1;
+        metadata.backups.retain(|entry| entry.id != backup.id);
     }
-    
+
     // Update metadata
     save_backup_metadata(&metadata).await?;
-    
+
     // Summary
     if failed_count == 0 {
         display_success(&format!("Cleaned {} old backups successfully", removed_count));
     } else {
         display_warning(&format!("Cleaned {} backups with {} errors", removed_count, failed_count));
     }
-    
+
     Ok(())
 }
 
@@ -248,8 +299,8 @@ pub async fn handle_backup_restore_interactive() -> Result<()> {
         })
         .collect();
     
-    let choice = Select::new("Select a backup to restore:", options)
-        .prompt()?;
+    let choice = handle_inquire_error(Select::new("Select a backup to restore:", options)
+        .prompt())?;
     
     // Extract backup ID from choice
     let backup_id = choice.split(" - ").next()
@@ -275,7 +326,10 @@ fn format_backup_age(timestamp: &DateTime<Utc>) -> String {
 }
 
 /// Show backup system status
-pub async fn handle_backup_status() -> Result<()> {
+///
+/// `size` adds a disk usage breakdown; `cleanup_keep` previews what
+/// `pm backup clean <keep>` would free.
+pub async fn handle_backup_status(size: bool, cleanup_keep: Option<usize>) -> Result<()> {
     let backup_dir = get_backup_dir()?;
     
     println!("📦 Backup System Status\n");
@@ -305,6 +359,15 @@ pub async fn handle_backup_status() -> Result<()> {
         
         println!("💾 Storage Used: {} ({} backup directories)", 
             format_size(total_size), total_files);
+
+        let config_backups = crate::commands::config::config_backup_sizes()?;
+        if config_backups.count > 0 {
+            println!("🗜️  Config Backups: {} ({} compressed), {} uncompressed, {} on disk",
+                config_backups.count,
+                config_backups.compressed_count,
+                format_size(config_backups.uncompressed_bytes),
+                format_size(config_backups.stored_bytes));
+        }
     } else {
         println!("💾 Storage Used: Directory not created yet");
     }
@@ -332,10 +395,79 @@ pub async fn handle_backup_status() -> Result<()> {
             println!("📊 Backup Count: 0 (metadata not found)");
         }
     }
+
+    if size {
+        print_disk_usage(&backup_dir).await?;
+    }
+
+    if let Some(keep) = cleanup_keep {
+        let metadata = load_backup_metadata().await?;
+        let to_remove = apply_retention_policy(&metadata.backups, &RetentionPolicy::KeepN(keep), Utc::now());
+        let mut freed = 0;
+        for backup in &to_remove {
+            freed += path_size(&backup_dir.join(&backup.id)).await;
+        }
+        println!("\n🧹 Cleanup Preview ({} backup clean {}):", crate::utils::get_binary_name(), keep);
+        if to_remove.is_empty() {
+            println!("   Nothing to clean ({} backups)", metadata.backups.len());
+        } else {
+            println!("   Would remove {} of {} backups, freeing {}",
+                to_remove.len(), metadata.backups.len(), format_size(freed));
+        }
+    }
     
     Ok(())
 }
 
+/// Disk usage of the backups, config file and extensions, warning above
+/// `settings.backup_size_warning_mb`
+async fn print_disk_usage(backup_dir: &Path) -> Result<()> {
+    println!("\n💽 Disk Usage:");
+
+    let mut backups = load_backup_metadata().await?.backups;
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.timestamp));
+    for backup in &backups {
+        let size = path_size(&backup_dir.join(&backup.id)).await;
+        println!("   📦 {:<40} {:>10}", backup.id, format_size(size));
+    }
+    for (name, size) in crate::commands::config::config_backup_file_sizes()? {
+        println!("   ⚙️  {:<40} {:>10}", name, format_size(size));
+    }
+
+    let backups_size = path_size(backup_dir).await;
+    let config_size = path_size(&crate::config::get_config_path()?).await;
+    let extensions_size = path_size(&crate::extensions::get_extensions_dir()?).await;
+    let total = backups_size + config_size + extensions_size;
+
+    println!();
+    println!("   {:<20} {:>10}", "Backups", format_size(backups_size));
+    println!("   {:<20} {:>10}", "Config file", format_size(config_size));
+    println!("   {:<20} {:>10}", "Extensions", format_size(extensions_size));
+    println!("   {:<20} {:>10}", "Total".bold(), format_size(total));
+
+    if let Some(warning_mb) = load_config().await?.settings.backup_size_warning_mb {
+        if backups_size > warning_mb * 1024 * 1024 {
+            display_warning(&format!(
+                "Backups use {}, more than settings.backup_size_warning_mb ({} MB)",
+                format_size(backups_size),
+                warning_mb
+            ));
+            println!("💡 Free space with: {} backup clean", crate::utils::get_binary_name());
+        }
+    }
+
+    Ok(())
+}
+
+/// Size of a file, or of a directory's contents (0 if it doesn't exist)
+async fn path_size(path: &Path) -> u64 {
+    match fs::metadata(path).await {
+        Ok(metadata) if metadata.is_dir() => calculate_dir_size(path).await.unwrap_or(0),
+        Ok(metadata) => metadata.len(),
+        Err(_) => 0,
+    }
+}
+
 /// Calculate directory size recursively
 fn calculate_dir_size(path: &Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<u64>> + Send + '_>> {
     Box::pin(async move {
@@ -355,22 +487,19 @@ fn calculate_dir_size(path: &Path) -> std::pin::Pin<Box<dyn std::future::Future<
     })
 }
 
-/// Format file size in human-readable format
-fn format_size(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
-    const THRESHOLD: u64 = 1024;
-    
-    if bytes < THRESHOLD {
-        return format!("{} B", bytes);
-    }
-    
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
-    
-    while size >= THRESHOLD as f64 && unit_index < UNITS.len() - 1 {
-        size /= THRESHOLD as f64;
-        unit_index += 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_path_size_of_files_directories_and_missing_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("backup/nested")).unwrap();
+        std::fs::write(temp.path().join("backup/config.yml"), "0123456789").unwrap();
+        std::fs::write(temp.path().join("backup/nested/history.json"), "01234").unwrap();
+
+        assert_eq!(path_size(&temp.path().join("backup/config.yml")).await, 10);
+        assert_eq!(path_size(&temp.path().join("backup")).await, 15);
+        assert_eq!(path_size(&temp.path().join("missing")).await, 0);
     }
-    
-    format!("{:.1} {}", size, UNITS[unit_index])
-}
\ No newline at end of file
+}
@@ -1,14 +1,16 @@
-use crate::config::{get_config_path, load_config, save_config, Config};
+use crate::config::{get_config_path, load_config, lock_config, save_config, Config};
+use crate::constants::DEFAULT_EDITOR;
 use crate::error::handle_inquire_error;
-use anyhow::Result;
+use crate::utils::{editor_command, resolve_editor};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use colored::*;
-use inquire::{Confirm, Select};
+use inquire::{Confirm, Select, Text};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 #[derive(clap::ValueEnum, Clone)]
 pub enum ExportFormat {
@@ -17,11 +19,23 @@ pub enum ExportFormat {
 }
 
 // Valid configuration keys for validation
-const VALID_KEYS: &[&str] = &[
+pub(crate) const VALID_KEYS: &[&str] = &[
     "version",
     "config_path",
     "settings.show_git_status",
     "settings.recent_projects_limit",
+    "settings.gitlab_url",
+    "settings.editor",
+    "settings.default_ide",
+    "settings.extension_sandbox",
+    "settings.tag_rules_file",
+    "settings.backup_retention_policy",
+    "settings.backup_size_warning_mb",
+    "settings.allow_unicode_paths",
+    "settings.tag_naming_policy",
+    "settings.default_name_strategy",
+    "settings.registry_cache_ttl_hours",
+    "settings.auto_detect_language",
 ];
 
 pub async fn handle_show() -> Result<()> {
@@ -67,6 +81,11 @@ pub async fn handle_show() -> Result<()> {
         &format!("{} projects", config.settings.recent_projects_limit),
         max_width,
     );
+    print_config_row(
+        "Editor",
+        &resolve_editor(config.settings.editor.as_deref()),
+        max_width,
+    );
 
     println!("└─────────────────────┴────────────────────────────────┘");
     println!();
@@ -81,13 +100,14 @@ pub async fn handle_show() -> Result<()> {
 pub async fn handle_edit() -> Result<()> {
     let config_path = get_config_path()?;
 
-    // Determine editor to use from environment
-    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "nano".to_string());
+    // The config may be invalid (that is often why it is being edited), so fall back to the environment
+    let configured_editor = load_config().await.ok().and_then(|config| config.settings.editor);
+    let editor = resolve_editor(configured_editor.as_deref());
 
     println!("🔧 Opening config file in {}...", editor.cyan());
 
     // Open the config file in editor
-    let status = Command::new(&editor).arg(&config_path).status()?;
+    let status = editor_command(&editor).arg(&config_path).status()?;
 
     if !status.success() {
         return Err(anyhow::anyhow!("Editor exited with non-zero status"));
@@ -144,6 +164,23 @@ pub async fn handle_validate() -> Result<()> {
                 );
             }
 
+            // Same directory registered under several names
+            let duplicates = config.find_duplicate_paths();
+            if duplicates.is_empty() {
+                println!("  - Project paths: {} no duplicates", "✓".green());
+            } else {
+                println!(
+                    "  - Project paths: {} {} path(s) registered more than once",
+                    "⚠️".yellow(),
+                    duplicates.len()
+                );
+                for (path, projects) in &duplicates {
+                    let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+                    println!("      {} → {}", path.display(), names.join(", "));
+                }
+                println!("    💡 Review them with 'pm list --show-duplicates'");
+            }
+
             println!();
             println!(
                 "📁 Config file: {}",
@@ -167,6 +204,19 @@ pub async fn handle_validate() -> Result<()> {
     }
 }
 
+/// Print the config file's JSON Schema (as JSON or YAML)
+pub fn handle_schema(format: &ExportFormat) -> Result<()> {
+    let schema = crate::config::config_schema();
+
+    let content = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&schema)?,
+        ExportFormat::Yaml => serde_yaml::to_string(&schema)?,
+    };
+    println!("{}", content.trim_end());
+
+    Ok(())
+}
+
 pub async fn handle_reset() -> Result<()> {
     let config_path = get_config_path()?;
 
@@ -259,6 +309,7 @@ pub async fn handle_set(key: &str, value: &str) -> Result<()> {
         return Err(anyhow::anyhow!("Invalid key: {}", key));
     }
 
+    let _lock = lock_config().await?;
     let mut config = load_config().await?;
 
     // Convert config to YAML Value for manipulation
@@ -309,6 +360,18 @@ pub async fn handle_list() -> Result<()> {
     println!("{}", "⚙️  Advanced Settings:".yellow().bold());
     list_config_key(&config_value, "settings.show_git_status", "boolean");
     list_config_key(&config_value, "settings.recent_projects_limit", "integer");
+    list_config_key(&config_value, "settings.gitlab_url", "url");
+    list_config_key(&config_value, "settings.editor", "string");
+    list_config_key(&config_value, "settings.default_ide", "ide");
+    list_config_key(&config_value, "settings.extension_sandbox", "boolean");
+    list_config_key(&config_value, "settings.tag_rules_file", "path");
+    list_config_key(&config_value, "settings.backup_retention_policy", "policy");
+    list_config_key(&config_value, "settings.backup_size_warning_mb", "integer");
+    list_config_key(&config_value, "settings.allow_unicode_paths", "boolean");
+    list_config_key(&config_value, "settings.tag_naming_policy", "policy");
+    list_config_key(&config_value, "settings.default_name_strategy", "strategy");
+    list_config_key(&config_value, "settings.registry_cache_ttl_hours", "integer");
+    list_config_key(&config_value, "settings.auto_detect_language", "boolean");
 
     println!();
     println!(
@@ -405,7 +468,10 @@ fn set_nested_value(value: &mut Value, path: &[&str], new_value: Value) -> Resul
 
 fn parse_value_with_validation(key: &str, value: &str) -> Result<Value> {
     match key {
-        "settings.show_git_status" => {
+        "settings.show_git_status"
+        | "settings.extension_sandbox"
+        | "settings.allow_unicode_paths"
+        | "settings.auto_detect_language" => {
             match value.to_lowercase().as_str() {
                 "true" | "1" | "yes" | "on" => Ok(Value::Bool(true)),
                 "false" | "0" | "no" | "off" => Ok(Value::Bool(false)),
@@ -425,7 +491,51 @@ fn parse_value_with_validation(key: &str, value: &str) -> Result<Value> {
             }
             Ok(Value::Number(num.into()))
         }
-        "config_path" => {
+        "settings.gitlab_url" => {
+            if !value.starts_with("http://") && !value.starts_with("https://") {
+                return Err(anyhow::anyhow!(
+                    "GitLab URL must start with http:// or https://"
+                ));
+            }
+            Ok(Value::String(value.trim_end_matches('/').to_string()))
+        }
+        "settings.default_ide" => {
+            let ide: crate::utils::Ide = value.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            Ok(serde_yaml::to_value(ide)?)
+        }
+        "settings.default_name_strategy" => {
+            let strategy: crate::utils::NameStrategy =
+                value.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            Ok(serde_yaml::to_value(strategy)?)
+        }
+        "settings.tag_naming_policy" => {
+            let policy: crate::validation::TagNamingPolicy =
+                value.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+            Ok(serde_yaml::to_value(policy)?)
+        }
+        "settings.registry_cache_ttl_hours" => {
+            let hours: u64 = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number format"))?;
+            if hours == 0 {
+                return Err(anyhow::anyhow!("Registry cache TTL must be at least 1 hour"));
+            }
+            Ok(Value::Number(hours.into()))
+        }
+        "settings.backup_size_warning_mb" => {
+            let mb: u64 = value
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid number format"))?;
+            if mb == 0 {
+                return Err(anyhow::anyhow!("Backup size warning must be at least 1 MB"));
+            }
+            Ok(Value::Number(mb.into()))
+        }
+        "settings.backup_retention_policy" => {
+            let policy: crate::backup::RetentionPolicy = value.parse()?;
+            Ok(Value::String(policy.to_string()))
+        }
+        "config_path" | "settings.tag_rules_file" => {
             let path = PathBuf::from(shellexpand::tilde(value).into_owned());
             Ok(Value::String(path.display().to_string()))
         }
@@ -527,6 +637,8 @@ struct BackupMetadata {
     created_at: DateTime<Utc>,
     description: Option<String>,
     config_version: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -580,33 +692,153 @@ fn config_hash(config: &Config) -> Result<String> {
     Ok(format!("{:x}", md5::compute(config_yaml.as_bytes())))
 }
 
+/// File extension of plain config backups
+const BACKUP_EXTENSION: &str = ".yml";
+/// File extension of gzip-compressed config backups
+const COMPRESSED_BACKUP_EXTENSION: &str = ".yml.gz";
+
+/// Backup name for a backup file path (`name.yml` or `name.yml.gz`), if it is one
+fn backup_name_from_path(path: &Path) -> Option<&str> {
+    let file_name = path.file_name()?.to_str()?;
+    file_name
+        .strip_suffix(COMPRESSED_BACKUP_EXTENSION)
+        .or_else(|| file_name.strip_suffix(BACKUP_EXTENSION))
+}
+
+fn is_compressed_backup(path: &Path) -> bool {
+    path.to_string_lossy().ends_with(COMPRESSED_BACKUP_EXTENSION)
+}
+
+/// Path of the backup called `name`, compressed or not
+fn find_backup_file(name: &str) -> Result<Option<PathBuf>> {
+    let backups_dir = get_backups_dir()?;
+    Ok([BACKUP_EXTENSION, COMPRESSED_BACKUP_EXTENSION]
+        .iter()
+        .map(|extension| backups_dir.join(format!("{}{}", name, extension)))
+        .find(|path| path.exists()))
+}
+
+/// All backup files in the backups directory, compressed or not
+fn list_backup_files() -> Result<Vec<PathBuf>> {
+    let backups_dir = get_backups_dir()?;
+    if !backups_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&backups_dir)? {
+        let path = entry?.path();
+        if path.is_file() && backup_name_from_path(&path).is_some() {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Backup file contents, decompressing `.yml.gz` files
+fn read_backup_content(path: &Path) -> Result<String> {
+    if !is_compressed_backup(path) {
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    let mut content = String::new();
+    flate2::read::GzDecoder::new(fs::File::open(path)?)
+        .read_to_string(&mut content)
+        .with_context(|| format!("Failed to decompress backup: {}", path.display()))?;
+    Ok(content)
+}
+
+fn read_backup(path: &Path) -> Result<(BackupMetadata, Config)> {
+    let content = read_backup_content(path)?;
+    serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse backup: {}", path.display()))
+}
+
+fn write_compressed_backup(path: &Path, content: &str) -> Result<()> {
+    let mut encoder = flate2::write::GzEncoder::new(fs::File::create(path)?, flate2::Compression::best());
+    encoder.write_all(content.as_bytes())?;
+    encoder.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// Sizes of the config backups, for `pm backup status`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConfigBackupSizes {
+    pub count: usize,
+    pub compressed_count: usize,
+    /// Total size with compressed backups counted decompressed
+    pub uncompressed_bytes: u64,
+    /// Total size on disk
+    pub stored_bytes: u64,
+}
+
+pub fn config_backup_sizes() -> Result<ConfigBackupSizes> {
+    let mut sizes = ConfigBackupSizes::default();
+    for path in list_backup_files()? {
+        let stored = fs::metadata(&path)?.len();
+        sizes.count += 1;
+        sizes.stored_bytes += stored;
+        if is_compressed_backup(&path) {
+            sizes.compressed_count += 1;
+            sizes.uncompressed_bytes += read_backup_content(&path).map_or(stored, |content| content.len() as u64);
+        } else {
+            sizes.uncompressed_bytes += stored;
+        }
+    }
+    Ok(sizes)
+}
+
+/// Name and on-disk size of each config backup file, for `pm backup status --size`
+pub fn config_backup_file_sizes() -> Result<Vec<(String, u64)>> {
+    let mut sizes = Vec::new();
+    for path in list_backup_files()? {
+        if let Some(name) = backup_name_from_path(&path) {
+            sizes.push((name.to_string(), fs::metadata(&path)?.len()));
+        }
+    }
+    sizes.sort();
+    Ok(sizes)
+}
+
 // =====================================================
 // Backup Commands
 // =====================================================
 
-pub async fn handle_backup_create(name: Option<&str>) -> Result<()> {
+pub async fn handle_backup_create(
+    name: Option<&str>,
+    description: Option<&str>,
+    tags: &[String],
+    compress: bool,
+) -> Result<()> {
+    let tags = normalize_backup_tags(tags)?;
     let config = load_config().await?;
     let backups_dir = get_backups_dir()?;
     ensure_dir_exists(&backups_dir)?;
 
     let backup_name = name.unwrap_or(&generate_backup_name()).to_string();
-    let backup_file = backups_dir.join(format!("{}.yml", backup_name));
-
-    if backup_file.exists() {
+    if find_backup_file(&backup_name)?.is_some() {
         return Err(anyhow::anyhow!("Backup '{}' already exists", backup_name));
     }
 
+    let extension = if compress { COMPRESSED_BACKUP_EXTENSION } else { BACKUP_EXTENSION };
+    let backup_file = backups_dir.join(format!("{}{}", backup_name, extension));
+
     // Create backup metadata
     let metadata = BackupMetadata {
         name: backup_name.clone(),
         created_at: Utc::now(),
-        description: None,
+        description: description.map(str::to_string),
         config_version: config.version.clone(),
+        tags,
     };
 
     // Save config and metadata
-    let backup_data = serde_yaml::to_string(&(metadata, config))?;
-    fs::write(&backup_file, backup_data)?;
+    let backup_data = serde_yaml::to_string(&(&metadata, config))?;
+    if compress {
+        write_compressed_backup(&backup_file, &backup_data)?;
+    } else {
+        fs::write(&backup_file, backup_data)?;
+    }
 
     // Add to history
     add_to_history(
@@ -616,6 +848,12 @@ pub async fn handle_backup_create(name: Option<&str>) -> Result<()> {
     .await?;
 
     println!("✅ Created backup: {}", backup_name.green());
+    if let Some(description) = description {
+        println!("📝 {}", description);
+    }
+    if !metadata.tags.is_empty() {
+        println!("🏷️  {}", metadata.tags.join(", "));
+    }
     println!(
         "📁 Location: {}",
         backup_file.display().to_string().bright_black()
@@ -625,17 +863,21 @@ pub async fn handle_backup_create(name: Option<&str>) -> Result<()> {
 }
 
 pub async fn handle_backup_restore(name: &str) -> Result<()> {
-    let backups_dir = get_backups_dir()?;
-    let backup_file = backups_dir.join(format!("{}.yml", name));
-
-    if !backup_file.exists() {
+    let Some(backup_file) = find_backup_file(name)? else {
         return Err(anyhow::anyhow!("Backup '{}' not found", name));
-    }
+    };
 
     // Load backup
-    let backup_content = fs::read_to_string(&backup_file)?;
-    let (metadata, backup_config): (BackupMetadata, Config) =
-        serde_yaml::from_str(&backup_content)?;
+    let (metadata, backup_config) = read_backup(&backup_file)?;
+
+    println!(
+        "📦 Backup '{}' from {}",
+        name.cyan(),
+        metadata.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    if let Some(description) = &metadata.description {
+        println!("📝 {}", description);
+    }
 
     // Confirm restore
     let confirm = handle_inquire_error(Confirm::new(&format!(
@@ -651,10 +893,12 @@ pub async fn handle_backup_restore(name: &str) -> Result<()> {
     }
 
     // Create automatic backup before restore
-    handle_backup_create(Some(&format!(
-        "auto_before_restore_{}",
-        Utc::now().format("%Y%m%d_%H%M%S")
-    )))
+    handle_backup_create(
+        Some(&format!("auto_before_restore_{}", Utc::now().format("%Y%m%d_%H%M%S"))),
+        Some(&format!("Automatic backup before restoring '{}'", name)),
+        &[],
+        false,
+    )
     .await?;
 
     // Restore config
@@ -676,28 +920,32 @@ pub async fn handle_backup_restore(name: &str) -> Result<()> {
     Ok(())
 }
 
-pub async fn handle_backup_list() -> Result<()> {
-    let backups_dir = get_backups_dir()?;
+/// Trimmed, de-duplicated backup tags; empty tags are rejected
+fn normalize_backup_tags(tags: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err(anyhow::anyhow!("Backup tags cannot be empty"));
+        }
+        if !normalized.iter().any(|existing| existing == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    Ok(normalized)
+}
 
-    if !backups_dir.exists() {
+pub async fn handle_backup_list(tag: Option<&str>) -> Result<()> {
+    if !get_backups_dir()?.exists() {
         println!("📦 No backups found");
         return Ok(());
     }
 
     let mut backups = Vec::new();
 
-    for entry in fs::read_dir(&backups_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.extension().and_then(|s| s.to_str()) == Some("yml") {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok((metadata, _)) =
-                    serde_yaml::from_str::<(BackupMetadata, Config)>(&content)
-                {
-                    backups.push(metadata);
-                }
-            }
+    for path in list_backup_files()? {
+        if let Ok((metadata, _)) = read_backup(&path) {
+            backups.push((metadata, is_compressed_backup(&path)));
         }
     }
 
@@ -706,18 +954,27 @@ pub async fn handle_backup_list() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(tag) = tag {
+        backups.retain(|(metadata, _)| metadata.tags.iter().any(|t| t == tag));
+        if backups.is_empty() {
+            println!("📦 No backups tagged '{}'", tag);
+            return Ok(());
+        }
+    }
+
     // Sort by creation date (newest first)
-    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    backups.sort_by(|a, b| b.0.created_at.cmp(&a.0.created_at));
 
     println!("{}", "📦 Configuration Backups".blue().bold());
     println!();
 
-    for backup in backups {
+    for (backup, compressed) in backups {
         let age = format_duration(Utc::now().signed_duration_since(backup.created_at));
         println!(
-            "  {} {}",
+            "  {} {}{}",
             backup.name.cyan().bold(),
-            format!("({})", age).bright_black()
+            format!("({})", age).bright_black(),
+            if compressed { " 🗜️" } else { "" }
         );
         println!(
             "    📅 {}",
@@ -726,6 +983,9 @@ pub async fn handle_backup_list() -> Result<()> {
         if let Some(desc) = backup.description {
             println!("    📝 {}", desc);
         }
+        if !backup.tags.is_empty() {
+            println!("    🏷️  {}", backup.tags.join(", "));
+        }
         println!();
     }
 
@@ -739,12 +999,9 @@ pub async fn handle_backup_list() -> Result<()> {
 }
 
 pub async fn handle_backup_delete(name: &str) -> Result<()> {
-    let backups_dir = get_backups_dir()?;
-    let backup_file = backups_dir.join(format!("{}.yml", name));
-
-    if !backup_file.exists() {
+    let Some(backup_file) = find_backup_file(name)? else {
         return Err(anyhow::anyhow!("Backup '{}' not found", name));
-    }
+    };
 
     let confirm = handle_inquire_error(Confirm::new(&format!("Delete backup '{}'? This cannot be undone.", name))
         .with_default(false)
@@ -769,27 +1026,141 @@ pub async fn handle_backup_delete(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Backups to delete when keeping the `keep` most recent ones
+///
+/// Older backups carrying any of the `keep_tagged` tags are never selected.
+fn select_backups_to_clean<'a>(
+    backups: &'a [(BackupMetadata, PathBuf)],
+    keep: usize,
+    keep_tagged: &[String],
+) -> Vec<&'a (BackupMetadata, PathBuf)> {
+    let mut newest_first: Vec<_> = backups.iter().collect();
+    newest_first.sort_by_key(|(metadata, _)| std::cmp::Reverse(metadata.created_at));
+    newest_first
+        .into_iter()
+        .skip(keep)
+        .filter(|(metadata, _)| !metadata.tags.iter().any(|tag| keep_tagged.contains(tag)))
+        .collect()
+}
+
+/// Delete all but the `keep` most recent backups, sparing those tagged with `keep_tagged`
+pub async fn handle_backup_clean(keep: usize, keep_tagged: &[String], force: bool) -> Result<()> {
+    let mut backups = Vec::new();
+    for path in list_backup_files()? {
+        if let Ok((metadata, _)) = read_backup(&path) {
+            backups.push((metadata, path));
+        }
+    }
+
+    let to_remove = select_backups_to_clean(&backups, keep, keep_tagged);
+    if to_remove.is_empty() {
+        println!("📦 No backups to clean (current: {}, keep: {})", backups.len(), keep);
+        return Ok(());
+    }
+
+    println!("🗑️  Found {} backups to clean (keep: {})", to_remove.len(), keep);
+    for (metadata, _) in &to_remove {
+        let age = format_duration(Utc::now().signed_duration_since(metadata.created_at));
+        println!("   📦 {} ({})", metadata.name, age);
+    }
+
+    if !force {
+        let confirmed = handle_inquire_error(
+            Confirm::new(&format!("Delete {} old backups?", to_remove.len()))
+                .with_default(false)
+                .prompt(),
+        )?;
+        if !confirmed {
+            println!("🚫 Backup cleanup cancelled");
+            return Ok(());
+        }
+    }
+
+    for (metadata, path) in &to_remove {
+        fs::remove_file(path)?;
+        add_to_history(
+            &format!("backup_delete:{}", metadata.name),
+            &format!("Deleted backup '{}'", metadata.name),
+        )
+        .await?;
+    }
+
+    println!("✅ Deleted {} backup(s)", to_remove.len());
+    Ok(())
+}
+
+/// Gzip one backup, or every uncompressed backup when `name` is `None`
+pub async fn handle_backup_compress(name: Option<&str>) -> Result<()> {
+    let targets: Vec<PathBuf> = match name {
+        Some(name) => match find_backup_file(name)? {
+            Some(path) if is_compressed_backup(&path) => {
+                println!("🗜️  Backup '{}' is already compressed", name);
+                return Ok(());
+            }
+            Some(path) => vec![path],
+            None => return Err(anyhow::anyhow!("Backup '{}' not found", name)),
+        },
+        None => list_backup_files()?
+            .into_iter()
+            .filter(|path| !is_compressed_backup(path))
+            .collect(),
+    };
+
+    if targets.is_empty() {
+        println!("📦 No uncompressed backups found");
+        return Ok(());
+    }
+
+    let (mut before, mut after) = (0u64, 0u64);
+    for path in &targets {
+        let backup_name = backup_name_from_path(path).unwrap_or_default();
+        let content = fs::read_to_string(path)?;
+        let compressed_path = path.with_file_name(format!("{}{}", backup_name, COMPRESSED_BACKUP_EXTENSION));
+        write_compressed_backup(&compressed_path, &content)?;
+        fs::remove_file(path)?;
+
+        let compressed_size = fs::metadata(&compressed_path)?.len();
+        before += content.len() as u64;
+        after += compressed_size;
+        println!(
+            "🗜️  {}: {} → {}",
+            backup_name.cyan(),
+            crate::utils::format_size(content.len() as u64),
+            crate::utils::format_size(compressed_size)
+        );
+    }
+
+    println!(
+        "✅ Compressed {} backup(s): {} → {}",
+        targets.len(),
+        crate::utils::format_size(before),
+        crate::utils::format_size(after)
+    );
+
+    Ok(())
+}
+
 // =====================================================
 // Template Commands
 // =====================================================
 
-pub async fn handle_template_list() -> Result<()> {
-    let templates_dir = get_templates_dir()?;
-
-    // Show built-in templates first
-    println!("{}", "📋 Configuration Templates".blue().bold());
-    println!();
-    println!("{}", "🏭 Built-in Templates:".yellow().bold());
+/// Template metadata for `pm config template list --format json` and `template info`
+#[derive(Serialize, Debug)]
+struct TemplateSummary {
+    name: String,
+    description: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    /// "builtin" or "user"
+    source: &'static str,
+    settings: crate::config::ConfigSettings,
+}
 
-    let builtin_templates = get_builtin_templates();
-    for (name, description) in builtin_templates {
-        println!("  {} - {}", name.cyan().bold(), description);
-    }
+/// User templates saved in the templates directory, sorted by name
+fn load_user_templates() -> Result<Vec<ConfigTemplate>> {
+    let templates_dir = get_templates_dir()?;
+    let mut user_templates = Vec::new();
 
-    // Show user templates
     if templates_dir.exists() {
-        let mut user_templates = Vec::new();
-
         for entry in fs::read_dir(&templates_dir)? {
             let entry = entry?;
             let path = entry.path();
@@ -802,29 +1173,93 @@ pub async fn handle_template_list() -> Result<()> {
                 }
             }
         }
+    }
 
-        if !user_templates.is_empty() {
-            println!();
-            println!("{}", "👤 User Templates:".yellow().bold());
+    user_templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(user_templates)
+}
 
-            user_templates.sort_by(|a, b| a.name.cmp(&b.name));
+/// Look up a built-in or user template by name, returning its metadata and config
+fn find_template(name: &str) -> Result<Option<(TemplateSummary, Config)>> {
+    if let Some((_, description)) = get_builtin_templates().into_iter().find(|(n, _)| *n == name) {
+        let config = builtin_template_config(name)?;
+        let summary = TemplateSummary {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            created_at: None,
+            source: "builtin",
+            settings: config.settings.clone(),
+        };
+        return Ok(Some((summary, config)));
+    }
 
-            for template in user_templates {
-                println!(
-                    "  {} - {}",
-                    template.name.cyan().bold(),
-                    template
-                        .description
-                        .unwrap_or_else(|| "No description".to_string())
-                );
-                println!("    📅 Created: {}", template.created_at.format("%Y-%m-%d"));
+    let template_file = get_templates_dir()?.join(format!("{}.yml", name));
+    if !template_file.exists() {
+        return Ok(None);
+    }
+    let template: ConfigTemplate = serde_yaml::from_str(&fs::read_to_string(&template_file)?)?;
+    let summary = TemplateSummary {
+        name: template.name.clone(),
+        description: template.description.clone(),
+        created_at: Some(template.created_at),
+        source: "user",
+        settings: template.config.settings.clone(),
+    };
+    Ok(Some((summary, template.config)))
+}
+
+pub async fn handle_template_list(format: &str) -> Result<()> {
+    let user_templates = load_user_templates()?;
+
+    if format == "json" {
+        let mut summaries = Vec::new();
+        for (name, _) in get_builtin_templates() {
+            if let Some((summary, _)) = find_template(name)? {
+                summaries.push(summary);
             }
         }
+        summaries.extend(user_templates.into_iter().map(|template| TemplateSummary {
+            name: template.name,
+            description: template.description,
+            created_at: Some(template.created_at),
+            source: "user",
+            settings: template.config.settings,
+        }));
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+        return Ok(());
+    }
+
+    // Show built-in templates first
+    println!("{}", "📋 Configuration Templates".blue().bold());
+    println!();
+    println!("{}", "🏭 Built-in Templates:".yellow().bold());
+
+    let builtin_templates = get_builtin_templates();
+    for (name, description) in builtin_templates {
+        println!("  {} - {}", name.cyan().bold(), description);
+    }
+
+    // Show user templates
+    if !user_templates.is_empty() {
+        println!();
+        println!("{}", "👤 User Templates:".yellow().bold());
+
+        for template in user_templates {
+            println!(
+                "  {} - {}",
+                template.name.cyan().bold(),
+                template
+                    .description
+                    .unwrap_or_else(|| "No description".to_string())
+            );
+            println!("    📅 Created: {}", template.created_at.format("%Y-%m-%d"));
+        }
     }
 
     println!();
     println!(
-        "💡 Use: {} | {}",
+        "💡 Use: {} | {} | {}",
+        "pm config template info <name>".cyan(),
         "pm config template apply <name>".cyan(),
         "pm config template save <name>".cyan()
     );
@@ -832,6 +1267,56 @@ pub async fn handle_template_list() -> Result<()> {
     Ok(())
 }
 
+/// Show a template's settings and what applying it would change
+pub async fn handle_template_info(name: &str) -> Result<()> {
+    let Some((summary, template_config)) = find_template(name)? else {
+        return Err(anyhow::anyhow!("Template '{}' not found", name));
+    };
+
+    println!("{} {}", "📋 Template:".blue().bold(), summary.name.cyan().bold());
+    println!("  Source:      {}", summary.source);
+    println!(
+        "  Description: {}",
+        summary.description.as_deref().unwrap_or("No description")
+    );
+    if let Some(created_at) = summary.created_at {
+        println!("  Created:     {}", created_at.format("%Y-%m-%d %H:%M"));
+    }
+
+    println!();
+    println!("{}", "⚙️  Settings:".yellow().bold());
+    let settings_value = serde_yaml::to_value(&summary.settings)?;
+    if let Value::Mapping(settings) = settings_value {
+        for (key, value) in settings {
+            println!(
+                "  settings.{}: {}",
+                format_value_for_display(&key),
+                format_value_for_display(&value).green()
+            );
+        }
+    }
+    println!("  projects: {}", template_config.projects.len());
+
+    println!();
+    println!("{}", "🔍 Changes if applied:".yellow().bold());
+    let current_config = load_config().await?;
+    if serde_yaml::to_value(&current_config.settings)? == serde_yaml::to_value(&template_config.settings)? {
+        println!("  No settings changes");
+    } else {
+        show_config_diff(&current_config, &template_config)?;
+    }
+    if current_config.projects.len() != template_config.projects.len() {
+        println!(
+            "  {} {} → {}",
+            "projects:".yellow(),
+            current_config.projects.len().to_string().red(),
+            template_config.projects.len().to_string().green()
+        );
+    }
+
+    Ok(())
+}
+
 pub async fn handle_template_apply(name: &str) -> Result<()> {
     // Check built-in templates first
     let builtin_templates = get_builtin_templates();
@@ -867,10 +1352,12 @@ pub async fn handle_template_apply(name: &str) -> Result<()> {
     }
 
     // Create backup before applying template
-    handle_backup_create(Some(&format!(
-        "auto_before_template_{}",
-        Utc::now().format("%Y%m%d_%H%M%S")
-    )))
+    handle_backup_create(
+        Some(&format!("auto_before_template_{}", Utc::now().format("%Y%m%d_%H%M%S"))),
+        Some(&format!("Automatic backup before applying template '{}'", name)),
+        &[],
+        false,
+    )
     .await?;
 
     // Apply template
@@ -980,14 +1467,18 @@ fn get_builtin_templates() -> Vec<(&'static str, &'static str)> {
     ]
 }
 
+fn builtin_template_config(name: &str) -> Result<Config> {
+    match name {
+        "minimal" => Ok(create_minimal_config()),
+        "developer" => Ok(create_developer_config()),
+        "team" => Ok(create_team_config()),
+        "enterprise" => Ok(create_enterprise_config()),
+        _ => Err(anyhow::anyhow!("Unknown built-in template: {}", name)),
+    }
+}
+
 async fn apply_builtin_template(name: &str) -> Result<()> {
-    let config = match name {
-        "minimal" => create_minimal_config(),
-        "developer" => create_developer_config(),
-        "team" => create_team_config(),
-        "enterprise" => create_enterprise_config(),
-        _ => return Err(anyhow::anyhow!("Unknown built-in template: {}", name)),
-    };
+    let config = builtin_template_config(name)?;
 
     // Confirm application
     let confirm = handle_inquire_error(Confirm::new(&format!(
@@ -1003,10 +1494,12 @@ async fn apply_builtin_template(name: &str) -> Result<()> {
     }
 
     // Create backup before applying template
-    handle_backup_create(Some(&format!(
-        "auto_before_builtin_{}",
-        Utc::now().format("%Y%m%d_%H%M%S")
-    )))
+    handle_backup_create(
+        Some(&format!("auto_before_builtin_{}", Utc::now().format("%Y%m%d_%H%M%S"))),
+        Some(&format!("Automatic backup before applying template '{}'", name)),
+        &[],
+        false,
+    )
     .await?;
 
     // Apply template
@@ -1081,6 +1574,19 @@ pub async fn handle_setup(quick: bool) -> Result<()> {
     let recent_limit = handle_inquire_error(Select::new("Recent projects limit:", recent_limit_options).prompt())?;
     config.settings.recent_projects_limit = recent_limit as u32;
 
+    // Editor (suggest the current $EDITOR)
+    let suggested_editor = std::env::var("EDITOR")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+    let editor = handle_inquire_error(
+        Text::new("Editor command:")
+            .with_default(&suggested_editor)
+            .with_help_message("$VISUAL still takes precedence when set")
+            .prompt(),
+    )?;
+    config.settings.editor = Some(editor.trim().to_string()).filter(|editor| !editor.is_empty());
+
 
     // Save configuration
     save_config(&config).await?;
@@ -1160,10 +1666,12 @@ pub async fn handle_import(file: &Path, force: bool) -> Result<()> {
 
     if !force {
         // Create backup before import
-        handle_backup_create(Some(&format!(
-            "auto_before_import_{}",
-            Utc::now().format("%Y%m%d_%H%M%S")
-        )))
+        handle_backup_create(
+            Some(&format!("auto_before_import_{}", Utc::now().format("%Y%m%d_%H%M%S"))),
+            Some(&format!("Automatic backup before importing {}", file.display())),
+            &[],
+            false,
+        )
         .await?;
 
         let confirm = handle_inquire_error(Confirm::new(&format!(
@@ -1205,15 +1713,11 @@ pub async fn handle_diff(backup_name: Option<&str>) -> Result<()> {
 
     let (backup_config, backup_display_name) = if let Some(name) = backup_name {
         // Compare with specific backup
-        let backups_dir = get_backups_dir()?;
-        let backup_file = backups_dir.join(format!("{}.yml", name));
-
-        if !backup_file.exists() {
+        let Some(backup_file) = find_backup_file(name)? else {
             return Err(anyhow::anyhow!("Backup '{}' not found", name));
-        }
+        };
 
-        let backup_content = fs::read_to_string(&backup_file)?;
-        let (_, backup_config): (BackupMetadata, Config) = serde_yaml::from_str(&backup_content)?;
+        let (_, backup_config) = read_backup(&backup_file)?;
         (backup_config, name.to_string())
     } else {
         // Compare with latest backup
@@ -1227,20 +1731,11 @@ pub async fn handle_diff(backup_name: Option<&str>) -> Result<()> {
         let mut latest_backup = None;
         let mut latest_time = DateTime::from_timestamp(0, 0).unwrap();
 
-        for entry in fs::read_dir(&backups_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.extension().and_then(|s| s.to_str()) == Some("yml") {
-                if let Ok(content) = fs::read_to_string(&path) {
-                    if let Ok((metadata, config)) =
-                        serde_yaml::from_str::<(BackupMetadata, Config)>(&content)
-                    {
-                        if metadata.created_at > latest_time {
-                            latest_time = metadata.created_at;
-                            latest_backup = Some((config, metadata.name));
-                        }
-                    }
+        for path in list_backup_files()? {
+            if let Ok((metadata, config)) = read_backup(&path) {
+                if metadata.created_at > latest_time {
+                    latest_time = metadata.created_at;
+                    latest_backup = Some((config, metadata.name));
                 }
             }
         }
@@ -1311,7 +1806,7 @@ pub async fn handle_history(limit: usize) -> Result<()> {
 // Helper Functions
 // =====================================================
 
-async fn add_to_history(action: &str, details: &str) -> Result<()> {
+pub(crate) async fn add_to_history(action: &str, details: &str) -> Result<()> {
     let history_file = get_history_file()?;
     let config = load_config().await?;
 
@@ -1348,7 +1843,7 @@ async fn add_to_history(action: &str, details: &str) -> Result<()> {
     Ok(())
 }
 
-fn show_config_diff(old: &Config, new: &Config) -> Result<()> {
+pub(crate) fn show_config_diff(old: &Config, new: &Config) -> Result<()> {
     // Simple field-by-field comparison
 
 
@@ -1370,6 +1865,119 @@ fn show_config_diff(old: &Config, new: &Config) -> Result<()> {
         );
     }
 
+    if old.settings.extension_sandbox != new.settings.extension_sandbox {
+        println!(
+            "  {} {} → {}",
+            "settings.extension_sandbox:".yellow(),
+            old.settings.extension_sandbox.to_string().red(),
+            new.settings.extension_sandbox.to_string().green()
+        );
+    }
+
+    if old.settings.allow_unicode_paths != new.settings.allow_unicode_paths {
+        println!(
+            "  {} {} → {}",
+            "settings.allow_unicode_paths:".yellow(),
+            old.settings.allow_unicode_paths.to_string().red(),
+            new.settings.allow_unicode_paths.to_string().green()
+        );
+    }
+
+    if old.settings.auto_detect_language != new.settings.auto_detect_language {
+        println!(
+            "  {} {} → {}",
+            "settings.auto_detect_language:".yellow(),
+            old.settings.auto_detect_language.to_string().red(),
+            new.settings.auto_detect_language.to_string().green()
+        );
+    }
+
+    if old.settings.registry_cache_ttl_hours != new.settings.registry_cache_ttl_hours {
+        println!(
+            "  {} {} → {}",
+            "settings.registry_cache_ttl_hours:".yellow(),
+            old.settings.registry_cache_ttl_hours.to_string().red(),
+            new.settings.registry_cache_ttl_hours.to_string().green()
+        );
+    }
+
+    if old.settings.default_name_strategy != new.settings.default_name_strategy {
+        println!(
+            "  {} {} → {}",
+            "settings.default_name_strategy:".yellow(),
+            old.settings.default_name_strategy.to_string().red(),
+            new.settings.default_name_strategy.to_string().green()
+        );
+    }
+
+    if old.settings.tag_naming_policy != new.settings.tag_naming_policy {
+        println!(
+            "  {} {} → {}",
+            "settings.tag_naming_policy:".yellow(),
+            old.settings.tag_naming_policy.to_string().red(),
+            new.settings.tag_naming_policy.to_string().green()
+        );
+    }
+
+    if old.settings.editor != new.settings.editor {
+        println!(
+            "  {} {} → {}",
+            "settings.editor:".yellow(),
+            old.settings.editor.as_deref().unwrap_or("not set").red(),
+            new.settings.editor.as_deref().unwrap_or("not set").green()
+        );
+    }
+
+    if old.settings.default_ide != new.settings.default_ide {
+        let ide_name = |ide: Option<crate::utils::Ide>| ide.map_or("not set".to_string(), |ide| ide.to_string());
+        println!(
+            "  {} {} → {}",
+            "settings.default_ide:".yellow(),
+            ide_name(old.settings.default_ide).red(),
+            ide_name(new.settings.default_ide).green()
+        );
+    }
+
+    if old.settings.tag_rules_file != new.settings.tag_rules_file {
+        println!(
+            "  {} {} → {}",
+            "settings.tag_rules_file:".yellow(),
+            old.settings.tag_rules_file.as_deref().unwrap_or("not set").red(),
+            new.settings.tag_rules_file.as_deref().unwrap_or("not set").green()
+        );
+    }
+
+    if old.settings.backup_retention_policy != new.settings.backup_retention_policy {
+        let display = |policy: Option<crate::backup::RetentionPolicy>| {
+            policy.map_or_else(|| "not set".to_string(), |policy| policy.to_string())
+        };
+        println!(
+            "  {} {} → {}",
+            "settings.backup_retention_policy:".yellow(),
+            display(old.settings.backup_retention_policy).red(),
+            display(new.settings.backup_retention_policy).green()
+        );
+    }
+
+    if old.settings.backup_size_warning_mb != new.settings.backup_size_warning_mb {
+        let display = |mb: Option<u64>| mb.map_or_else(|| "not set".to_string(), |mb| format!("{} MB", mb));
+        println!(
+            "  {} {} → {}",
+            "settings.backup_size_warning_mb:".yellow(),
+            display(old.settings.backup_size_warning_mb).red(),
+            display(new.settings.backup_size_warning_mb).green()
+        );
+    }
+
+    if old.settings.gitlab_url != new.settings.gitlab_url {
+        println!(
+            "  {} {} → {}",
+            "settings.gitlab_url:".yellow(),
+            old.settings.gitlab_url.as_deref().unwrap_or("not set").red(),
+            new.settings.gitlab_url.as_deref().unwrap_or("not set").green()
+        );
+    }
+
     Ok(())
 }
 
@@ -1386,3 +1994,79 @@ fn format_duration(duration: chrono::TimeDelta) -> String {
         format!("{}d ago", total_seconds / 86400)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_size_warning_requires_positive_megabytes() {
+        let key = "settings.backup_size_warning_mb";
+        assert_eq!(parse_value_with_validation(key, "500").unwrap(), Value::Number(500.into()));
+        assert!(parse_value_with_validation(key, "0").is_err());
+        assert!(parse_value_with_validation(key, "lots").is_err());
+    }
+
+    #[test]
+    fn test_compressed_backup_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("nightly.yml.gz");
+        let content = "name: nightly\n".repeat(100);
+
+        write_compressed_backup(&path, &content).unwrap();
+
+        assert!(is_compressed_backup(&path));
+        assert!(fs::metadata(&path).unwrap().len() < content.len() as u64);
+        assert_eq!(read_backup_content(&path).unwrap(), content);
+        assert_eq!(backup_name_from_path(&path), Some("nightly"));
+        assert_eq!(backup_name_from_path(Path::new("/b/nightly.yml")), Some("nightly"));
+        assert_eq!(backup_name_from_path(Path::new("/b/metadata.json")), None);
+    }
+
+    #[test]
+    fn test_clean_spares_backups_with_kept_tags() {
+        let backup = |name: &str, days_ago: i64, tags: &[&str]| {
+            (
+                BackupMetadata {
+                    name: name.to_string(),
+                    created_at: Utc::now() - chrono::Duration::days(days_ago),
+                    description: None,
+                    config_version: "0.1.1".to_string(),
+                    tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                },
+                PathBuf::from(format!("/b/{}.yml", name)),
+            )
+        };
+        let backups = vec![
+            backup("oldest", 30, &["pre-upgrade"]),
+            backup("old", 20, &["weekly"]),
+            backup("newer", 10, &[]),
+            backup("newest", 1, &[]),
+        ];
+
+        let names = |selected: Vec<&(BackupMetadata, PathBuf)>| {
+            selected.iter().map(|(metadata, _)| metadata.name.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(names(select_backups_to_clean(&backups, 2, &[])), ["old", "oldest"]);
+        assert_eq!(
+            names(select_backups_to_clean(&backups, 1, &["pre-upgrade".to_string()])),
+            ["newer", "old"]
+        );
+        assert!(select_backups_to_clean(&backups, 4, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_backup_description_round_trip() {
+        let metadata = BackupMetadata {
+            name: "stable".to_string(),
+            created_at: Utc::now(),
+            description: Some("stable config: for new team members".to_string()),
+            config_version: "0.1.1".to_string(),
+            tags: Vec::new(),
+        };
+
+        let yaml = serde_yaml::to_string(&metadata).unwrap();
+        let parsed: BackupMetadata = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.description, metadata.description);
+    }
+}
@@ -0,0 +1,329 @@
+use crate::commands::project::{new_project, project_name_from_path, register_projects};
+use crate::config::load_config;
+use crate::display::display_warning;
+use crate::error::handle_inquire_error;
+use crate::extensions::hooks::{dispatch_hook, HookContext, HookEvent};
+use crate::utils::{canonical_path, path_comparison_key};
+use crate::validation::allowed_generated_tags;
+use anyhow::{Context, Result};
+use colored::*;
+use inquire::Confirm;
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Tag added to every project imported from a JetBrains IDE
+const JETBRAINS_TAG: &str = "jetbrains";
+
+/// A `recentProjects.xml` file of one installed JetBrains product
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JetBrainsProduct {
+    /// Config directory name, e.g. `GoLand2024.1`
+    pub directory_name: String,
+    pub recent_projects_file: PathBuf,
+}
+
+impl JetBrainsProduct {
+    /// Product name without the version, e.g. `GoLand`
+    pub fn name(&self) -> &str {
+        let end = self
+            .directory_name
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(self.directory_name.len());
+        &self.directory_name[..end]
+    }
+}
+
+/// JetBrains config directory: `~/.config/JetBrains` on Linux,
+/// `~/Library/Application Support/JetBrains` on macOS, `%APPDATA%\JetBrains` on Windows
+fn jetbrains_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("JetBrains"))
+}
+
+/// Installed products with a recent projects file, optionally limited to names starting with `product`
+pub fn find_jetbrains_products(config_dir: &Path, product: Option<&str>) -> Vec<JetBrainsProduct> {
+    let Ok(entries) = fs::read_dir(config_dir) else {
+        return Vec::new();
+    };
+    let product = product.map(str::to_lowercase);
+
+    let mut products: Vec<JetBrainsProduct> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let directory_name = entry.file_name().to_str()?.to_string();
+            if product
+                .as_deref()
+                .is_some_and(|product| !directory_name.to_lowercase().starts_with(product))
+            {
+                return None;
+            }
+            // Current versions keep it under options/, very old ones at the top level
+            let recent_projects_file = [
+                entry.path().join("options").join("recentProjects.xml"),
+                entry.path().join("recentProjects.xml"),
+            ]
+            .into_iter()
+            .find(|path| path.is_file())?;
+            Some(JetBrainsProduct {
+                directory_name,
+                recent_projects_file,
+            })
+        })
+        .collect();
+    products.sort_by(|a, b| a.directory_name.cmp(&b.directory_name));
+    products
+}
+
+/// Project paths listed in a JetBrains `recentProjects.xml`
+///
+/// Reads the `additionalInfo` map keys (current format) and the `recentPaths`
+/// list (older format). `$USER_HOME$` is expanded to `home`; paths using other
+/// path macros are skipped.
+pub fn parse_recent_projects(xml: &str, home: &Path) -> Result<Vec<PathBuf>> {
+    let mut reader = Reader::from_str(xml);
+    // `name` of each enclosing <option> element (None for other elements)
+    let mut option_stack: Vec<Option<String>> = Vec::new();
+    let mut paths: Vec<PathBuf> = Vec::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .context("Failed to parse recentProjects.xml")?;
+        let (element, is_start) = match &event {
+            Event::Start(element) => (element, true),
+            Event::Empty(element) => (element, false),
+            Event::End(_) => {
+                option_stack.pop();
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        let enclosing_option = option_stack.iter().rev().flatten().next().map(String::as_str);
+        let raw_path = match (element.name().as_ref(), enclosing_option) {
+            (b"entry", Some("additionalInfo")) => attribute(element, "key")?,
+            (b"option", Some("recentPaths")) => attribute(element, "value")?,
+            _ => None,
+        };
+        if let Some(path) = raw_path.and_then(|raw| expand_path_macros(&raw, home)) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+
+        if is_start {
+            let option_name = match element.name().as_ref() {
+                b"option" => attribute(element, "name")?,
+                _ => None,
+            };
+            option_stack.push(option_name);
+        }
+    }
+
+    Ok(paths)
+}
+
+fn attribute(element: &quick_xml::events::BytesStart, name: &str) -> Result<Option<String>> {
+    match element.try_get_attribute(name)? {
+        Some(attribute) => Ok(Some(attribute.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+fn expand_path_macros(raw: &str, home: &Path) -> Option<PathBuf> {
+    let path = match raw.strip_prefix("$USER_HOME$") {
+        Some(rest) => format!("{}{}", home.display(), rest),
+        None => raw.to_string(),
+    };
+    if path.contains('$') {
+        return None;
+    }
+    Some(PathBuf::from(path))
+}
+
+/// `pm import jetbrains`: add recently opened JetBrains IDE projects to PM
+pub async fn handle_import_jetbrains(product: Option<&str>, dry_run: bool, yes: bool) -> Result<()> {
+    let Some(config_dir) = jetbrains_config_dir() else {
+        return Err(anyhow::anyhow!("Could not determine the configuration directory"));
+    };
+    let products = find_jetbrains_products(&config_dir, product);
+    if products.is_empty() {
+        match product {
+            Some(product) => println!("❌ No JetBrains product matching '{}' found in {}", product, config_dir.display()),
+            None => println!("❌ No JetBrains products found in {}", config_dir.display()),
+        }
+        return Ok(());
+    }
+
+    let home = dirs::home_dir().unwrap_or_default();
+    // Prompts run against an unlocked snapshot; `register_projects` takes the lock to save
+    let config = load_config().await?;
+    let mut seen: HashSet<PathBuf> = config
+        .projects
+        .values()
+        .map(|p| path_comparison_key(&canonical_path(&p.path)))
+        .collect();
+    let mut names: HashSet<String> = config.projects.values().map(|p| p.name.clone()).collect();
+
+    // (path, product name) of each project to import
+    let mut to_import: Vec<(PathBuf, String)> = Vec::new();
+    let (mut missing, mut tracked) = (0, 0);
+    for product in &products {
+        let xml = fs::read_to_string(&product.recent_projects_file)
+            .with_context(|| format!("Failed to read {}", product.recent_projects_file.display()))?;
+        let paths = match parse_recent_projects(&xml, &home) {
+            Ok(paths) => paths,
+            Err(e) => {
+                display_warning(&format!("{}: {}", product.directory_name, e));
+                continue;
+            }
+        };
+        println!("🔍 {}: {} recent projects", product.directory_name, paths.len());
+
+        for path in paths {
+            if !path.is_dir() {
+                missing += 1;
+                continue;
+            }
+            let path = canonical_path(&path);
+            if !seen.insert(path_comparison_key(&path)) {
+                tracked += 1;
+                continue;
+            }
+            let name = project_name_from_path(&path);
+            if !names.insert(name.clone()) {
+                display_warning(&format!("Skipping {}: a project named '{}' already exists", path.display(), name));
+                continue;
+            }
+            to_import.push((path, product.name().to_string()));
+        }
+    }
+
+    if missing > 0 {
+        println!("   ⏭️  {} no longer exist", missing);
+    }
+    if tracked > 0 {
+        println!("   ⏭️  {} already tracked", tracked);
+    }
+    if to_import.is_empty() {
+        println!("✅ No new projects to import");
+        return Ok(());
+    }
+
+    println!("📦 Found {} new projects:", to_import.len());
+    for (path, product_name) in &to_import {
+        println!(
+            "  {} {} {} {}",
+            if crate::utils::is_git_repository(path) { "🔗" } else { "📁" },
+            project_name_from_path(path),
+            format!("({})", product_name).dimmed(),
+            path.display().to_string().bright_black()
+        );
+    }
+
+    if dry_run {
+        println!("\n💡 Dry run, nothing was added");
+        return Ok(());
+    }
+
+    if !yes {
+        let confirmed = handle_inquire_error(
+            Confirm::new(&format!("Add {} projects?", to_import.len()))
+                .with_default(true)
+                .prompt(),
+        )?;
+        if !confirmed {
+            println!("❌ No projects added");
+            return Ok(());
+        }
+    }
+
+    let mut projects = Vec::new();
+    for (path, product_name) in &to_import {
+        let tags = allowed_generated_tags(
+            [
+                JETBRAINS_TAG.to_string(),
+                format!("{}/{}", JETBRAINS_TAG, product_name.to_lowercase()),
+            ],
+            config.settings.tag_naming_policy,
+        );
+        projects.push(new_project(path, project_name_from_path(path), tags, None));
+    }
+    let added = register_projects(projects).await?;
+
+    println!("✅ Imported {} projects", added.len());
+
+    for project in &added {
+        dispatch_hook(HookEvent::ProjectAdd, &HookContext::for_project(project)).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recent_projects() {
+        let xml = r#"<application>
+  <component name="RecentProjectsManager">
+    <option name="additionalInfo">
+      <map>
+        <entry key="$USER_HOME$/code/api">
+          <value>
+            <RecentProjectMetaInfo frameTitle="api">
+              <option name="projectWorkspaceId" value="abc" />
+            </RecentProjectMetaInfo>
+          </value>
+        </entry>
+        <entry key="/srv/tools &amp; scripts" />
+        <entry key="$APPLICATION_CONFIG_DIR$/scratches" />
+      </map>
+    </option>
+    <option name="recentPaths">
+      <list>
+        <option value="$USER_HOME$/code/api" />
+        <option value="$USER_HOME$/code/legacy" />
+      </list>
+    </option>
+    <option name="lastProjectLocation" value="$USER_HOME$/ignored" />
+  </component>
+</application>"#;
+
+        let paths = parse_recent_projects(xml, Path::new("/home/me")).unwrap();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("/home/me/code/api"),
+                PathBuf::from("/srv/tools & scripts"),
+                PathBuf::from("/home/me/code/legacy"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_jetbrains_products() {
+        let dir = tempfile::tempdir().unwrap();
+        for (product, file) in [
+            ("GoLand2024.1", "options/recentProjects.xml"),
+            ("CLion2023.3", "options/recentProjects.xml"),
+            ("IntelliJIdea2024.1", "options/other.xml"),
+        ] {
+            let path = dir.path().join(product).join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "<application />").unwrap();
+        }
+
+        let products = find_jetbrains_products(dir.path(), None);
+        let names: Vec<_> = products.iter().map(JetBrainsProduct::name).collect();
+        assert_eq!(names, ["CLion", "GoLand"]);
+
+        let goland = find_jetbrains_products(dir.path(), Some("goland"));
+        assert_eq!(goland.len(), 1);
+        assert_eq!(goland[0].directory_name, "GoLand2024.1");
+    }
+}
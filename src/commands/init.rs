@@ -1,11 +1,15 @@
-use crate::backup::{BackupReason, create_backup, add_backup_entry};
-use crate::config::{get_config_path, get_config_dir, save_config, Config, ConfigSettings};
+use crate::backup::{BackupEntry, BackupReason, create_backup, add_backup_entry};
+use crate::config::{
+    config_lock_status, get_config_dir, lock_config, get_config_path, migrate_config_file,
+    migrate_config_value, save_config, Config, ConfigLockStatus, ConfigSettings,
+};
 use crate::constants::*;
 use crate::display::*;
 use crate::error::{handle_inquire_error, PmError};
 use crate::shell_integration;
 use crate::utils;
 use anyhow::Result;
+use colored::Colorize;
 use inquire::{Confirm, Select, Text};
 use std::path::{Path, PathBuf};
 
@@ -35,6 +39,7 @@ pub async fn handle_init(
             println!("📁 Configuration file: {}", config_path.display());
             println!("\n💡 To reinitialize with backup:");
             println!("   pm init --replace   # Backup existing and recreate");
+            println!("   pm init --update    # Migrate to the current config version");
             return Ok(());
         } else if replace {
             // Non-interactive replace mode
@@ -53,6 +58,7 @@ pub async fn handle_init(
                     println!("📁 Configuration file: {}", config_path.display());
                     println!("\n💡 To reinitialize with backup:");
                     println!("   pm init --replace   # Backup existing and recreate");
+                    println!("   pm init --update    # Migrate to the current config version");
                     return Ok(());
                 }
                 ConflictAction::Replace => {
@@ -109,6 +115,18 @@ pub async fn handle_init(
         settings: ConfigSettings {
             show_git_status,
             recent_projects_limit: 10, // default
+            gitlab_url: None,
+            editor: None,
+            default_ide: None,
+            extension_sandbox: false,
+            tag_rules_file: None,
+            backup_retention_policy: None,
+            backup_size_warning_mb: None,
+            allow_unicode_paths: true,
+            tag_naming_policy: Default::default(),
+            default_name_strategy: Default::default(),
+            registry_cache_ttl_hours: crate::extensions::remote::DEFAULT_REGISTRY_CACHE_TTL_HOURS,
+            auto_detect_language: false,
         },
         projects: std::collections::HashMap::new(),
         machine_metadata: std::collections::HashMap::new(),
@@ -135,8 +153,10 @@ pub async fn handle_init(
             backup.files.extend(shell_backup.files);
             backup.shell_changes.extend(shell_backup.shell_changes);
         }
+        let backup_id = backup.id.clone();
         add_backup_entry(backup).await?;
         println!("💾 Backup created successfully");
+        crate::commands::backup::apply_configured_retention_policy(&backup_id).await?;
     }
     
     // Step 7: Development mode setup (for _pm binary)
@@ -152,6 +172,228 @@ pub async fn handle_init(
     Ok(())
 }
 
+/// Report which process holds the config lock (`pm init --force-unlock`)
+///
+/// The lock is released by the OS when its holder exits, so nothing is removed.
+pub async fn handle_init_force_unlock() -> Result<()> {
+    match config_lock_status()? {
+        ConfigLockStatus::Missing | ConfigLockStatus::Free => {
+            display_info("No PM process holds the configuration lock");
+        }
+        ConfigLockStatus::Held(Some(pid)) => {
+            display_warning(&format!(
+                "Configuration lock is held by PM process {}; it is released when that process exits",
+                pid
+            ));
+        }
+        ConfigLockStatus::Held(None) => {
+            display_warning(
+                "Configuration lock is held by another PM process; it is released when that process exits",
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Migrate an existing configuration to the current schema version
+pub async fn handle_init_update() -> Result<()> {
+    let config_path = get_config_path()?;
+    if !config_path.exists() {
+        display_error("PM not initialized", "Configuration file not found");
+        println!("\n💡 Run '{} init' to create a new configuration", utils::get_binary_name());
+        return Err(PmError::InitializationFailed.into());
+    }
+
+    let Some((from_version, applied, backup_id)) = migrate_config_file().await? else {
+        display_success(&format!("Configuration is already up to date (v{})", CONFIG_VERSION));
+        return Ok(());
+    };
+    crate::commands::backup::apply_configured_retention_policy(&backup_id).await?;
+
+    println!("🔄 Migrated configuration v{} → v{}", from_version, CONFIG_VERSION);
+    println!("💾 The previous configuration was backed up");
+    for step in &applied {
+        println!("   ✅ {}", step);
+    }
+    display_success(&format!("Configuration migrated to v{}", CONFIG_VERSION));
+    println!("📄 Configuration file: {}", config_path.display());
+
+    Ok(())
+}
+
+/// Raw GitHub URL of `github:<user>/<repo>/<path>`, or `source` itself, which must use HTTPS
+pub fn resolve_config_url(source: &str) -> Result<reqwest::Url> {
+    let url = match source.strip_prefix("github:") {
+        Some(rest) => {
+            let parts: Vec<&str> = rest.splitn(3, '/').collect();
+            match parts.as_slice() {
+                [user, repo, path] if !user.is_empty() && !repo.is_empty() && !path.is_empty() => {
+                    format!("https://raw.githubusercontent.com/{}/{}/HEAD/{}", user, repo, path)
+                }
+                _ => {
+                    return Err(PmError::ValidationFailed(format!(
+                        "invalid GitHub shorthand '{}', expected github:<user>/<repo>/<path>",
+                        source
+                    ))
+                    .into())
+                }
+            }
+        }
+        None => source.to_string(),
+    };
+
+    let url = reqwest::Url::parse(&url)
+        .map_err(|e| PmError::ValidationFailed(format!("invalid config URL '{}': {}", source, e)))?;
+    if url.scheme() != "https" {
+        return Err(PmError::ValidationFailed(format!(
+            "config URL must use https, got {}://",
+            url.scheme()
+        ))
+        .into());
+    }
+    Ok(url)
+}
+
+/// HTTP client for `pm init --from` that refuses plain-HTTP requests, including redirects
+fn config_http_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .https_only(true)
+        .user_agent("pm-cli")
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?)
+}
+
+/// Download a config file, refusing bodies larger than `MAX_REMOTE_CONFIG_BYTES`
+async fn download_config(client: &reqwest::Client, url: reqwest::Url) -> Result<String> {
+    let mut response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("Failed to download config: {}", response.status()));
+    }
+
+    let too_large = || {
+        PmError::ValidationFailed(format!(
+            "downloaded config is larger than {} bytes",
+            MAX_REMOTE_CONFIG_BYTES
+        ))
+    };
+    if response.content_length().is_some_and(|len| len > MAX_REMOTE_CONFIG_BYTES as u64) {
+        return Err(too_large().into());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_REMOTE_CONFIG_BYTES {
+            return Err(too_large().into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    String::from_utf8(body)
+        .map_err(|_| PmError::ValidationFailed("downloaded config is not valid UTF-8".to_string()).into())
+}
+
+/// Copy the config file saved in `backup` back to `config_path`
+///
+/// Returns false when the backup has no copy of the config file.
+async fn restore_config_from_backup(backup: &BackupEntry, config_path: &Path) -> Result<bool> {
+    let Some(file) = backup
+        .files
+        .iter()
+        .find(|file| file.original_path.file_name() == config_path.file_name())
+    else {
+        return Ok(false);
+    };
+    tokio::fs::copy(&file.backup_path, config_path).await?;
+    Ok(true)
+}
+
+/// `pm init --from`: replace the config with one downloaded from a URL, after showing
+/// what changes and asking for confirmation
+pub async fn handle_init_from(source: &str) -> Result<()> {
+    let url = resolve_config_url(source)?;
+    println!("🌐 Downloading configuration from {}...", url);
+
+    let content = download_config(&config_http_client()?, url.clone()).await?;
+
+    let value: serde_yaml::Value = serde_yaml::from_str(&content)
+        .map_err(|e| PmError::ValidationFailed(format!("downloaded config is not valid YAML: {}", e)))?;
+    let (value, _) = migrate_config_value(value)?;
+    let mut config: Config = serde_yaml::from_value(value)
+        .map_err(|e| PmError::ValidationFailed(format!("downloaded config is invalid: {}", e)))?;
+    config.config_path = get_config_dir()?;
+
+    let config_path = get_config_path()?;
+    let existing = if config_path.exists() {
+        crate::config::load_config().await.ok()
+    } else {
+        None
+    };
+
+    match &existing {
+        Some(current) => {
+            println!("\n📋 Changes to your configuration:");
+            crate::commands::config::show_config_diff(current, &config)?;
+            println!("  {} {} → {}", "projects:".yellow(), current.projects.len().to_string().red(), config.projects.len().to_string().green());
+        }
+        None => {
+            println!("\n📋 New configuration:");
+            println!("  {} {}", "projects:".yellow(), config.projects.len());
+        }
+    }
+    println!();
+
+    let confirmed = handle_inquire_error(
+        Confirm::new(if config_path.exists() {
+            "Replace your configuration with the downloaded one?"
+        } else {
+            "Initialize PM with the downloaded configuration?"
+        })
+        .with_default(false)
+        .prompt(),
+    )?;
+    if !confirmed {
+        println!("🚫 Initialization cancelled");
+        return Ok(());
+    }
+
+    // Lock only after the prompt; the backup below captures any change made meanwhile
+    let _lock = lock_config().await?;
+    let backup = if config_path.exists() {
+        println!("💾 Creating backup of existing config...");
+        Some(create_backup(&config_path, BackupReason::InitFromRemote).await?)
+    } else {
+        None
+    };
+
+    save_config(&config).await?;
+
+    if let Err(e) = crate::commands::config::handle_validate().await {
+        if let Some(backup) = &backup {
+            if restore_config_from_backup(backup, &config_path).await? {
+                println!("↩️  Restored the previous configuration");
+            }
+        } else {
+            let _ = tokio::fs::remove_file(&config_path).await;
+        }
+        return Err(e);
+    }
+
+    if let Some(backup) = backup {
+        let backup_id = backup.id.clone();
+        add_backup_entry(backup).await?;
+        crate::commands::backup::apply_configured_retention_policy(&backup_id).await?;
+    }
+    crate::commands::config::add_to_history(
+        &format!("init:{}", url),
+        &format!("Initialized configuration from '{}'", url),
+    )
+    .await?;
+
+    display_success(&format!("Configuration initialized from {}", url));
+    println!("📄 Configuration file: {}", config_path.display());
+
+    Ok(())
+}
+
 /// Handle configuration file conflicts with interactive user choice
 async fn handle_config_conflict_interactive(
     config_path: &Path,
@@ -199,3 +441,87 @@ async fn setup_dev_environment() -> Result<()> {
     Ok(())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_config_url() {
+        assert_eq!(
+            resolve_config_url("github:acme/dotfiles/pm/config.yml").unwrap().as_str(),
+            "https://raw.githubusercontent.com/acme/dotfiles/HEAD/pm/config.yml"
+        );
+        assert_eq!(
+            resolve_config_url("https://example.com/pm.yml").unwrap().as_str(),
+            "https://example.com/pm.yml"
+        );
+        assert!(resolve_config_url("http://example.com/pm.yml").is_err());
+        assert!(resolve_config_url("file:///etc/pm.yml").is_err());
+        assert!(resolve_config_url("github:acme/dotfiles").is_err());
+        assert!(resolve_config_url("github:acme//config.yml").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_download_config_enforces_size_limit() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/pm.yml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("version: \"0.1.3\"\n"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/huge.yml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("#".repeat(MAX_REMOTE_CONFIG_BYTES + 1)))
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::new();
+        let url = |name: &str| reqwest::Url::parse(&format!("{}/{}", server.uri(), name)).unwrap();
+        assert_eq!(download_config(&client, url("pm.yml")).await.unwrap(), "version: \"0.1.3\"\n");
+        assert!(download_config(&client, url("huge.yml")).await.unwrap_err().to_string().contains("larger than"));
+        assert!(download_config(&client, url("missing.yml")).await.is_err());
+
+        // The real client refuses plain HTTP, so an https URL cannot be redirected to one
+        assert!(download_config(&config_http_client().unwrap(), url("pm.yml")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restore_config_from_backup_finds_config_by_name() {
+        use crate::backup::{BackupFile, BackupFileType};
+
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join(CONFIG_FILENAME);
+        std::fs::write(&config_path, "downloaded").unwrap();
+        std::fs::write(dir.path().join("pm.sh.bak"), "shell").unwrap();
+        std::fs::write(dir.path().join("config.yml.bak"), "previous").unwrap();
+
+        let mut backup = BackupEntry {
+            timestamp: chrono::Utc::now(),
+            id: "test".to_string(),
+            reason: BackupReason::InitFromRemote,
+            shell_changes: Vec::new(),
+            files: vec![
+                BackupFile {
+                    original_path: dir.path().join("pm.sh"),
+                    backup_path: dir.path().join("pm.sh.bak"),
+                    file_type: BackupFileType::ShellIntegration(crate::shell_integration::ShellType::Zsh),
+                },
+                BackupFile {
+                    original_path: config_path.clone(),
+                    backup_path: dir.path().join("config.yml.bak"),
+                    file_type: BackupFileType::Config,
+                },
+            ],
+        };
+
+        assert!(restore_config_from_backup(&backup, &config_path).await.unwrap());
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "previous");
+
+        backup.files.truncate(1);
+        assert!(!restore_config_from_backup(&backup, &config_path).await.unwrap());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod backup;
 pub mod config;
+pub mod import;
 pub mod init;
 pub mod project;
 pub mod status;
@@ -1,18 +1,29 @@
-use crate::config::{load_config, save_config, Config};
+use crate::commands::tag::tags_match;
+use crate::config::{get_config_path, load_config, lock_config, save_config, Config};
 use crate::constants::*;
 use crate::display::*;
 use crate::error::{handle_inquire_error, PmError};
-use crate::utils::{get_last_git_commit_time, is_git_repository};
-use crate::validation::{parse_time_duration, validate_path};
+use crate::extensions::hooks::{dispatch_hook, HookContext, HookEvent};
+use crate::commands::status::{read_git_status, GitStatusInfo};
+use crate::utils::{
+    canonical_path, compute_access_frequency, convert_remote_url, count_project_files,
+    detect_project_description, detect_project_language, editor_command, get_directory_modified_time, get_last_git_commit_time,
+    infer_project_name, is_git_repository, path_comparison_key,
+    resolve_editor, resolve_ide_command, AccessFrequency, Ide, NameStrategy, UrlFormat,
+};
+use crate::validation::{
+    allowed_generated_tags, check_path_format, parse_time_filter, validate_color, validate_path,
+    validate_tag_name, validate_tag_names, TagNamingPolicy,
+};
 use crate::Project;
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use colored::*;
 use git2::Repository;
 use indicatif::{ProgressBar, ProgressStyle};
 use inquire::{Confirm, MultiSelect, Select, Text};
 use octocrab::{Octocrab, params::users::repos::Type as RepoType, params::repos::Sort};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
@@ -21,6 +32,17 @@ use walkdir::WalkDir;
 // Type alias for complex project data tuple
 type ProjectData = (Project, Option<chrono::DateTime<chrono::Utc>>, u32);
 
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct GitLabRepo {
+    pub name: String,
+    pub path_with_namespace: String,
+    pub description: Option<String>,
+    pub http_url_to_repo: String,
+    pub visibility: Option<String>,
+    pub forked_from_project: Option<serde_json::Value>,
+    pub last_activity_at: Option<chrono::DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct GitHubRepo {
     pub name: String,
@@ -33,13 +55,54 @@ pub struct GitHubRepo {
     pub updated_at: Option<chrono::DateTime<Utc>>,
 }
 
-pub async fn handle_add(
-    path: &PathBuf,
-    name: &Option<String>,
-    _tags: &[String], // Will be replaced with interactive selection
-    description: &Option<String>,
-) -> Result<()> {
+/// What `pm add` does when a path is already tracked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExistingProjectPolicy {
+    /// Fail with `DuplicateProject` (multi-directory adds skip instead)
+    #[default]
+    Error,
+    /// Succeed without changes (`--if-not-exists`)
+    Ignore,
+    /// Succeed and add any new tags to the existing project (`--if-not-exists --update-tags`)
+    UpdateTags,
+}
+
+impl ExistingProjectPolicy {
+    pub fn from_flags(if_not_exists: bool, update_tags: bool) -> Self {
+        match (if_not_exists, update_tags) {
+            (true, true) => ExistingProjectPolicy::UpdateTags,
+            (true, false) => ExistingProjectPolicy::Ignore,
+            _ => ExistingProjectPolicy::Error,
+        }
+    }
+}
+
+/// Options for `pm add <path>`
+#[derive(Debug, Clone, Default)]
+pub struct AddOptions {
+    pub name: Option<String>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    /// Use the README's first paragraph when no description is given
+    pub detect_description: bool,
+    pub priority: Option<u8>,
+    pub existing: ExistingProjectPolicy,
+    /// Defaults to `settings.default_name_strategy`
+    pub name_strategy: Option<NameStrategy>,
+    /// Also enabled by `settings.auto_detect_language`
+    pub detect_language: bool,
+}
+
+pub async fn handle_add(path: &PathBuf, options: &AddOptions) -> Result<()> {
+    // Prompts run against an unlocked snapshot; `register_projects` takes the lock to save
     let mut config = load_config().await?;
+    let existing_ids: HashSet<Uuid> = config.projects.keys().copied().collect();
+    let options = AddOptions {
+        tags: checked_tag_names(&options.tags, &config)?,
+        name_strategy: Some(options.name_strategy.unwrap_or(config.settings.default_name_strategy)),
+        detect_language: options.detect_language || config.settings.auto_detect_language,
+        ..options.clone()
+    };
     
     // Parse path patterns
     let path_str = path.to_string_lossy().to_string();
@@ -72,10 +135,11 @@ pub async fn handle_add(
 
     let mut added_count = 0;
     let mut skipped_count = 0;
+    let mut added_names = Vec::new();
     let target_count = target_paths.len();
 
     for (index, target_path) in target_paths.iter().enumerate() {
-        let result = process_single_add(&mut config, target_path, name, description, index + 1, target_count).await;
+        let result = process_single_add(&mut config, target_path, &options, index + 1, target_count).await;
         
         match result {
             Ok(AddResult::Added(project_name)) => {
@@ -84,12 +148,12 @@ pub async fn handle_add(
                     println!("✅ Successfully added project '{}'", project_name);
                     println!("   Path: {}", target_path.display());
                 }
+                added_names.push(project_name);
             }
-            Ok(AddResult::Skipped) => {
+            Ok(AddResult::AlreadyTracked(project_id)) => {
                 skipped_count += 1;
                 if target_count == 1 {
-                    println!("ℹ️  Project already exists at this path");
-                    return Ok(());
+                    return handle_already_tracked(&config, project_id, &options.tags, options.existing).await;
                 }
             }
             Ok(AddResult::Created(project_name)) => {
@@ -98,6 +162,7 @@ pub async fn handle_add(
                     println!("✅ Created and added project '{}'", project_name);
                     println!("   Path: {}", target_path.display());
                 }
+                added_names.push(project_name);
             }
             Err(_) => {
                 if target_count == 1 {
@@ -120,22 +185,97 @@ pub async fn handle_add(
         println!("\n📊 Summary: {} added, {} skipped", added_count, skipped_count);
     }
 
-    save_config(&config).await?;
+    let new_projects: Vec<Project> = added_names
+        .iter()
+        .filter_map(|name| config.find_project_by_name(name))
+        .filter(|project| !existing_ids.contains(&project.id))
+        .cloned()
+        .collect();
+    for project in register_projects(new_projects).await? {
+        dispatch_hook(HookEvent::ProjectAdd, &HookContext::for_project(&project)).await;
+    }
+    Ok(())
+}
+
+/// Register `projects` under the config lock, skipping any whose path another PM
+/// process registered in the meantime; returns the projects actually added
+pub(crate) async fn register_projects(projects: Vec<Project>) -> Result<Vec<Project>> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let mut added = Vec::new();
+    for project in projects {
+        let key = path_comparison_key(&project.path);
+        if config.projects.values().any(|p| path_comparison_key(&p.path) == key) {
+            display_warning(&format!(
+                "'{}' was registered by another PM process meanwhile, skipping",
+                project.path.display()
+            ));
+            continue;
+        }
+        config.add_project(project.clone());
+        added.push(project);
+    }
+    if !added.is_empty() {
+        save_config(&config).await?;
+    }
+    Ok(added)
+}
+
+/// Report (and with `UpdateTags`, update) a project `pm add` found already tracked
+async fn handle_already_tracked(
+    config: &Config,
+    project_id: Uuid,
+    tags: &[String],
+    existing: ExistingProjectPolicy,
+) -> Result<()> {
+    let project = config
+        .projects
+        .get(&project_id)
+        .ok_or(PmError::ProjectNotFound)?;
+
+    if existing == ExistingProjectPolicy::Error {
+        println!("ℹ️  '{}' already tracks {}", project.name, project.path.display());
+        println!("💡 Pass --if-not-exists to treat this as success");
+        return Err(PmError::DuplicateProject.into());
+    }
+
+    println!("ℹ️  Project '{}' is already tracked", project.name);
+    println!("   Path: {}", project.path.display());
+
+    if existing == ExistingProjectPolicy::UpdateTags {
+        let new_tags: Vec<String> = tags
+            .iter()
+            .filter(|tag| !project.tags.contains(tag))
+            .cloned()
+            .collect();
+        if !new_tags.is_empty() {
+            let _lock = lock_config().await?;
+            let mut config = load_config().await?;
+            let project = config
+                .projects
+                .get_mut(&project_id)
+                .ok_or(PmError::ProjectNotFound)?;
+            add_missing_tags(&mut project.tags, new_tags.clone());
+            project.updated_at = Utc::now();
+            save_config(&config).await?;
+            println!("🏷️  Added tags: {}", new_tags.join(", "));
+        }
+    }
+
     Ok(())
 }
 
 #[derive(Debug)]
 enum AddResult {
     Added(String),
-    Skipped,
+    AlreadyTracked(Uuid),
     Created(String),
 }
 
 async fn process_single_add(
     config: &mut Config,
     target_path: &Path,
-    name: &Option<String>,
-    description: &Option<String>,
+    options: &AddOptions,
     current_index: usize,
     total_count: usize,
 ) -> Result<AddResult> {
@@ -144,6 +284,9 @@ async fn process_single_add(
         println!("\n[{}/{}] Processing: {}", current_index, total_count, target_path.display());
     }
 
+    // Reject unusable paths before offering to create them
+    check_path_format(target_path, config.settings.allow_unicode_paths)?;
+
     let absolute_path = if !target_path.exists() {
         // Directory doesn't exist - ask to create
         let should_create = if total_count == 1 {
@@ -169,17 +312,103 @@ async fn process_single_add(
         }
         
 
-        validate_path(target_path)?
+        validate_path(target_path, config.settings.allow_unicode_paths)?
+    } else {
+        validate_path(target_path, config.settings.allow_unicode_paths)?
+    };
+
+    // Check for duplicate projects (path-based, case-insensitive on Windows)
+    let path_key = path_comparison_key(&absolute_path);
+    if let Some(existing) = config.projects.values().find(|p| path_comparison_key(&p.path) == path_key) {
+        return Ok(AddResult::AlreadyTracked(existing.id));
+    }
+
+    let name_strategy = options.name_strategy.unwrap_or(config.settings.default_name_strategy);
+    let project_name = options
+        .name
+        .clone()
+        .unwrap_or_else(|| inferred_project_name(&absolute_path, name_strategy));
+
+    // Interactive tag selection (only for single operations without --tags)
+    let mut selected_tags = if !options.tags.is_empty() {
+        options.tags.clone()
+    } else if total_count == 1 {
+        select_tags_interactive(config, &project_name).await?
+    } else {
+        Vec::new() // For batch operations, no tags by default
+    };
+
+    if options.detect_language {
+        let language_tags = detected_language_tags(&absolute_path, config.settings.tag_naming_policy);
+        if total_count == 1 && !language_tags.is_empty() {
+            println!("🔤 Detected languages: {}", language_tags.join(", "));
+        }
+        add_missing_tags(&mut selected_tags, language_tags);
+    }
+
+    let description = match &options.description {
+        Some(description) => Some(description.clone()),
+        None if options.detect_description => {
+            let detected = detect_project_description(&absolute_path);
+            if let (Some(detected), 1) = (&detected, total_count) {
+                println!("📝 Description from README: {}", detected);
+            }
+            detected
+        }
+        None => None,
+    };
+
+    let mut project = new_project(&absolute_path, project_name.clone(), selected_tags, description);
+    project.priority = options.priority.unwrap_or(DEFAULT_PROJECT_PRIORITY);
+    config.add_project(project);
+
+    if target_path.exists() {
+        Ok(AddResult::Added(project_name))
+    } else {
+        Ok(AddResult::Created(project_name))
+    }
+}
+
+/// Create a new project directory (optionally with Git and a template) and add it to PM
+pub async fn handle_create(
+    path: &Path,
+    name: Option<&str>,
+    tags: &[String],
+    git: bool,
+    template: Option<&str>,
+) -> Result<()> {
+    let target_path = if path.is_absolute() {
+        path.to_path_buf()
     } else {
-        validate_path(target_path)?
+        std::env::current_dir()?.join(path)
     };
 
-    // Check for duplicate projects (path-based)
-    if config.projects.values().any(|p| p.path == absolute_path) {
-        return Ok(AddResult::Skipped);
+    if target_path.exists() {
+        display_error("Directory already exists", &target_path.display().to_string());
+        println!("💡 Use '{} add {}' to track an existing directory", crate::utils::get_binary_name(), path.display());
+        return Err(PmError::DuplicateProject.into());
+    }
+
+    if let Some(template) = template {
+        if let Err(e) = crate::scaffold::validate_template(template) {
+            display_error("Invalid template", &e);
+            return Err(PmError::ValidationFailed(e).into());
+        }
+    }
+
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let tags = checked_tag_names(tags, &config)?;
+    check_path_format(&target_path, config.settings.allow_unicode_paths)?;
+
+    if let Err(e) = fs::create_dir_all(&target_path) {
+        display_error("Failed to create directory", &e.to_string());
+        return Err(PmError::DirectoryCreationFailed.into());
     }
+    let absolute_path = validate_path(&target_path, config.settings.allow_unicode_paths)?;
+    println!("📂 Created directory: {}", absolute_path.display());
 
-    let project_name = name.clone().unwrap_or_else(|| {
+    let project_name = name.map(str::to_string).unwrap_or_else(|| {
         absolute_path
             .file_name()
             .and_then(|name| name.to_str())
@@ -187,37 +416,396 @@ async fn process_single_add(
             .to_string()
     });
 
-    // Interactive tag selection (only for single operations)
-    let selected_tags = if total_count == 1 {
-        select_tags_interactive(config, &project_name).await?
-    } else {
-        Vec::new() // For batch operations, no tags by default
-    };
+    if let Some(template) = template {
+        let files = crate::scaffold::scaffold_project(&absolute_path, &project_name, template)?;
+        println!("🧱 Applied template '{}' ({} files)", template, files.len());
+    }
+
+    if git {
+        if let Err(e) = Repository::init(&absolute_path) {
+            display_error("Failed to initialize Git repository", &e.to_string());
+            return Err(PmError::GitOperationFailed.into());
+        }
+        println!("🌱 Initialized Git repository");
+    }
+
+    let project = new_project(&absolute_path, project_name.clone(), tags.clone(), None);
+    let hook_context = HookContext::for_project(&project);
+    config.add_project(project);
+    save_config(&config).await?;
+    dispatch_hook(HookEvent::ProjectAdd, &hook_context).await;
+
+    println!("✅ Created and added project '{}'", project_name);
+    println!("   Path: {}", absolute_path.display());
+    if !tags.is_empty() {
+        println!("   Tags: {}", tags.join(", "));
+    }
+
+    Ok(())
+}
 
-    let git_updated_at = match get_last_git_commit_time(&absolute_path) {
+/// Build a new project entry for an existing directory
+pub(crate) fn new_project(
+    absolute_path: &Path,
+    name: String,
+    tags: Vec<String>,
+    description: Option<String>,
+) -> Project {
+    let git_updated_at = match get_last_git_commit_time(absolute_path) {
         Ok(time) => time,
         Err(_) => None,
     };
 
-    let project = Project {
+    Project {
         id: Uuid::new_v4(),
-        name: project_name.clone(),
-        path: absolute_path.clone(),
-        tags: selected_tags,
-        description: description.clone(),
+        name,
+        path: absolute_path.to_path_buf(),
+        tags,
+        description,
         created_at: Utc::now(),
         updated_at: Utc::now(),
         git_updated_at,
-        is_git_repository: is_git_repository(&absolute_path),
+        is_git_repository: is_git_repository(absolute_path),
+        color: None,
+        remote_url: None,
+        priority: DEFAULT_PROJECT_PRIORITY,
+    }
+}
+
+/// Config with one project per `(name, tags)` entry, each at `/tmp/<name>`
+#[cfg(test)]
+pub(crate) fn config_with_projects(projects: &[(&str, &[&str])]) -> Config {
+    let mut config = Config::default();
+    for (name, tags) in projects {
+        let tags = tags.iter().map(|tag| tag.to_string()).collect();
+        config.add_project(new_project(&Path::new("/tmp").join(name), name.to_string(), tags, None));
+    }
+    config
+}
+
+/// One entry of a `pm add --batch` file
+struct BatchEntry {
+    line_number: usize,
+    path: PathBuf,
+    name: Option<String>,
+    tags: Vec<String>,
+}
+
+/// Parse a batch file: `path[<TAB>name[<TAB>tag1,tag2]]` per line, `#` starts a comment
+fn parse_batch_file(content: &str) -> Vec<BatchEntry> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            let mut fields = line.split('\t').map(str::trim);
+            let path = PathBuf::from(shellexpand::tilde(fields.next().unwrap_or_default()).to_string());
+            let name = fields.next().filter(|n| !n.is_empty()).map(str::to_string);
+            let tags = fields
+                .next()
+                .map(|tags| {
+                    tags.split(',')
+                        .map(str::trim)
+                        .filter(|t| !t.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            BatchEntry {
+                line_number: index + 1,
+                path,
+                name,
+                tags,
+            }
+        })
+        .collect()
+}
+
+/// Why a batch entry was not added
+enum BatchSkip {
+    /// The path or name is already registered
+    Duplicate(String),
+    /// The path or tags failed validation
+    Invalid(String),
+}
+
+/// Validate one batch entry the way `pm add` would and build its project
+fn batch_entry_project(
+    config: &Config,
+    entry: &BatchEntry,
+    current_dir: &Path,
+) -> std::result::Result<Project, BatchSkip> {
+    let resolved_path = if entry.path.is_absolute() {
+        entry.path.clone()
+    } else {
+        current_dir.join(&entry.path)
     };
+    let absolute_path = validate_path(&resolved_path, config.settings.allow_unicode_paths)
+        .map_err(|e| BatchSkip::Invalid(e.to_string().lines().next().unwrap_or_default().to_string()))?;
+    let tags = validate_tag_names(&entry.tags, config.settings.tag_naming_policy)
+        .map_err(|e| BatchSkip::Invalid(e.to_string()))?;
 
-    config.add_project(project);
+    let project_name = entry.name.clone().unwrap_or_else(|| {
+        absolute_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unnamed-project")
+            .to_string()
+    });
 
-    if target_path.exists() {
-        Ok(AddResult::Added(project_name))
+    let path_key = path_comparison_key(&absolute_path);
+    if let Some(existing) = config.projects.values().find(|p| path_comparison_key(&p.path) == path_key) {
+        return Err(BatchSkip::Duplicate(format!(
+            "{} is already registered as '{}', skipping",
+            absolute_path.display(),
+            existing.name
+        )));
+    }
+    if config.find_project_by_name(&project_name).is_some() {
+        return Err(BatchSkip::Duplicate(format!(
+            "a project named '{}' is already registered, skipping",
+            project_name
+        )));
+    }
+
+    Ok(new_project(&absolute_path, project_name, tags, None))
+}
+
+/// Add every project listed in a batch file, skipping invalid lines and duplicates
+pub async fn handle_add_batch(file: &Path) -> Result<()> {
+    let content = fs::read_to_string(file).map_err(|e| {
+        display_error("Failed to read batch file", &format!("{}: {}", file.display(), e));
+        PmError::InvalidPath
+    })?;
+    let entries = parse_batch_file(&content);
+
+    if entries.is_empty() {
+        println!("📋 No project entries found in {}", file.display());
+        return Ok(());
+    }
+
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let current_dir = std::env::current_dir()?;
+
+    let pb = ProgressBar::new(entries.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("[{pos}/{len}] {msg} {bar:40.cyan/blue} {percent}%")
+            .unwrap()
+            .progress_chars("██▓▒░"),
+    );
+
+    let mut added = Vec::new();
+    let mut skipped_count = 0;
+    let mut failed_count = 0;
+
+    for entry in entries {
+        pb.set_message(entry.path.display().to_string());
+        let warn = |message: String| {
+            pb.suspend(|| display_warning(&format!("Line {}: {}", entry.line_number, message)))
+        };
+
+        match batch_entry_project(&config, &entry, &current_dir) {
+            Ok(project) => {
+                added.push(project.id);
+                config.add_project(project);
+            }
+            Err(BatchSkip::Duplicate(message)) => {
+                warn(message);
+                skipped_count += 1;
+            }
+            Err(BatchSkip::Invalid(message)) => {
+                warn(message);
+                failed_count += 1;
+            }
+        }
+        pb.inc(1);
+    }
+
+    pb.finish_and_clear();
+    save_config(&config).await?;
+
+    println!("✅ Processed batch file: {}", file.display());
+    if !added.is_empty() {
+        println!("   ✅ Added: {} projects", added.len());
+    }
+    if skipped_count > 0 {
+        println!("   ⏭️  Skipped: {} already registered", skipped_count);
+    }
+    if failed_count > 0 {
+        println!("   ❌ Failed: {} invalid entries", failed_count);
+    }
+    println!(
+        "\n📊 Summary: {} added, {} skipped, {} failed",
+        added.len(), skipped_count, failed_count
+    );
+
+    for id in added {
+        if let Some(project) = config.projects.get(&id) {
+            dispatch_hook(HookEvent::ProjectAdd, &HookContext::for_project(project)).await;
+        }
+    }
+    Ok(())
+}
+
+/// Add every project root found under `directory` (up to `max_depth` levels) after a preview
+pub async fn handle_add_scan(
+    directory: &Path,
+    max_depth: usize,
+    tags: &[String],
+    detect_description: bool,
+    name_strategy: Option<NameStrategy>,
+    detect_language: bool,
+) -> Result<()> {
+    let config = load_config().await?;
+    let name_strategy = name_strategy.unwrap_or(config.settings.default_name_strategy);
+    let detect_language = detect_language || config.settings.auto_detect_language;
+
+    let tags = checked_tag_names(tags, &config)?;
+
+    let resolved_path = if directory.is_absolute() {
+        directory.to_path_buf()
     } else {
-        Ok(AddResult::Created(project_name))
+        std::env::current_dir()?.join(directory)
+    };
+    let scan_dir = validate_path(&resolved_path, config.settings.allow_unicode_paths)?;
+
+    println!("🔍 Scanning {} (depth {})", scan_dir.display(), max_depth);
+
+    let existing_paths: HashSet<PathBuf> = config
+        .projects
+        .values()
+        .map(|p| path_comparison_key(&p.path))
+        .collect();
+    let (tracked, discovered): (Vec<PathBuf>, Vec<PathBuf>) = find_project_roots(&scan_dir, max_depth)
+        .into_iter()
+        .partition(|path| existing_paths.contains(&path_comparison_key(path)));
+
+    if discovered.is_empty() {
+        if tracked.is_empty() {
+            println!("❌ No projects found in {}", scan_dir.display());
+        } else {
+            println!("✅ All {} discovered projects are already tracked by PM", tracked.len());
+        }
+        return Ok(());
+    }
+
+    println!("📦 Found {} new projects:", discovered.len());
+    for path in &discovered {
+        println!(
+            "  {} {} {}",
+            if path.join(".git").exists() { "🔗" } else { "📁" },
+            inferred_project_name(path, name_strategy),
+            path.display().to_string().bright_black()
+        );
+    }
+    if !tracked.is_empty() {
+        println!("   ⏭️  {} already tracked", tracked.len());
     }
+
+    let confirmed = handle_inquire_error(
+        Confirm::new(&format!("Add {} projects?", discovered.len()))
+            .with_default(true)
+            .prompt(),
+    )?;
+    if !confirmed {
+        println!("❌ No projects added");
+        return Ok(());
+    }
+
+    let mut projects = Vec::new();
+    for path in &discovered {
+        let description = if detect_description {
+            detect_project_description(path)
+        } else {
+            None
+        };
+        let mut project_tags = tags.clone();
+        if detect_language {
+            add_missing_tags(&mut project_tags, detected_language_tags(path, config.settings.tag_naming_policy));
+        }
+        projects.push(new_project(path, inferred_project_name(path, name_strategy), project_tags, description));
+    }
+    let added = register_projects(projects).await?;
+
+    println!("✅ Added {} projects", added.len());
+
+    for project in &added {
+        dispatch_hook(HookEvent::ProjectAdd, &HookContext::for_project(project)).await;
+    }
+    Ok(())
+}
+
+/// Project roots below `directory` (excluding itself), detected the same way as `pm scan`
+///
+/// The walk does not descend into a project root, so sub-crates and packages of a workspace
+/// are not reported as separate projects.
+fn find_project_roots(directory: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    let mut entries = WalkDir::new(directory)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_entry(|e| e.file_type().is_dir() && !should_skip_directory(e.path()));
+
+    while let Some(entry) = entries.next() {
+        let Ok(entry) = entry else { continue };
+        if is_project_root(entry.path()) {
+            entries.skip_current_dir();
+            roots.push(entry.into_path());
+        }
+    }
+    roots
+}
+
+pub(crate) fn project_name_from_path(path: &Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("unnamed-project")
+        .to_string()
+}
+
+/// Tags for the languages detected in `path`, most likely first
+fn detected_language_tags(path: &Path, policy: TagNamingPolicy) -> Vec<String> {
+    let tags = detect_project_language(path)
+        .iter()
+        .map(|language| crate::utils::language_tag(language))
+        .collect::<Vec<_>>();
+    allowed_generated_tags(tags, policy)
+}
+
+/// `validate_tag_names` under `settings.tag_naming_policy`, reporting a rejected tag
+fn checked_tag_names(tags: &[String], config: &Config) -> Result<Vec<String>> {
+    validate_tag_names(tags, config.settings.tag_naming_policy).map_err(|e| {
+        display_error("Invalid tags", &e.to_string());
+        e.into()
+    })
+}
+
+/// `add_config` with its `--tags` checked against `settings.tag_naming_policy`
+///
+/// Done before cloning so a rejected tag does not leave a clone behind.
+async fn checked_clone_tags(add_config: &AddConfig) -> Result<AddConfig> {
+    let mut add_config = add_config.clone();
+    if let (Some(tags), true) = (&add_config.tags, add_config.add_to_pm) {
+        let config = load_config().await?;
+        add_config.tags = Some(checked_tag_names(tags, &config)?);
+    }
+    Ok(add_config)
+}
+
+fn add_missing_tags(tags: &mut Vec<String>, new_tags: Vec<String>) {
+    for tag in new_tags {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+}
+
+/// Name from `strategy`, falling back to the directory name when it finds none
+fn inferred_project_name(path: &Path, strategy: NameStrategy) -> String {
+    infer_project_name(path, strategy).unwrap_or_else(|| project_name_from_path(path))
 }
 
 async fn select_tags_interactive(config: &Config, project_name: &str) -> Result<Vec<String>> {
@@ -307,7 +895,13 @@ async fn create_and_select_tags(config: &Config) -> Result<Vec<String>> {
         )?;
 
         if !new_tag.trim().is_empty() {
-            let tag_name = new_tag.trim().to_string();
+            let tag_name = match validate_tag_name(new_tag.trim(), config.settings.tag_naming_policy) {
+                Ok(tag_name) => tag_name,
+                Err(e) => {
+                    display_warning(&e.to_string());
+                    continue;
+                }
+            };
             if !final_tags.contains(&tag_name) {
                 final_tags.push(tag_name);
             }
@@ -347,17 +941,110 @@ async fn create_and_select_tags(config: &Config) -> Result<Vec<String>> {
 }
 
 
-pub async fn handle_list(
-    tags: &[String],
-    tags_any: &[String],
-    recent: &Option<String>,
-    limit: &Option<usize>,
-    verbose: bool,
-) -> Result<()> {
-    let config = load_config().await?;
+/// How `pm list` groups its output
+#[derive(clap::ValueEnum, Debug, Clone)]
+pub enum GroupBy {
+    Tags,
+}
+
+/// Ordering for `pm list`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListSort {
+    /// Most recently committed or updated first
+    #[default]
+    Updated,
+    /// Highest priority first, then most recently committed
+    Priority,
+}
+
+/// Output format for `pm list`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Options controlling `pm list` filtering, pagination and output
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    pub tags: Vec<String>,
+    pub tags_any: Vec<String>,
+    pub recent: Option<String>,
+    pub since_last_access: Option<String>,
+    /// Last commit for Git repositories, directory mtime otherwise
+    pub since_modified: Option<String>,
+    /// Only projects whose last commit is older than this (or unknown), oldest first
+    pub stale_threshold: Option<chrono::Duration>,
+    /// Some(true) for Git repositories only, Some(false) for non-Git projects only
+    pub git: Option<bool>,
+    /// Some(true) for projects whose path exists, Some(false) for missing paths only
+    pub exists: Option<bool>,
+    /// Only projects without a description (or with a blank one)
+    pub missing_description: bool,
+    /// Only projects without tags
+    pub missing_tags: bool,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub page: Option<usize>,
+    pub verbose: bool,
+    pub format: ListFormat,
+    pub interactive: bool,
+    pub group_by: Option<GroupBy>,
+    pub sort: ListSort,
+    /// Print only the number of matching projects
+    pub count: bool,
+    /// Print only project names
+    pub brief: bool,
+    /// Show one entry per canonical path (the most recently updated)
+    pub unique_paths: bool,
+    /// Show only projects whose path is registered more than once
+    pub show_duplicates: bool,
+    /// Add a leading column with each project's short ID
+    pub show_id: bool,
+    /// Add a column with each project's Git remote URL
+    pub show_remote_url: bool,
+    /// Show only the last N components of each path
+    pub path_depth: Option<usize>,
+    pub access_frequency: Option<AccessFrequency>,
+    /// Redraw the list whenever the config changes
+    pub watch: bool,
+    /// Also poll the config every N seconds while watching
+    pub interval: Option<u64>,
+}
+
+impl ListOptions {
+    /// Resolve the effective offset (`--page` takes precedence over `--offset`)
+    fn effective_offset(&self) -> usize {
+        match (self.page, self.limit) {
+            (Some(page), Some(limit)) => page.saturating_sub(1).saturating_mul(limit),
+            _ => self.offset.unwrap_or(0),
+        }
+    }
+}
+
+pub async fn handle_list(options: &ListOptions) -> Result<()> {
+    if options.watch {
+        let poll_interval = options.interval.map(|secs| std::time::Duration::from_secs(secs.max(1)));
+        return watch_and_display(&get_config_path()?, poll_interval, || list_projects(options)).await;
+    }
+    list_projects(options).await
+}
+
+async fn list_projects(options: &ListOptions) -> Result<()> {
+    let mut config = load_config().await?;
+    let json_output = options.format == ListFormat::Json;
 
     if config.projects.is_empty() {
-        display_no_projects();
+        if options.count {
+            println!("0");
+        } else if options.brief {
+            print_project_names(&[], json_output)?;
+        } else if json_output {
+            print_project_page_json(&[], 0, options)?;
+        } else {
+            display_no_projects();
+        }
         return Ok(());
     }
 
@@ -366,94 +1053,1176 @@ pub async fn handle_list(
     // Update git_updated_at for projects in the background
     update_git_times_by_ids(&project_ids).await;
 
+    if options.show_remote_url {
+        fill_remote_urls(&mut config);
+    }
+
     // Get filtered project data
-    let filtered_project_data = get_filtered_project_data(&config, tags, tags_any, recent)?;
+    let filtered_project_data =
+        get_filtered_project_data(&config, options)?;
+    let total = filtered_project_data.len();
 
-    if filtered_project_data.is_empty() {
+    if options.count {
+        println!("{}", total);
+        return Ok(());
+    }
+
+    if filtered_project_data.is_empty() && !json_output && !options.brief {
         display_no_matches();
         return Ok(());
     }
 
-    // Apply limit
-    let limited_project_data = if let Some(limit_count) = limit {
-        filtered_project_data
-            .into_iter()
-            .take(*limit_count)
-            .collect()
+    // Apply offset and limit
+    let offset = options.effective_offset();
+    let page_data: Vec<ProjectData> = filtered_project_data
+        .into_iter()
+        .skip(offset)
+        .take(options.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    if options.brief {
+        return print_project_names(&page_data, json_output);
+    }
+
+    if json_output {
+        return print_project_page_json(&page_data, total, options);
+    }
+
+    if options.interactive {
+        let projects: Vec<Project> = page_data.into_iter().map(|(project, _, _)| project).collect();
+        let project = pick_project(&config, projects, "📂 Select project to open:")?;
+        let editor = resolve_open_command(&config, None)?;
+        return open_project_in_editor(project, &editor).await;
+    }
+
+    let missing = match (options.missing_description, options.missing_tags) {
+        (true, true) => Some("descriptions and tags"),
+        (true, false) => Some("descriptions"),
+        (false, true) => Some("tags"),
+        (false, false) => None,
+    };
+    if let Some(missing) = missing {
+        println!(
+            "🔎 {} {} missing {}\n",
+            total,
+            if total == 1 { "project is" } else { "projects are" },
+            missing
+        );
+    }
+
+    if page_data.len() < total {
+        display_project_list_page_header(offset, page_data.len(), total, options.show_id, options.show_remote_url);
+    } else {
+        display_project_list_header(page_data.len(), options.show_id, options.show_remote_url);
+    }
+
+    match options.group_by {
+        Some(GroupBy::Tags) => display_projects_grouped_by_tags(&page_data, options),
+        None => {
+            for (project, last_accessed, access_count) in &page_data {
+                display_project_row(project, *last_accessed, *access_count, options);
+            }
+        }
+    }
+
+    if options.exists == Some(false) {
+        println!(
+            "\n💡 Stop tracking missing projects with: {} remove <name>",
+            crate::utils::get_binary_name()
+        );
+    }
+
+    Ok(())
+}
+
+/// Look up the current remote URL of every Git project (in memory only; `list` never saves)
+fn fill_remote_urls(config: &mut Config) {
+    for project in config.projects.values_mut().filter(|project| project.is_git_repository) {
+        project.remote_url = crate::utils::get_git_remote_url(&project.path).ok().flatten();
+    }
+}
+
+/// `pm list --brief`: names only, one per line or as a JSON array
+fn print_project_names(project_data: &[ProjectData], json_output: bool) -> Result<()> {
+    let names: Vec<&str> = project_data.iter().map(|(project, _, _)| project.name.as_str()).collect();
+    if json_output {
+        println!("{}", serde_json::to_string(&names)?);
+    } else {
+        for name in names {
+            println!("{}", name);
+        }
+    }
+    Ok(())
+}
+
+fn display_project_row(
+    project: &Project,
+    last_accessed: Option<chrono::DateTime<Utc>>,
+    access_count: u32,
+    options: &ListOptions,
+) {
+    if options.verbose {
+        display_project_detailed(project, last_accessed, access_count);
+    } else {
+        display_project_simple(
+            project,
+            last_accessed,
+            options.show_id,
+            options.show_remote_url,
+            options.path_depth,
+        );
+    }
+
+    if options.stale_threshold.is_some() {
+        match project.git_updated_at {
+            Some(committed) => println!(
+                "   💤 {} days since last commit",
+                Utc::now().signed_duration_since(committed).num_days()
+            ),
+            None => println!("   💤 no commits"),
+        }
+    }
+}
+
+/// Display projects under one header per tag (projects may appear in several groups)
+fn display_projects_grouped_by_tags(project_data: &[ProjectData], options: &ListOptions) {
+    let mut groups: std::collections::BTreeMap<&str, Vec<&ProjectData>> =
+        std::collections::BTreeMap::new();
+    let mut untagged = Vec::new();
+
+    for data in project_data {
+        if data.0.tags.is_empty() {
+            untagged.push(data);
+        }
+        for tag in &data.0.tags {
+            groups.entry(tag.as_str()).or_default().push(data);
+        }
+    }
+
+    let untagged_group = (!untagged.is_empty()).then_some(("untagged", untagged));
+    for (tag, projects) in groups.into_iter().chain(untagged_group) {
+        let header = format!("🏷️  {} ({})", tag, projects.len());
+        match most_common_color(&projects) {
+            Some(color) => println!("\n{}", header.color(color).bold()),
+            None => println!("\n{}", header.bold()),
+        }
+        for (project, last_accessed, access_count) in projects {
+            display_project_row(project, *last_accessed, *access_count, options);
+        }
+    }
+}
+
+/// Most common color label among the given projects (ties resolved alphabetically)
+fn most_common_color(projects: &[&ProjectData]) -> Option<Color> {
+    let mut counts: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+    for (project, _, _) in projects {
+        if let Some(color) = project.color.as_deref() {
+            *counts.entry(color).or_default() += 1;
+        }
+    }
+
+    let max_count = counts.values().copied().max()?;
+    counts
+        .into_iter()
+        .find(|(_, count)| *count == max_count)
+        .and_then(|(color, _)| parse_project_color(color))
+}
+
+/// Projects whose path matches `pattern`, by name, split into (already tagged, to tag)
+fn partition_path_matches<'a>(
+    config: &'a Config,
+    pattern: &glob::Pattern,
+    tag: &str,
+) -> (Vec<&'a Project>, Vec<&'a Project>) {
+    let mut matches: Vec<&Project> = config
+        .projects
+        .values()
+        .filter(|p| pattern.matches_path(&p.path))
+        .collect();
+    matches.sort_by(|a, b| a.name.cmp(&b.name));
+    matches.into_iter().partition(|p| p.tags.iter().any(|t| t == tag))
+}
+
+/// Tag every project whose path matches a glob pattern
+pub async fn handle_tag_from_path(pattern: &str, tag: &str, dry_run: bool, skip_confirm: bool) -> Result<()> {
+    let glob_pattern = glob::Pattern::new(&shellexpand::tilde(pattern)).map_err(|e| {
+        display_error("Invalid glob pattern", &e.to_string());
+        PmError::ValidationFailed(format!("invalid glob pattern '{}'", pattern))
+    })?;
+
+    let config = load_config().await?;
+    let tag = validate_tag_name(tag, config.settings.tag_naming_policy).inspect_err(|e| {
+        display_error("Invalid tag", &e.to_string());
+    })?;
+    let tag = tag.as_str();
+
+    let (already_tagged, to_tag) = partition_path_matches(&config, &glob_pattern, tag);
+    if already_tagged.is_empty() && to_tag.is_empty() {
+        println!("📋 No projects match pattern: {}", pattern);
+        return Ok(());
+    }
+
+    println!("🏷️  Projects matching '{}':", pattern);
+    for project in &to_tag {
+        println!("   + {} ({})", project.name, project.path.display());
+    }
+    for project in &already_tagged {
+        println!("   = {} (already tagged)", project.name);
+    }
+
+    if to_tag.is_empty() {
+        display_info(&format!("All matching projects already have the '{}' tag", tag));
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("\n🔍 Dry run: {} projects would be tagged '{}'", to_tag.len(), tag);
+        return Ok(());
+    }
+
+    if !skip_confirm {
+        let confirmed = handle_inquire_error(
+            Confirm::new(&format!("Add tag '{}' to {} projects?", tag, to_tag.len()))
+                .with_default(true)
+                .prompt(),
+        )?;
+        if !confirmed {
+            println!("❌ Tagging cancelled");
+            return Ok(());
+        }
+    }
+
+    let ids: Vec<Uuid> = to_tag.iter().map(|p| p.id).collect();
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let mut tagged = 0;
+    for id in &ids {
+        if let Some(project) = config.projects.get_mut(id) {
+            if !project.tags.iter().any(|t| t == tag) {
+                project.tags.push(tag.to_string());
+                project.updated_at = Utc::now();
+                tagged += 1;
+            }
+        }
+    }
+    save_config(&config).await?;
+
+    display_success(&format!("Tagged {} projects with '{}'", tagged, tag));
+    Ok(())
+}
+
+/// Update `updated_at` and record an access for one or more projects
+pub async fn handle_touch(name: Option<&str>, all_in_cwd: bool) -> Result<()> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let current_dir = std::env::current_dir()?;
+
+    let project_ids: Vec<Uuid> = if all_in_cwd {
+        config
+            .projects
+            .values()
+            .filter(|p| p.path.starts_with(&current_dir) || current_dir.starts_with(&p.path))
+            .map(|p| p.id)
+            .collect()
+    } else if let Some(name) = name {
+        match config.find_project_by_name(name) {
+            Some(project) => vec![project.id],
+            None => {
+                display_error(ERROR_PROJECT_NOT_FOUND, &format!("'{}'", name));
+                let suggestions = suggest_similar_projects(&config, name);
+                display_suggestions(&suggestions);
+                return Err(PmError::ProjectNotFound.into());
+            }
+        }
+    } else {
+        match config.find_project_by_path(&current_dir) {
+            Some(project) => vec![project.id],
+            None => {
+                display_error(
+                    ERROR_PROJECT_NOT_FOUND,
+                    &format!("no project tracks {}", current_dir.display()),
+                );
+                return Err(PmError::ProjectNotFound.into());
+            }
+        }
+    };
+
+    if project_ids.is_empty() {
+        println!("📋 No projects found in {}", current_dir.display());
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    for &project_id in &project_ids {
+        if let Some(project) = config.projects.get_mut(&project_id) {
+            project.updated_at = now;
+        }
+        config.record_project_access(project_id);
+    }
+    save_config(&config).await?;
+
+    let mut touched: Vec<&str> = project_ids
+        .iter()
+        .filter_map(|id| config.projects.get(id).map(|p| p.name.as_str()))
+        .collect();
+    touched.sort();
+    for name in touched {
+        println!("👆 Touched '{}'", name);
+    }
+
+    Ok(())
+}
+
+/// Print a project's path, optionally relative, home-abbreviated or its parent
+pub async fn handle_path(name: &str, relative: bool, home: bool, parent: bool) -> Result<()> {
+    let config = load_config().await?;
+
+    let Some(project) = config.find_project_by_name(name) else {
+        display_error(ERROR_PROJECT_NOT_FOUND, &format!("'{}'", name));
+        let suggestions = suggest_similar_projects(&config, name);
+        display_suggestions(&suggestions);
+        return Err(PmError::ProjectNotFound.into());
+    };
+
+    let mut path = project.path.clone();
+    if parent {
+        if let Some(parent_dir) = path.parent() {
+            path = parent_dir.to_path_buf();
+        }
+    }
+
+    let output = if relative {
+        let current_dir = std::env::current_dir()?;
+        crate::utils::relative_path(&path, &current_dir)
+            .display()
+            .to_string()
+    } else if home {
+        crate::utils::abbreviate_home(&path)
+    } else {
+        path.display().to_string()
+    };
+
+    println!("{}", output);
+    Ok(())
+}
+
+/// Set a project's description from the command line
+pub async fn handle_description_set(name: &str, text: &str) -> Result<()> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let description = normalize_description(text);
+    update_project_description(&mut config, name, description).await
+}
+
+/// Edit a project's description in the configured editor via a temporary markdown file
+pub async fn handle_description_edit(name: &str) -> Result<()> {
+    let config = load_config().await?;
+
+    let Some(project) = config.find_project_by_name(name) else {
+        display_error(ERROR_PROJECT_NOT_FOUND, &format!("'{}'", name));
+        let suggestions = suggest_similar_projects(&config, name);
+        display_suggestions(&suggestions);
+        return Err(PmError::ProjectNotFound.into());
+    };
+
+    let mut temp_file = tempfile::Builder::new()
+        .prefix(&format!("pm-{}-", project.name))
+        .suffix(".md")
+        .tempfile()?;
+    std::io::Write::write_all(
+        &mut temp_file,
+        project.description.as_deref().unwrap_or_default().as_bytes(),
+    )?;
+
+    let editor = resolve_editor(config.settings.editor.as_deref());
+    println!("🔧 Opening description in {}...", editor.cyan());
+
+    let status = editor_command(&editor)
+        .arg(temp_file.path())
+        .status()
+        .map_err(|e| {
+            display_error("Failed to launch editor", &format!("{}: {}", editor, e));
+            PmError::EditorLaunchFailed
+        })?;
+
+    if !status.success() {
+        display_warning("Editor exited with an error, description not changed");
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(temp_file.path())?;
+    let description = normalize_description(&content);
+    if description == project.description {
+        println!("📝 Description unchanged");
+        return Ok(());
+    }
+
+    // Lock only after the editor closes so other commands can write in the meantime
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    update_project_description(&mut config, name, description).await
+}
+
+/// Trim surrounding whitespace; empty descriptions are stored as None
+fn normalize_description(text: &str) -> Option<String> {
+    let trimmed = text.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+async fn update_project_description(
+    config: &mut Config,
+    name: &str,
+    description: Option<String>,
+) -> Result<()> {
+    let Some(project) = config.find_project_by_name_mut(name) else {
+        display_error(ERROR_PROJECT_NOT_FOUND, &format!("'{}'", name));
+        let suggestions = suggest_similar_projects(config, name);
+        display_suggestions(&suggestions);
+        return Err(PmError::ProjectNotFound.into());
+    };
+
+    let cleared = description.is_none();
+    project.description = description;
+    project.updated_at = Utc::now();
+    save_config(config).await?;
+
+    if cleared {
+        display_success(&format!("Cleared description of '{}'", name));
+    } else {
+        display_success(&format!("Updated description of '{}'", name));
+    }
+
+    Ok(())
+}
+
+/// Set or clear a project's color label
+pub async fn handle_color(name: &str, color: Option<&str>, clear: bool) -> Result<()> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+
+    let new_color = if clear {
+        None
+    } else {
+        let color = color.unwrap_or_default().to_lowercase();
+        if let Err(e) = validate_color(&color) {
+            display_error("Invalid color", &e);
+            return Err(PmError::ValidationFailed(e).into());
+        }
+        Some(color)
+    };
+
+    let Some(project) = config.find_project_by_name_mut(name) else {
+        display_error(ERROR_PROJECT_NOT_FOUND, &format!("'{}'", name));
+        let suggestions = suggest_similar_projects(&config, name);
+        display_suggestions(&suggestions);
+        return Err(PmError::ProjectNotFound.into());
+    };
+
+    project.color = new_color.clone();
+    project.updated_at = Utc::now();
+    let project_name = colorize_project_name(project).to_string();
+    save_config(&config).await?;
+
+    match new_color {
+        Some(color) => display_success(&format!("Set color of '{}' to {}", project_name, color)),
+        None => display_success(&format!("Cleared color of '{}'", project_name)),
+    }
+
+    Ok(())
+}
+
+/// Set a project's priority
+pub async fn handle_priority(name: &str, priority: u8) -> Result<()> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+
+    let Some(project) = config.find_project_by_name_mut(name) else {
+        display_error(ERROR_PROJECT_NOT_FOUND, &format!("'{}'", name));
+        let suggestions = suggest_similar_projects(&config, name);
+        display_suggestions(&suggestions);
+        return Err(PmError::ProjectNotFound.into());
+    };
+
+    project.priority = priority;
+    project.updated_at = Utc::now();
+    let project_name = colorize_project_name(project).to_string();
+    save_config(&config).await?;
+
+    display_success(&format!(
+        "Set priority of '{}' to {} {}",
+        project_name,
+        priority,
+        format_priority_stars(priority)
+    ));
+    Ok(())
+}
+
+/// Report projects whose branch is behind `origin`, exiting with status 1 if any are
+pub async fn handle_check_update(
+    project_name: Option<&str>,
+    all: bool,
+    fetch: bool,
+    timeout_secs: u64,
+) -> Result<()> {
+    let config = load_config().await?;
+
+    let mut projects: Vec<&Project> = if all {
+        config
+            .projects
+            .values()
+            .filter(|p| p.is_git_repository)
+            .collect()
+    } else {
+        let project = match project_name {
+            Some(name) => config.find_project_by_name(name),
+            None => config.find_project_by_path(&std::env::current_dir()?),
+        };
+        let Some(project) = project else {
+            let target = project_name.unwrap_or("for the current directory");
+            display_error(ERROR_PROJECT_NOT_FOUND, target);
+            if let Some(name) = project_name {
+                display_suggestions(&suggest_similar_projects(&config, name));
+            }
+            return Err(PmError::ProjectNotFound.into());
+        };
+        vec![project]
+    };
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if projects.is_empty() {
+        println!("📋 No Git projects to check");
+        return Ok(());
+    }
+
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+    let name_width = projects
+        .iter()
+        .map(|p| p.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("NAME".len());
+
+    println!(
+        "{:<name_width$}  {:<20}  {:>6}  {:>5}",
+        "NAME".bold(),
+        "BRANCH".bold(),
+        "BEHIND".bold(),
+        "AHEAD".bold(),
+    );
+
+    let mut behind_count = 0;
+    for project in projects {
+        match crate::utils::check_project_update(&project.path, fetch, timeout) {
+            Ok(check) => {
+                let behind = if check.behind > 0 {
+                    behind_count += 1;
+                    check.behind.to_string().red().bold()
+                } else {
+                    "0".green()
+                };
+                println!(
+                    "{:<name_width$}  {:<20}  {:>6}  {:>5}",
+                    project.name,
+                    check.branch,
+                    behind,
+                    check.ahead,
+                );
+            }
+            Err(e) => println!(
+                "{:<name_width$}  {}",
+                project.name,
+                format!("skipped: {}", e).dimmed(),
+            ),
+        }
+    }
+
+    if behind_count > 0 {
+        println!(
+            "\n⬇️  {} project(s) behind origin{}",
+            behind_count,
+            if fetch { "" } else { " (run with --fetch to refresh)" }
+        );
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Outcome of running the `pm exec` command in one project
+struct ExecResult {
+    name: String,
+    /// `None` if the command could not be started or was killed by a signal
+    exit_code: Option<i32>,
+    duration: std::time::Duration,
+    /// Captured stdout followed by stderr
+    output: String,
+}
+
+impl ExecResult {
+    fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    fn status_label(&self) -> String {
+        let duration = format!("{:.1}s", self.duration.as_secs_f64());
+        match self.exit_code {
+            Some(0) => format!("✅ Done ({})", duration),
+            Some(code) => format!("❌ Failed (exit {}, {})", code, duration),
+            None => format!("❌ Failed ({})", duration),
+        }
+    }
+}
+
+/// Build the shell command line for `pm exec`
+///
+/// A single argument is used as written so it can contain pipes and `&&`; several arguments are
+/// quoted one by one so `pm exec grep "foo bar"` searches for `foo bar`.
+fn exec_command_line(args: &[String]) -> String {
+    match args {
+        [command] => command.clone(),
+        args => args.iter().map(|arg| quote_shell_arg(arg)).collect::<Vec<_>>().join(" "),
+    }
+}
+
+/// Quote one argument for `sh -c` (or `cmd /C` on Windows) unless it only has safe characters
+fn quote_shell_arg(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if safe {
+        arg.to_string()
+    } else if cfg!(windows) {
+        format!("\"{}\"", arg.replace('"', "\"\""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Run `command` through the platform shell in `path`, capturing its output
+async fn run_in_project(command: &str, name: String, path: &Path) -> ExecResult {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    cmd.arg(command)
+        .current_dir(path)
+        .stdin(std::process::Stdio::null());
+
+    let start = std::time::Instant::now();
+    let output = cmd.output().await;
+    let duration = start.elapsed();
+
+    match output {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            ExecResult {
+                name,
+                exit_code: output.status.code(),
+                duration,
+                output: text,
+            }
+        }
+        Err(e) => ExecResult {
+            name,
+            exit_code: None,
+            duration,
+            output: format!("Failed to run command in {}: {}\n", path.display(), e),
+        },
+    }
+}
+
+fn print_exec_output(result: &ExecResult) {
+    println!("{}", format!("==> {} <==", result.name).bold());
+    print!("{}", result.output);
+    if !result.output.is_empty() && !result.output.ends_with('\n') {
+        println!();
+    }
+}
+
+/// `pm exec`: run a shell command in each project's directory, up to `jobs` at once
+///
+/// With `progress`, each project gets a live status line and only the output of
+/// failed commands is printed afterwards. Without it, every project's output is
+/// printed under a header in name order. Exits 1 if any command failed.
+pub async fn handle_exec(command: &[String], tags: &[String], jobs: usize, progress: bool) -> Result<()> {
+    use indicatif::MultiProgress;
+    use std::sync::Arc;
+
+    let command = exec_command_line(command);
+
+    let config = load_config().await?;
+    let mut projects: Vec<&Project> = config
+        .projects
+        .values()
+        .filter(|p| tags.iter().all(|tag| tags_match(tag, &p.tags)))
+        .collect();
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if projects.is_empty() {
+        println!("📋 No projects to run in");
+        return Ok(());
+    }
+
+    let name_width = projects
+        .iter()
+        .map(|p| p.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("NAME".len());
+    let multi = progress.then(MultiProgress::new);
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(jobs));
+
+    let mut handles = Vec::new();
+    for project in &projects {
+        let bar = multi.as_ref().map(|multi| {
+            let bar = multi.add(ProgressBar::new_spinner());
+            bar.set_style(
+                ProgressStyle::default_spinner()
+                    .template("{spinner:.green} {prefix} {msg}")
+                    .unwrap(),
+            );
+            bar.set_prefix(format!("{:<name_width$}", project.name));
+            bar.set_message("Waiting".dimmed().to_string());
+            bar.tick();
+            bar
+        });
+        let semaphore = Arc::clone(&semaphore);
+        let command = command.to_string();
+        let name = project.name.clone();
+        let path = project.path.clone();
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            if let Some(bar) = &bar {
+                bar.set_message("Running");
+                bar.enable_steady_tick(std::time::Duration::from_millis(100));
+            }
+            let result = run_in_project(&command, name, &path).await;
+            if let Some(bar) = &bar {
+                bar.set_style(ProgressStyle::default_spinner().template("  {prefix} {msg}").unwrap());
+                let label = result.status_label();
+                bar.finish_with_message(if result.succeeded() {
+                    label.green().to_string()
+                } else {
+                    label.red().to_string()
+                });
+            }
+            result
+        }));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let result = handle.await?;
+        if !progress {
+            print_exec_output(&result);
+        }
+        results.push(result);
+    }
+
+    let failed = results.iter().filter(|r| !r.succeeded()).count();
+    if progress && failed > 0 {
+        println!();
+        for result in results.iter().filter(|r| !r.succeeded()) {
+            print_exec_output(result);
+        }
+    }
+
+    println!();
+    println!("{:<name_width$}  {}", "NAME".bold(), "STATUS".bold());
+    for result in &results {
+        let label = result.status_label();
+        println!(
+            "{:<name_width$}  {}",
+            result.name,
+            if result.succeeded() { label.green() } else { label.red() }
+        );
+    }
+
+    println!(
+        "\n{} succeeded, {} failed",
+        results.len() - failed,
+        failed
+    );
+    if failed > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Print one page of projects as JSON, wrapped with pagination metadata
+fn print_project_page_json(
+    page_data: &[ProjectData],
+    total: usize,
+    options: &ListOptions,
+) -> Result<()> {
+    let projects: Vec<serde_json::Value> = page_data
+        .iter()
+        .map(|(project, last_accessed, access_count)| {
+            let mut value = serde_json::to_value(project)?;
+            if let Some(object) = value.as_object_mut() {
+                object.insert("last_accessed".to_string(), serde_json::json!(last_accessed));
+                object.insert("access_count".to_string(), serde_json::json!(access_count));
+                if options.show_remote_url {
+                    object.insert("remote_url".to_string(), serde_json::json!(project.remote_url));
+                }
+            }
+            Ok(value)
+        })
+        .collect::<Result<_>>()?;
+
+    let output = serde_json::json!({
+        "total": total,
+        "offset": options.effective_offset(),
+        "limit": options.limit,
+        "projects": projects,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// What `pm switch` should switch to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwitchTarget {
+    /// A project by name
+    Name(String),
+    /// Pick a project interactively
+    Picker,
+    /// The previous entry in the switch history
+    Back,
+    /// The next entry in the switch history (after `Back`)
+    Forward,
+    /// Pick from the recent switch history
+    History,
+}
+
+impl SwitchTarget {
+    /// Build a target from the mutually exclusive `pm switch` arguments
+    pub fn from_args(name: Option<&str>, back: bool, forward: bool, history: bool) -> Self {
+        match (name, back, forward, history) {
+            (Some(name), ..) => SwitchTarget::Name(name.to_string()),
+            (None, true, ..) => SwitchTarget::Back,
+            (None, _, true, _) => SwitchTarget::Forward,
+            (None, _, _, true) => SwitchTarget::History,
+            _ => SwitchTarget::Picker,
+        }
+    }
+}
+
+pub async fn handle_switch_target(target: SwitchTarget) -> Result<()> {
+    let config = load_config().await?;
+    match target {
+        SwitchTarget::Name(name) => handle_switch(&config, &name).await,
+        SwitchTarget::Picker => handle_switch_interactive(&config).await,
+        SwitchTarget::Back => handle_switch_history_step(1).await,
+        SwitchTarget::Forward => handle_switch_history_step(-1).await,
+        SwitchTarget::History => handle_switch_history(&config).await,
+    }
+}
+
+pub async fn handle_switch(config: &Config, name: &str) -> Result<()> {
+    if config.projects.is_empty() {
+        display_no_projects();
+        return Err(PmError::NoProjectsFound.into());
+    }
+
+    if let Some(project) = config.find_project_by_name(name) {
+        let project_id = project.id;
+        switch_to_project(config, project_id, true).await
+    } else {
+        display_error(ERROR_PROJECT_NOT_FOUND, &format!("'{}'", name));
+
+        let suggestions = suggest_similar_projects(config, name);
+        display_suggestions(&suggestions);
+
+        Err(PmError::ProjectNotFound.into())
+    }
+}
+
+/// Switch to a registered project, optionally adding it to the switch history
+///
+/// The config lock is only held while recording the access, not while hooks run.
+async fn switch_to_project(config: &Config, project_id: Uuid, record_history: bool) -> Result<()> {
+    let project = config
+        .projects
+        .get(&project_id)
+        .ok_or(PmError::ProjectNotFound)?;
+    let project_name = project.name.clone();
+    let project_path = project.path.clone();
+    let hook_context = HookContext::for_project(project);
+
+    // Check if project path still exists
+    if !project_path.exists() {
+        display_error(
+            ERROR_PROJECT_NOT_FOUND,
+            &format!("path no longer exists: {}", project_path.display()),
+        );
+        println!("\n💡 Suggestions:");
+        println!("  - Update the project path");
+        println!("  - Remove the project: pm project remove {}", project_name);
+        return Err(PmError::ProjectPathNotFound.into());
+    }
+
+    // Record access before switching
+    let (last_accessed, access_count) = record_switch_access(project_id, record_history).await?;
+
+    display_switch_info(&project_name, access_count, last_accessed);
+
+    dispatch_hook(HookEvent::PreSwitch, &hook_context).await;
+
+    if let Err(e) = std::env::set_current_dir(&project_path) {
+        display_error(ERROR_DIRECTORY_CHANGE, &e.to_string());
+        println!("   Path: {}", project_path.display());
+        return Err(PmError::DirectoryChangeFailed.into());
+    }
+
+    dispatch_hook(HookEvent::ProjectSwitch, &hook_context).await;
+
+    display_switch_success(&project_path);
+    
+    // Output for shell integration (parsed by shell function)
+    println!("Switched to: {}", project_path.display());
+
+    Ok(())
+}
+
+/// Record an access (and optionally a switch history entry) for `project_id`,
+/// returning the updated access info for display
+async fn record_switch_access(
+    project_id: Uuid,
+    record_history: bool,
+) -> Result<(Option<DateTime<Utc>>, u32)> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    config.record_project_access(project_id);
+    if record_history {
+        config.record_switch(project_id);
+    }
+
+    // Save config with updated access tracking
+    if let Err(e) = save_config(&config).await {
+        display_warning(&format!("Failed to save access tracking: {}", e));
+        // Continue anyway, don't fail the switch operation
+    }
+
+    Ok(config.get_project_access_info(project_id))
+}
+
+/// `pm switch --back` (steps = 1) and `pm switch --forward` (steps = -1)
+async fn handle_switch_history_step(steps: isize) -> Result<()> {
+    let (config, project_id) = {
+        let _lock = lock_config().await?;
+        let mut config = load_config().await?;
+
+        // Skip entries whose project has been removed since it was recorded
+        let project_id = loop {
+            let Some(project_id) = config.step_switch_history(steps) else {
+                let direction = if steps > 0 { "back" } else { "forward" };
+                return Err(anyhow::anyhow!("No project to go {} to in the switch history", direction));
+            };
+            if config.projects.contains_key(&project_id) {
+                break project_id;
+            }
+        };
+        save_config(&config).await?;
+        (config, project_id)
+    };
+
+    switch_to_project(&config, project_id, false).await
+}
+
+/// `pm switch --history`: pick one of the most recent switches
+async fn handle_switch_history(config: &Config) -> Result<()> {
+    let (history, position) = config.get_switch_history();
+    let entries: Vec<(usize, Project)> = history
+        .iter()
+        .enumerate()
+        .filter_map(|(index, id)| config.projects.get(id).map(|project| (index, project.clone())))
+        .take(SWITCH_HISTORY_DISPLAY_LIMIT)
+        .collect();
+
+    if entries.is_empty() {
+        display_info("Switch history is empty. Switch to a project with 'pm switch <name>' first");
+        return Ok(());
+    }
+
+    let options: Vec<String> = entries
+        .iter()
+        .map(|(index, project)| {
+            let marker = if *index == position { " (current)" } else { "" };
+            format!("{} - {}{}", project.name, project.path.display(), marker)
+        })
+        .collect();
+
+    let selection = handle_inquire_error(
+        Select::new("🕘 Switch history:", options)
+            .with_starting_cursor(entries.iter().position(|(index, _)| *index == position).unwrap_or(0))
+            .raw_prompt(),
+    )?;
+    let (_, project) = entries.get(selection.index).ok_or(PmError::ProjectNotFound)?;
+
+    switch_to_project(config, project.id, true).await
+}
+
+/// Switch to a project chosen from an interactive picker
+pub async fn handle_switch_interactive(config: &Config) -> Result<()> {
+    if config.projects.is_empty() {
+        display_no_projects();
+        return Err(PmError::NoProjectsFound.into());
+    }
+
+    let mut projects: Vec<Project> = config.projects.values().cloned().collect();
+    sort_projects_by_recent_access(config, &mut projects);
+
+    let project = pick_project(config, projects, "🔄 Select project to switch to:")?;
+    handle_switch(config, &project.name).await
+}
+
+/// Most recently accessed projects first, then alphabetically
+fn sort_projects_by_recent_access(config: &Config, projects: &mut [Project]) {
+    projects.sort_by(|a, b| {
+        let (a_accessed, _) = config.get_project_access_info(a.id);
+        let (b_accessed, _) = config.get_project_access_info(b.id);
+        b_accessed.cmp(&a_accessed).then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Format a single picker entry: name, path and tags, plus a preview column
+/// with description and access details when running under fzf
+fn format_picker_option(config: &Config, project: &Project) -> String {
+    let tags_display = if project.tags.is_empty() {
+        String::new()
     } else {
-        filtered_project_data
+        format!(" [{}]", project.tags.join(", "))
     };
+    let option = format!("{} - {}{}", project.name, project.path.display(), tags_display);
 
-    display_project_list_header(limited_project_data.len());
+    let Some(preview_columns) = std::env::var("FZF_PREVIEW_COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse::<usize>().ok())
+    else {
+        return option;
+    };
 
-    for (project, last_accessed, access_count) in limited_project_data {
-        if verbose {
-            display_project_detailed(&project, last_accessed, access_count);
-        } else {
-            display_project_simple(&project, last_accessed);
-        }
+    let (last_accessed, access_count) = config.get_project_access_info(project.id);
+    let mut preview = Vec::new();
+    if let Some(description) = &project.description {
+        preview.push(description.clone());
+    }
+    if let Some(last_accessed) = last_accessed {
+        preview.push(format!("accessed {}", format_relative_time(last_accessed)));
+    }
+    if access_count > 0 {
+        preview.push(format!("{} visits", access_count));
+    }
+    if preview.is_empty() {
+        return option;
     }
 
-    Ok(())
+    let preview: String = preview.join(" · ").chars().take(preview_columns).collect();
+    format!("{}  │ {}", option, preview)
 }
 
-pub async fn handle_switch(config: &mut Config, name: &str) -> Result<()> {
-    if config.projects.is_empty() {
-        display_no_projects();
+/// Fuzzy-searchable single-select over the given projects
+fn pick_project(config: &Config, projects: Vec<Project>, prompt: &str) -> Result<Project> {
+    if projects.is_empty() {
+        display_no_matches();
         return Err(PmError::NoProjectsFound.into());
     }
 
-    if let Some(project) = config.find_project_by_name(name) {
-        let project_id = project.id;
-        let project_name = project.name.clone();
-        let project_path = project.path.clone();
-
-        // Check if project path still exists
-        if !project_path.exists() {
-            display_error(
-                ERROR_PROJECT_NOT_FOUND,
-                &format!("path no longer exists: {}", project_path.display()),
-            );
-            println!("\n💡 Suggestions:");
-            println!("  - Update the project path");
-            println!("  - Remove the project: pm project remove {}", project_name);
-            return Err(PmError::ProjectPathNotFound.into());
-        }
+    let options: Vec<String> = projects
+        .iter()
+        .map(|project| format_picker_option(config, project))
+        .collect();
 
-        // Record access before switching
-        config.record_project_access(project_id);
+    let selection = handle_inquire_error(
+        Select::new(prompt, options)
+            .with_help_message("↑↓ navigate • Enter to select • Ctrl+C to cancel • Type to filter")
+            .with_page_size(15)
+            .raw_prompt(),
+    )?;
 
-        // Get access info for display
-        let (last_accessed, access_count) = config.get_project_access_info(project_id);
+    projects
+        .into_iter()
+        .nth(selection.index)
+        .ok_or_else(|| PmError::ProjectNotFound.into())
+}
 
-        display_switch_info(&project_name, access_count, last_accessed);
+/// Command that opens projects: `ide`, then `settings.default_ide`, then the editor
+fn resolve_open_command(config: &Config, ide: Option<Ide>) -> Result<String> {
+    match resolve_ide_command(ide, config.settings.default_ide)? {
+        Some(command) => Ok(command),
+        None => Ok(resolve_editor(config.settings.editor.as_deref())),
+    }
+}
 
-        if let Err(e) = std::env::set_current_dir(&project_path) {
-            display_error(ERROR_DIRECTORY_CHANGE, &e.to_string());
-            println!("   Path: {}", project_path.display());
-            return Err(PmError::DirectoryChangeFailed.into());
-        }
+/// `pm open`: open a project in an IDE or the configured editor
+pub async fn handle_open(name: Option<&str>, ide: Option<Ide>) -> Result<()> {
+    let config = load_config().await?;
 
-        // Save config with updated access tracking
-        if let Err(e) = save_config(config).await {
-            display_warning(&format!("Failed to save access tracking: {}", e));
-            // Continue anyway, don't fail the switch operation
+    let project = match name {
+        Some(name) => config.find_project_by_name(name),
+        None => config.find_project_by_path(&std::env::current_dir()?),
+    };
+    let Some(project) = project.cloned() else {
+        display_error(ERROR_PROJECT_NOT_FOUND, name.unwrap_or("for the current directory"));
+        if let Some(name) = name {
+            display_suggestions(&suggest_similar_projects(&config, name));
         }
+        return Err(PmError::ProjectNotFound.into());
+    };
 
-        display_switch_success(&project_path);
-        
-        // Output for shell integration (parsed by shell function)
-        println!("Switched to: {}", project_path.display());
+    let command = resolve_open_command(&config, ide).inspect_err(|_| {
+        println!("💡 Install the IDE's command line launcher or pick another with --ide");
+    })?;
+    open_project_in_editor(project, &command).await
+}
 
-        Ok(())
-    } else {
-        display_error(ERROR_PROJECT_NOT_FOUND, &format!("'{}'", name));
+/// Open a project directory in the user's editor and record the access
+async fn open_project_in_editor(project: Project, editor: &str) -> Result<()> {
+    if !project.path.exists() {
+        display_error(
+            ERROR_PROJECT_NOT_FOUND,
+            &format!("path no longer exists: {}", project.path.display()),
+        );
+        return Err(PmError::ProjectPathNotFound.into());
+    }
 
-        let suggestions = suggest_similar_projects(config, name);
-        display_suggestions(&suggestions);
+    println!("🚀 Opening {} in {}", project.name, editor);
 
-        Err(PmError::ProjectNotFound.into())
+    let status = editor_command(editor)
+        .arg(&project.path)
+        .current_dir(&project.path)
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            display_warning(&format!("Editor exited with status: {}", status));
+        }
+        Err(e) => {
+            display_error("Failed to launch editor", &format!("{}: {}", editor, e));
+            println!("💡 Set your editor with: pm config set settings.editor <command>");
+            return Err(PmError::EditorLaunchFailed.into());
+        }
     }
+
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    config.record_project_access(project.id);
+    if let Err(e) = save_config(&config).await {
+        display_warning(&format!("Failed to save access tracking: {}", e));
+    }
+
+    Ok(())
 }
 
 fn suggest_similar_projects(config: &Config, target: &str) -> Vec<String> {
@@ -468,106 +2237,565 @@ fn suggest_similar_projects(config: &Config, target: &str) -> Vec<String> {
                 || name.chars().take(3).collect::<String>().to_lowercase()
                     == target.chars().take(3).collect::<String>().to_lowercase()
         })
-        .cloned()
-        .collect()
+        .cloned()
+        .collect()
+}
+
+async fn update_git_times_by_ids(project_ids: &[uuid::Uuid]) {
+    use crate::config::load_config;
+    use crate::constants::GIT_UPDATE_INTERVAL_HOURS;
+
+    for &project_id in project_ids {
+        tokio::spawn(async move {
+            if let Ok(config) = load_config().await {
+                if let Some(project) = config.projects.get(&project_id) {
+                    let needs_update = project.git_updated_at.is_none()
+                        || (Utc::now() - project.git_updated_at.unwrap()).num_hours()
+                            >= GIT_UPDATE_INTERVAL_HOURS;
+                    if needs_update {
+                        let project_path = project.path.clone();
+                        if let Ok(Some(git_time)) = get_last_git_commit_time(&project_path) {
+                            let Ok(_lock) = lock_config().await else {
+                                return;
+                            };
+                            if let Ok(mut config) = load_config().await {
+                                if let Some(p) = config.projects.get_mut(&project_id) {
+                                    if p.git_updated_at != Some(git_time) {
+                                        p.git_updated_at = Some(git_time);
+                                        let _ = save_config(&config).await;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Last activity for `--since-modified`: the last commit for Git repositories and the
+/// directory mtime otherwise, each falling back to the other when unavailable
+fn last_modified_at(project: &Project) -> Option<DateTime<Utc>> {
+    if project.is_git_repository {
+        project
+            .git_updated_at
+            .or_else(|| get_directory_modified_time(&project.path))
+    } else {
+        get_directory_modified_time(&project.path).or(project.git_updated_at)
+    }
+}
+
+fn get_filtered_project_data(config: &Config, options: &ListOptions) -> Result<Vec<ProjectData>> {
+    let tags = &options.tags;
+    let tags_any = &options.tags_any;
+    let recent = &options.recent;
+
+    let last_access_cutoff = match &options.since_last_access {
+        Some(duration_str) => {
+            let filter = parse_time_filter(duration_str).map_err(|e| {
+                display_error("Invalid --since-last-access value", &e);
+                PmError::ValidationFailed(e)
+            })?;
+            Some(filter.cutoff(Utc::now()))
+        }
+        None => None,
+    };
+
+    let modified_cutoff = match &options.since_modified {
+        Some(duration_str) => {
+            let filter = parse_time_filter(duration_str).map_err(|e| {
+                display_error("Invalid --since-modified value", &e);
+                PmError::ValidationFailed(e)
+            })?;
+            Some(filter.cutoff(Utc::now()))
+        }
+        None => None,
+    };
+
+    let stale_cutoff = options.stale_threshold.map(|threshold| Utc::now() - threshold);
+
+    let recent_cutoff = recent.as_ref().map(|recent_str| match parse_time_filter(recent_str) {
+        Ok(filter) => filter.cutoff(Utc::now()),
+        Err(_) => {
+            display_warning(&format!(
+                "Invalid time format: {}. Using default of {} days.",
+                recent_str, DEFAULT_RECENT_DAYS
+            ));
+            Utc::now() - chrono::Duration::days(DEFAULT_RECENT_DAYS)
+        }
+    });
+
+    let mut project_data: Vec<ProjectData> = config
+        .projects
+        .values()
+        .filter(|project| {
+            // Tags filter (AND logic - all tags must match; `work` also matches `work/*`)
+            if !tags.is_empty() && !tags.iter().all(|tag| tags_match(tag, &project.tags)) {
+                return false;
+            }
+
+            // Tags any filter (OR logic - any tag can match)
+            if !tags_any.is_empty() && !tags_any.iter().any(|tag| tags_match(tag, &project.tags)) {
+                return false;
+            }
+
+            // Recent filter
+            if let Some(cutoff) = recent_cutoff {
+                let last_activity = project.git_updated_at.unwrap_or(project.updated_at);
+                if last_activity < cutoff {
+                    return false;
+                }
+            }
+
+            // Stale filter (projects without a known commit count as stale)
+            if let Some(cutoff) = stale_cutoff {
+                if project.git_updated_at.is_some_and(|committed| committed >= cutoff) {
+                    return false;
+                }
+            }
+
+            // Git repository filter
+            if let Some(git) = options.git {
+                if project.is_git_repository != git {
+                    return false;
+                }
+            }
+
+            // Gap-finding filters
+            if options.missing_description
+                && project.description.as_deref().is_some_and(|d| !d.trim().is_empty())
+            {
+                return false;
+            }
+            if options.missing_tags && !project.tags.is_empty() {
+                return false;
+            }
+
+            // Modified since cutoff (projects without any timestamp are excluded)
+            if let Some(cutoff) = modified_cutoff {
+                if last_modified_at(project).is_none_or(|modified| modified < cutoff) {
+                    return false;
+                }
+            }
+
+            // Not accessed since cutoff (never-accessed projects count as cold)
+            if let Some(cutoff) = last_access_cutoff {
+                let (last_accessed, _) = config.get_project_access_info(project.id);
+                if last_accessed.is_some_and(|accessed| accessed >= cutoff) {
+                    return false;
+                }
+            }
+
+            true
+        })
+        .cloned()
+        .map(|project| {
+            let (last_accessed, access_count) = config.get_project_access_info(project.id);
+            (project, last_accessed, access_count)
+        })
+        .filter(|(project, last_accessed, access_count)| {
+            options.access_frequency.is_none_or(|frequency| {
+                compute_access_frequency(*access_count, *last_accessed, project.created_at, Utc::now())
+                    == frequency
+            })
+        })
+        .collect();
+
+    // Path existence filter, applied last so only the remaining projects hit the filesystem
+    if let Some(exists) = options.exists {
+        if project_data.len() > PATH_EXISTS_WARNING_THRESHOLD {
+            display_warning(&format!(
+                "Checking {} project paths; this can be slow on network filesystems",
+                project_data.len()
+            ));
+        }
+        project_data.retain(|(project, _, _)| project.path.exists() == exists);
+    }
+
+    // Before the display sorts, so the entry kept per path does not depend on them
+    if options.unique_paths || options.show_duplicates {
+        // Canonicalize once per project; paths are case-insensitive on Windows
+        let keys: Vec<PathBuf> = project_data
+            .iter()
+            .map(|(project, _, _)| path_comparison_key(&canonical_path(&project.path)))
+            .collect();
+
+        let keep: Vec<bool> = if options.show_duplicates {
+            let mut path_counts: HashMap<&PathBuf, usize> = HashMap::new();
+            for key in &keys {
+                *path_counts.entry(key).or_default() += 1;
+            }
+            keys.iter().map(|key| path_counts[key] > 1).collect()
+        } else {
+            // Index of the most recently updated entry per path
+            let mut newest: HashMap<&PathBuf, usize> = HashMap::new();
+            for (index, key) in keys.iter().enumerate() {
+                let entry = newest.entry(key).or_insert(index);
+                if project_data[index].0.updated_at > project_data[*entry].0.updated_at {
+                    *entry = index;
+                }
+            }
+            (0..keys.len()).map(|index| newest[&keys[index]] == index).collect()
+        };
+        let mut keep = keep.into_iter();
+        project_data.retain(|_| keep.next().unwrap_or(false));
+    }
+
+    // Sort projects: git_updated_at (later), updated_at, created_at
+    project_data.sort_by(|a, b| {
+        b.0.git_updated_at
+            .cmp(&a.0.git_updated_at)
+            .then_with(|| b.0.updated_at.cmp(&a.0.updated_at))
+            .then_with(|| b.0.created_at.cmp(&a.0.created_at))
+    });
+
+    // Stalest first; projects without a known commit lead
+    if stale_cutoff.is_some() {
+        project_data.sort_by_key(|(project, _, _)| project.git_updated_at);
+    }
+
+    // Stable sort keeps the time order within each priority
+    if options.sort == ListSort::Priority {
+        project_data.sort_by_key(|(project, _, _)| std::cmp::Reverse(project.priority));
+    }
+
+    Ok(project_data)
+}
+
+/// Find a project by full UUID or a unique prefix of it (such as the short ID from `pm list --show-id`)
+fn find_project_by_id_prefix<'a>(config: &'a Config, id: &str) -> Result<Option<&'a Project>> {
+    let prefix = id.trim().to_lowercase();
+    if prefix.chars().count() < MIN_PROJECT_ID_PREFIX_LENGTH {
+        display_error(
+            "Project ID too short",
+            &format!("Use at least {} characters of the ID (see pm ls --show-id)", MIN_PROJECT_ID_PREFIX_LENGTH),
+        );
+        return Err(PmError::ValidationFailed(format!(
+            "project ID prefix must be at least {} characters",
+            MIN_PROJECT_ID_PREFIX_LENGTH
+        ))
+        .into());
+    }
+    let matches: Vec<&Project> = config
+        .projects
+        .values()
+        .filter(|project| project.id.to_string().starts_with(&prefix))
+        .collect();
+
+    if matches.len() > 1 {
+        let names: Vec<&str> = matches.iter().map(|project| project.name.as_str()).collect();
+        display_error("Ambiguous project ID", &format!("'{}' matches {}", id, names.join(", ")));
+        return Err(PmError::ValidationFailed(format!("ambiguous project ID '{}'", id)).into());
+    }
+    Ok(matches.first().copied())
+}
+
+/// `pm info --format json`: the project plus computed fields
+#[derive(serde::Serialize)]
+struct ProjectInfo<'a> {
+    #[serde(flatten)]
+    project: &'a Project,
+    path_exists: bool,
+    last_accessed: Option<chrono::DateTime<Utc>>,
+    access_count: u32,
+    /// This machine's last `MAX_RECENT_ACCESSES` access times, newest first
+    recent_accesses: &'a [chrono::DateTime<Utc>],
+    access_frequency: AccessFrequency,
+    language: Option<String>,
+    /// Capped at `PROJECT_FILE_COUNT_LIMIT`
+    file_count: usize,
+    git: Option<GitStatusInfo>,
+}
+
+/// Show everything PM knows about one project
+pub async fn handle_info(name: Option<&str>, id: Option<&str>, format: &str) -> Result<()> {
+    let config = load_config().await?;
+    let project = match (name, id) {
+        (_, Some(id)) => find_project_by_id_prefix(&config, id)?,
+        (Some(name), None) => config.find_project_by_name(name),
+        (None, None) => config.find_project_by_path(&std::env::current_dir()?),
+    };
+    let Some(project) = project else {
+        let target = id.or(name).unwrap_or("for the current directory");
+        display_error(ERROR_PROJECT_NOT_FOUND, target);
+        return Err(PmError::ProjectNotFound.into());
+    };
+
+    let (last_accessed, access_count) = config.get_project_access_info(project.id);
+    let path_exists = project.path.exists();
+    let info = ProjectInfo {
+        project,
+        path_exists,
+        last_accessed,
+        access_count,
+        recent_accesses: config.get_recent_accesses(project.id),
+        access_frequency: compute_access_frequency(access_count, last_accessed, project.created_at, Utc::now()),
+        language: path_exists
+            .then(|| detect_project_language(&project.path).into_iter().next())
+            .flatten(),
+        file_count: if path_exists { count_project_files(&project.path) } else { 0 },
+        git: project
+            .is_git_repository
+            .then(|| read_git_status(&project.path))
+            .flatten(),
+    };
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
+
+    display_project_detailed(project, last_accessed, access_count);
+    println!("  Access Frequency: {}", format!("{:?}", info.access_frequency).to_lowercase());
+    if !info.recent_accesses.is_empty() {
+        println!("  Recent Accesses:");
+        for accessed in info.recent_accesses {
+            println!("    {}", accessed.format("%Y-%m-%d %H:%M:%S"));
+        }
+    }
+    if !path_exists {
+        println!("  {}", "Path does not exist".red());
+        return Ok(());
+    }
+    if let Some(language) = &info.language {
+        println!("  Language: {}", language);
+    }
+    if info.file_count >= PROJECT_FILE_COUNT_LIMIT {
+        println!("  Files: {}+", info.file_count);
+    } else {
+        println!("  Files: {}", info.file_count);
+    }
+    if let Some(git) = &info.git {
+        let state = if git.is_dirty { "dirty".yellow() } else { "clean".green() };
+        println!(
+            "  Git: {} ({}, ahead {}, behind {})",
+            git.branch_name.as_deref().unwrap_or("detached HEAD"),
+            state,
+            git.ahead,
+            git.behind
+        );
+    }
+
+    Ok(())
+}
+
+/// Print a project's remote URL(s) converted to `format`, undecorated for scripting
+pub async fn handle_url(name: Option<&str>, format: UrlFormat, remote: Option<&str>) -> Result<()> {
+    let config = load_config().await?;
+    let project = match name {
+        Some(name) => config.find_project_by_name(name),
+        None => config.find_project_by_path(&std::env::current_dir()?),
+    };
+    let Some(project) = project else {
+        display_error(ERROR_PROJECT_NOT_FOUND, name.unwrap_or("for the current directory"));
+        return Err(PmError::ProjectNotFound.into());
+    };
+
+    let repo = Repository::open(&project.path).map_err(|e| {
+        display_error("Not a Git repository", &e.to_string());
+        PmError::GitOperationFailed
+    })?;
+
+    let remote_names: Vec<String> = match remote {
+        Some(remote) => vec![remote.to_string()],
+        None if repo.find_remote("origin").is_ok() => vec!["origin".to_string()],
+        None => repo
+            .remotes()?
+            .iter()
+            .flatten()
+            .map(str::to_string)
+            .collect(),
+    };
+
+    if remote_names.is_empty() {
+        display_error("No Git remotes configured", &project.name);
+        return Err(PmError::GitOperationFailed.into());
+    }
+
+    let show_names = remote_names.len() > 1;
+    for remote_name in &remote_names {
+        let found = repo.find_remote(remote_name).map_err(|_| {
+            display_error("Remote not found", remote_name);
+            PmError::GitOperationFailed
+        })?;
+        let Some(url) = found.url() else { continue };
+        let url = convert_remote_url(url, format).unwrap_or_else(|| url.to_string());
+
+        if show_names {
+            println!("{}\t{}", remote_name, url);
+        } else {
+            println!("{}", url);
+        }
+    }
+
+    Ok(())
+}
+
+/// Copy a project's path, remote URL or name to the system clipboard
+///
+/// Without a usable clipboard (e.g. headless servers) the value is printed to stdout instead.
+pub async fn handle_copy_path(name: Option<&str>, url: bool, copy_name: bool) -> Result<()> {
+    let config = load_config().await?;
+    let project = match name {
+        Some(name) => config.find_project_by_name(name),
+        None => config.find_project_by_path(&std::env::current_dir()?),
+    };
+    let Some(project) = project else {
+        display_error(ERROR_PROJECT_NOT_FOUND, name.unwrap_or("for the current directory"));
+        return Err(PmError::ProjectNotFound.into());
+    };
+
+    let (label, value) = if url {
+        match crate::utils::get_git_remote_url(&project.path)? {
+            Some(remote_url) => ("remote URL", remote_url),
+            None => {
+                display_error("No origin remote configured", &project.name);
+                return Err(PmError::GitOperationFailed.into());
+            }
+        }
+    } else if copy_name {
+        ("name", project.name.clone())
+    } else {
+        ("path", project.path.display().to_string())
+    };
+
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(value.clone())) {
+        Ok(()) => println!("📋 Copied {} of '{}': {}", label, project.name, value),
+        Err(e) => {
+            eprintln!("⚠️  Clipboard unavailable ({}); printing instead", e);
+            println!("{}", value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Repository changes reported by `pm watch`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// A new commit on the current branch
+    Commit,
+    /// HEAD moved to a different branch
+    BranchChange,
+    /// The working tree went from clean to dirty
+    Dirty,
+    /// The working tree went from dirty to clean
+    Clean,
+}
+
+/// Git state compared between `pm watch` refreshes
+#[derive(Debug, Clone, PartialEq)]
+struct WatchSnapshot {
+    head: Option<git2::Oid>,
+    branch: Option<String>,
+    dirty: bool,
+}
+
+impl WatchSnapshot {
+    fn read(path: &Path) -> Option<Self> {
+        crate::utils::invalidate_git_dirty_cache(path);
+        let repo = Repository::open(path).ok()?;
+        let head = repo.head().ok();
+        let status = crate::commands::status::read_git_status(path)?;
+        Some(Self {
+            head: head.as_ref().and_then(|head| head.target()),
+            branch: status.branch_name,
+            dirty: status.is_dirty,
+        })
+    }
 }
 
-async fn update_git_times_by_ids(project_ids: &[uuid::Uuid]) {
-    use crate::config::load_config;
-    use crate::constants::GIT_UPDATE_INTERVAL_HOURS;
+/// Watch a project's repository and print commits, branch switches and dirty/clean transitions
+pub async fn handle_watch(name: Option<&str>, events: &[WatchEvent]) -> Result<()> {
+    let config = load_config().await?;
+    let project = match name {
+        Some(name) => config.find_project_by_name(name),
+        None => config.find_project_by_path(&std::env::current_dir()?),
+    };
+    let Some(project) = project else {
+        display_error(ERROR_PROJECT_NOT_FOUND, name.unwrap_or("for the current directory"));
+        return Err(PmError::ProjectNotFound.into());
+    };
 
-    for &project_id in project_ids {
-        tokio::spawn(async move {
-            if let Ok(config) = load_config().await {
-                if let Some(project) = config.projects.get(&project_id) {
-                    let needs_update = project.git_updated_at.is_none()
-                        || (Utc::now() - project.git_updated_at.unwrap()).num_hours()
-                            >= GIT_UPDATE_INTERVAL_HOURS;
-                    if needs_update {
-                        let project_path = project.path.clone();
-                        if let Ok(Some(git_time)) = get_last_git_commit_time(&project_path) {
-                            if let Ok(mut config) = load_config().await {
-                                if let Some(p) = config.projects.get_mut(&project_id) {
-                                    p.git_updated_at = Some(git_time);
-                                    let _ = save_config(&config).await;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        });
-    }
+    let Some(mut previous) = WatchSnapshot::read(&project.path) else {
+        display_error("Not a Git repository", &project.path.display().to_string());
+        return Err(PmError::GitOperationFailed.into());
+    };
+
+    let wants = |event: WatchEvent| events.is_empty() || events.contains(&event);
+
+    println!("👀 Watching '{}' ({})", project.name, project.path.display());
+    println!(
+        "   Branch: {}{}",
+        previous.branch.as_deref().unwrap_or("(detached)"),
+        if previous.dirty { " (dirty)" } else { "" }
+    );
+    println!("   Press Ctrl+C to stop");
+
+    watch_for_changes(
+        Some(&project.path),
+        notify::RecursiveMode::Recursive,
+        crate::commands::status::is_relevant_change,
+        None,
+        |_| {
+            report_watch_changes(&project.path, &mut previous, wants);
+            std::future::ready(Ok(()))
+        },
+    )
+    .await
 }
 
-fn get_filtered_project_data(
-    config: &Config,
-    tags: &[String],
-    tags_any: &[String],
-    recent: &Option<String>,
-) -> Result<Vec<ProjectData>> {
-    let mut project_data: Vec<ProjectData> = config
-        .projects
-        .values()
-        .filter(|project| {
-            // Tags filter (AND logic - all tags must match)
-            if !tags.is_empty() {
-                let project_tags: HashSet<String> = project.tags.iter().cloned().collect();
-                if !tags.iter().all(|tag| project_tags.contains(tag)) {
-                    return false;
-                }
-            }
+/// Print the watched events that happened since `previous` and make the current state
+/// the new baseline
+fn report_watch_changes(path: &Path, previous: &mut WatchSnapshot, wants: impl Fn(WatchEvent) -> bool) {
+    let Some(current) = WatchSnapshot::read(path) else {
+        return;
+    };
+    let time = chrono::Local::now().format("%H:%M:%S");
+
+    if current.branch != previous.branch && wants(WatchEvent::BranchChange) {
+        println!(
+            "[{}] 🔀 Branch changed: {} → {}",
+            time,
+            previous.branch.as_deref().unwrap_or("(detached)"),
+            current.branch.as_deref().unwrap_or("(detached)")
+        );
+    } else if current.head != previous.head && wants(WatchEvent::Commit) {
+        if let Some(oid) = current.head {
+            let summary = Repository::open(path)
+                .ok()
+                .and_then(|repo| {
+                    repo.find_commit(oid)
+                        .ok()
+                        .and_then(|commit| commit.summary().map(str::to_string))
+                })
+                .unwrap_or_default();
+            let hash = oid.to_string();
+            println!("[{}] 📝 Commit {} {}", time, &hash[..7], summary);
+        }
+    }
 
-            // Tags any filter (OR logic - any tag can match)
-            if !tags_any.is_empty() {
-                let project_tags: HashSet<String> = project.tags.iter().cloned().collect();
-                if !tags_any.iter().any(|tag| project_tags.contains(tag)) {
-                    return false;
-                }
-            }
+    if current.dirty && !previous.dirty && wants(WatchEvent::Dirty) {
+        println!("[{}] {}", time, "⚠️  Working tree has uncommitted changes".yellow());
+    } else if !current.dirty && previous.dirty && wants(WatchEvent::Clean) {
+        println!("[{}] {}", time, "✅ Working tree is clean".green());
+    }
 
-            // Recent filter
-            if let Some(recent_str) = recent {
-                match parse_time_duration(recent_str) {
-                    Ok(duration) => {
-                        let cutoff = Utc::now() - duration;
-                        let last_activity = project.git_updated_at.unwrap_or(project.updated_at);
-                        if last_activity < cutoff {
-                            return false;
-                        }
-                    }
-                    Err(_) => {
-                        display_warning(&format!(
-                            "Invalid time format: {}. Using default of {} days.",
-                            recent_str, DEFAULT_RECENT_DAYS
-                        ));
-                        let cutoff = Utc::now() - chrono::Duration::days(DEFAULT_RECENT_DAYS);
-                        let last_activity = project.git_updated_at.unwrap_or(project.updated_at);
-                        if last_activity < cutoff {
-                            return false;
-                        }
-                    }
-                }
-            }
+    *previous = current;
+}
 
-            true
-        })
-        .cloned()
-        .map(|project| {
-            let (last_accessed, access_count) = config.get_project_access_info(project.id);
-            (project, last_accessed, access_count)
-        })
-        .collect();
+/// Print the JSON Schema for `Project` (as JSON or YAML)
+pub fn handle_json_schema(format: &crate::ExportFormat) -> Result<()> {
+    let schema = schemars::schema_for!(Project);
 
-    // Sort projects: git_updated_at (later), updated_at, created_at
-    project_data.sort_by(|a, b| {
-        b.0.git_updated_at
-            .cmp(&a.0.git_updated_at)
-            .then_with(|| b.0.updated_at.cmp(&a.0.updated_at))
-            .then_with(|| b.0.created_at.cmp(&a.0.created_at))
-    });
+    let content = match format {
+        crate::ExportFormat::Json => serde_json::to_string_pretty(&schema)?,
+        crate::ExportFormat::Yaml => serde_yaml::to_string(&schema)?,
+    };
+    println!("{}", content.trim_end());
 
-    Ok(project_data)
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
@@ -676,12 +2904,16 @@ pub async fn handle_scan(directory: Option<&Path>, show_all: bool) -> Result<usi
     }
 
     // Filter out already tracked projects
-    let existing_paths: HashSet<PathBuf> =
-        config.projects.values().map(|p| p.path.clone()).collect();
+    // Paths are case-insensitive on Windows
+    let existing_paths: HashSet<PathBuf> = config
+        .projects
+        .values()
+        .map(|p| path_comparison_key(&p.path))
+        .collect();
 
     let new_repos: Vec<GitRepoInfo> = repositories
         .into_iter()
-        .filter(|repo| !existing_paths.contains(&repo.path))
+        .filter(|repo| !existing_paths.contains(&path_comparison_key(&repo.path)))
         .collect();
 
     if new_repos.is_empty() {
@@ -729,9 +2961,7 @@ pub async fn handle_scan(directory: Option<&Path>, show_all: bool) -> Result<usi
     }
 
     // Add selected repositories
-    let mut config = load_config().await?;
-    let mut added_count = 0;
-
+    let mut projects = Vec::new();
     for selected in selection {
         // Find the repository by matching the display string
         if let Some(repo) = new_repos.iter().find(|r| {
@@ -743,34 +2973,26 @@ pub async fn handle_scan(directory: Option<&Path>, show_all: bool) -> Result<usi
             );
             expected == *selected
         }) {
-            let git_updated_at = if repo.is_git {
-                get_last_git_commit_time(&repo.path).ok().flatten()
-            } else {
-                None
-            };
-
-            let project = Project {
-                id: Uuid::new_v4(),
-                name: repo.name.clone(),
-                path: repo.path.clone(),
-                tags: vec!["scanned".to_string()],
-                description: repo.remote_url.clone(),
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-                git_updated_at,
-                is_git_repository: is_git_repository(&repo.path),
-            };
-
-            config.add_project(project);
-            added_count += 1;
-            println!("✅ Added: {}", repo.name);
+            projects.push(new_project(
+                &repo.path,
+                repo.name.clone(),
+                vec!["scanned".to_string()],
+                repo.remote_url.clone(),
+            ));
         }
     }
 
-    save_config(&config).await?;
-    println!("🎉 Successfully added {} repositories to PM", added_count);
+    let added = register_projects(projects).await?;
+    for project in &added {
+        println!("✅ Added: {}", project.name);
+    }
+    println!("🎉 Successfully added {} repositories to PM", added.len());
 
-    Ok(added_count)
+    for project in &added {
+        dispatch_hook(HookEvent::ProjectAdd, &HookContext::for_project(project)).await;
+    }
+
+    Ok(added.len())
 }
 
 /// Check if GitHub CLI is installed and authenticated
@@ -821,7 +3043,7 @@ async fn check_gh_status() -> (bool, bool) {
 }
 
 /// Get GitHub token from gh CLI if available
-async fn get_gh_token() -> Option<String> {
+pub(crate) async fn get_gh_token() -> Option<String> {
     use std::process::Command;
     
     let output = Command::new("gh")
@@ -992,8 +3214,35 @@ pub async fn fetch_github_repositories(username: &str) -> Result<Vec<GitHubRepo>
     Ok(all_repos)
 }
 
+/// Options controlling whether and how a cloned repository is added to PM
+#[derive(Debug, Clone)]
+pub struct AddConfig {
+    /// Register the cloned repository as a PM project
+    pub add_to_pm: bool,
+    /// Project name (defaults to the repository name)
+    pub name: Option<String>,
+    /// Project tags (defaults to `github`)
+    pub tags: Option<Vec<String>>,
+    /// Project priority (defaults to 3)
+    pub priority: Option<u8>,
+}
+
+impl Default for AddConfig {
+    fn default() -> Self {
+        Self {
+            add_to_pm: true,
+            name: None,
+            tags: None,
+            priority: None,
+        }
+    }
+}
+
 /// Show repository selection interface and clone selected repositories
-pub async fn handle_github_repo_selection(username: Option<&str>) -> Result<usize> {
+pub async fn handle_github_repo_selection(
+    username: Option<&str>,
+    add_config: &AddConfig,
+) -> Result<usize> {
     // Use provided username or get current authenticated user
     let target_username = match username {
         Some(user) => user.to_string(),
@@ -1024,8 +3273,13 @@ pub async fn handle_github_repo_selection(username: Option<&str>) -> Result<usiz
         })
         .collect();
     
+    let prompt = if add_config.add_to_pm {
+        "Select repositories to clone and add to PM:"
+    } else {
+        "Select repositories to clone:"
+    };
     let selection = handle_inquire_error(
-        MultiSelect::new("Select repositories to clone and add to PM:", options)
+        MultiSelect::new(prompt, options)
             .with_page_size(15)
             .prompt()
     )?;
@@ -1062,7 +3316,7 @@ pub async fn handle_github_repo_selection(username: Option<&str>) -> Result<usiz
             pb.set_message(format!("Cloning {}", repo.full_name));
             
             // Use the silent version for batch operations
-            match handle_load_silent(&repo.full_name, None).await {
+            match handle_load_silent(&repo.full_name, None, add_config).await {
                 Ok(_) => {
                     cloned_count += 1;
                 }
@@ -1084,31 +3338,295 @@ pub async fn handle_github_repo_selection(username: Option<&str>) -> Result<usiz
     Ok(cloned_count)
 }
 
+/// GitLab instance URL from settings, defaulting to gitlab.com
+fn gitlab_base_url(config: &Config) -> String {
+    config
+        .settings
+        .gitlab_url
+        .as_deref()
+        .unwrap_or(DEFAULT_GITLAB_URL)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Resolve a GitLab access token from GITLAB_TOKEN or the glab CLI
+fn get_gitlab_token(base_url: &str) -> Result<String> {
+    if let Ok(token) = std::env::var("GITLAB_TOKEN") {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    let host = base_url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let glab_token = std::process::Command::new("glab")
+        .args(["config", "get", "token", "--host", host])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty());
+
+    glab_token.ok_or_else(|| {
+        anyhow::anyhow!(
+            "❌ GitLab authentication required\n\n\
+            Requirements:\n\
+            • Set GITLAB_TOKEN to a personal access token (read_api, read_repository), or\n\
+            • Authenticate the GitLab CLI: glab auth login --hostname {}",
+            host
+        )
+    })
+}
+
+/// Fetch projects the authenticated user is a member of, or the projects of a group
+pub async fn fetch_gitlab_repositories(
+    base_url: &str,
+    token: &str,
+    group: Option<&str>,
+) -> Result<Vec<GitLabRepo>> {
+    let endpoint = match group {
+        Some(group) => format!(
+            "{}/api/v4/groups/{}/projects?include_subgroups=true&order_by=last_activity_at",
+            base_url,
+            group.replace('/', "%2F")
+        ),
+        None => format!(
+            "{}/api/v4/projects?membership=true&order_by=last_activity_at",
+            base_url
+        ),
+    };
+
+    println!("🔍 Fetching projects from {}...", base_url);
+
+    let client = reqwest::Client::new();
+    let mut all_repos = Vec::new();
+    let mut page = 1u32;
+
+    loop {
+        let response = client
+            .get(format!("{}&per_page=100&page={}", endpoint, page))
+            .header("PRIVATE-TOKEN", token)
+            .header(reqwest::header::USER_AGENT, "pm-cli")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "GitLab API request failed: {}",
+                response.status()
+            ));
+        }
+
+        let next_page = response
+            .headers()
+            .get("x-next-page")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u32>().ok());
+
+        let repos: Vec<GitLabRepo> = response.json().await?;
+        all_repos.extend(repos);
+
+        match next_page {
+            Some(next) => page = next,
+            None => break,
+        }
+    }
+
+    println!("📦 Found {} projects", all_repos.len());
+
+    Ok(all_repos)
+}
+
+fn format_gitlab_option(repo: &GitLabRepo) -> String {
+    let privacy = match repo.visibility.as_deref() {
+        Some("public") => "🌐",
+        _ => "🔒",
+    };
+    let fork = if repo.forked_from_project.is_some() { "🍴" } else { "" };
+    let desc = repo
+        .description
+        .as_deref()
+        .filter(|desc| !desc.is_empty())
+        .unwrap_or("No description");
+
+    format!("{}{} {} - {}", privacy, fork, repo.path_with_namespace, desc)
+}
+
+fn gitlab_clone_request(repo: &GitLabRepo, token: &str) -> CloneRequest {
+    CloneRequest {
+        clone_url: repo.http_url_to_repo.clone(),
+        source: repo.path_with_namespace.clone(),
+        repo_name: repo.name.clone(),
+        default_tag: "gitlab",
+        token: Some(token.to_string()),
+        use_git_cli: false,
+    }
+}
+
+/// Show GitLab project selection interface and clone selected projects
+pub async fn handle_gitlab_repo_selection(
+    group: Option<&str>,
+    add_config: &AddConfig,
+) -> Result<usize> {
+    let config = load_config().await?;
+    let base_url = gitlab_base_url(&config);
+    let token = get_gitlab_token(&base_url)?;
+
+    let repos = fetch_gitlab_repositories(&base_url, &token, group).await?;
+
+    if repos.is_empty() {
+        println!("❌ No projects available for selection");
+        if let Some(group) = group {
+            println!("💡 Check that the group '{}' exists and your token can access it", group);
+        }
+        return Ok(0);
+    }
+
+    let options: Vec<String> = repos.iter().map(format_gitlab_option).collect();
+
+    let prompt = if add_config.add_to_pm {
+        "Select projects to clone and add to PM:"
+    } else {
+        "Select projects to clone:"
+    };
+    let selection = handle_inquire_error(
+        MultiSelect::new(prompt, options)
+            .with_page_size(15)
+            .prompt()
+    )?;
+
+    if selection.is_empty() {
+        println!("❌ No projects selected");
+        return Ok(0);
+    }
+
+    let current_dir = std::env::current_dir()?;
+    let mut cloned_count = 0;
+
+    let total_repos = selection.len();
+    let pb = ProgressBar::new(total_repos as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("[{pos}/{len}] {msg} {bar:40.cyan/blue} {percent}%")
+            .unwrap()
+            .progress_chars("██▓▒░"),
+    );
+
+    for (index, selected) in selection.iter().enumerate() {
+        if let Some(repo) = repos.iter().find(|r| format_gitlab_option(r) == *selected) {
+            pb.set_position(index as u64);
+            pb.set_message(format!("Cloning {}", repo.path_with_namespace));
+
+            let target_dir = current_dir.join(&repo.path_with_namespace);
+            let request = gitlab_clone_request(repo, &token);
+            match clone_and_register(&request, &target_dir, false, add_config).await {
+                Ok(_) => {
+                    cloned_count += 1;
+                }
+                Err(e) => {
+                    pb.println(format!("❌ Failed to clone {}: {}", repo.path_with_namespace, e));
+                }
+            }
+        }
+    }
+
+    pb.set_position(total_repos as u64);
+    if cloned_count > 0 {
+        pb.finish_with_message(format!("✅ Successfully cloned {} projects", cloned_count));
+    } else {
+        pb.finish_with_message("❌ No projects were cloned");
+    }
+
+    Ok(cloned_count)
+}
+
+/// Clone a single GitLab project given as `namespace/project`
+async fn handle_gitlab_clone(
+    repo: &str,
+    directory: Option<&Path>,
+    add_config: &AddConfig,
+) -> Result<()> {
+    let Some((_, repo_name)) = repo.rsplit_once('/') else {
+        return Err(anyhow::anyhow!(
+            "GitLab repository format should be 'namespace/project'"
+        ));
+    };
+
+    let config = load_config().await?;
+    let base_url = gitlab_base_url(&config);
+    let token = get_gitlab_token(&base_url)?;
+
+    let target_dir = match directory {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir()?.join(repo),
+    };
+
+    let request = CloneRequest {
+        clone_url: format!("{}/{}.git", base_url, repo),
+        source: repo.to_string(),
+        repo_name: repo_name.to_string(),
+        default_tag: "gitlab",
+        token: Some(token),
+        use_git_cli: false,
+    };
+
+    clone_and_register(&request, &target_dir, true, add_config).await
+}
+
 /// Silent version of handle_load for batch operations (no progress output)
-pub async fn handle_load_silent(repo: &str, directory: Option<&Path>) -> Result<()> {
-    load_repository_internal(repo, directory, false).await
+pub async fn handle_load_silent(
+    repo: &str,
+    directory: Option<&Path>,
+    add_config: &AddConfig,
+) -> Result<()> {
+    load_repository_internal(repo, directory, false, add_config).await
 }
 
 /// Handle clone command - either interactive browse or direct clone
-pub async fn handle_clone(repo: Option<&str>, directory: Option<&Path>) -> Result<()> {
+pub async fn handle_clone(
+    repo: Option<&str>,
+    directory: Option<&Path>,
+    gitlab: bool,
+    group: Option<&str>,
+    add_config: &AddConfig,
+) -> Result<()> {
+    let add_config = &checked_clone_tags(add_config).await?;
+
+    if gitlab {
+        return match repo {
+            Some(repo_str) => handle_gitlab_clone(repo_str, directory, add_config).await,
+            None => {
+                handle_gitlab_repo_selection(group, add_config).await?;
+                Ok(())
+            }
+        };
+    }
+
     match repo {
         Some(repo_str) => {
             // Direct clone: pm clone <owner>/<repo>
-            handle_load(repo_str, directory).await
+            handle_load(repo_str, directory, add_config).await
         }
         None => {
             // Interactive browse: pm clone
-            handle_github_repo_selection(None).await?;
+            handle_github_repo_selection(None, add_config).await?;
             Ok(())
         }
     }
 }
 
-pub async fn handle_load(repo: &str, directory: Option<&Path>) -> Result<()> {
-    load_repository_internal(repo, directory, true).await
+pub async fn handle_load(repo: &str, directory: Option<&Path>, add_config: &AddConfig) -> Result<()> {
+    load_repository_internal(repo, directory, true, add_config).await
 }
 
-async fn load_repository_internal(repo: &str, directory: Option<&Path>, show_progress: bool) -> Result<()> {
+async fn load_repository_internal(
+    repo: &str,
+    directory: Option<&Path>,
+    show_progress: bool,
+    add_config: &AddConfig,
+) -> Result<()> {
     // Parse repository format: support both "repo" and "owner/repo"
     let (owner, repo_name) = if repo.contains('/') {
         // owner/repo format
@@ -1123,15 +3641,176 @@ async fn load_repository_internal(repo: &str, directory: Option<&Path>, show_pro
         (current_user, repo.to_string())
     };
 
-    let _config = load_config().await?;
+    // Determine target directory
+    let target_dir = if let Some(dir) = directory {
+        dir.to_path_buf()
+    } else {
+        // Default: <current_dir>/<owner>/<repo>
+        std::env::current_dir()?.join(&owner).join(&repo_name)
+    };
+
+    let request = CloneRequest {
+        clone_url: format!("https://github.com/{}/{}.git", owner, repo_name),
+        source: format!("{}/{}", owner, repo_name),
+        repo_name,
+        default_tag: "github",
+        token: None,
+        use_git_cli: false,
+    };
+
+    clone_and_register(&request, &target_dir, show_progress, add_config).await
+}
+
+/// Clone a repository from a Git URL and add it to PM (`pm add --from-url`)
+pub async fn handle_add_from_url(
+    url: &str,
+    directory: Option<&Path>,
+    add_config: &AddConfig,
+) -> Result<()> {
+    let url = strip_web_path_suffix(url.trim());
+    let Some(git_url) = parse_git_url(url) else {
+        display_error("Invalid Git URL", url);
+        return Err(PmError::ValidationFailed(format!(
+            "expected https://host/owner/repo or git@host:owner/repo, got '{}'",
+            url
+        ))
+        .into());
+    };
+
+    let add_config = &checked_clone_tags(add_config).await?;
+
+    // Default: <current_dir>/<owner>/<repo>, same as `pm clone`
+    let target_dir = match directory {
+        Some(dir) => dir.to_path_buf(),
+        None => std::env::current_dir()?.join(&git_url.owner).join(&git_url.repo),
+    };
+
+    let is_github = git_url.host == "github.com";
+    let request = CloneRequest {
+        // HTTPS GitHub URLs clone through git2; SSH URLs and other hosts need the user's git setup
+        clone_url: url.to_string(),
+        source: if is_github {
+            format!("{}/{}", git_url.owner, git_url.repo)
+        } else {
+            format!("{}/{}/{}", git_url.host, git_url.owner, git_url.repo)
+        },
+        repo_name: git_url.repo.clone(),
+        default_tag: match git_url.host.as_str() {
+            "github.com" => "github",
+            "gitlab.com" => "gitlab",
+            _ => "git",
+        },
+        token: None,
+        use_git_cli: !(is_github && url.starts_with("https://")),
+    };
+
+    clone_and_register(&request, &target_dir, true, add_config).await
+}
+
+/// Host, owner and repository name parsed from a Git remote URL
+#[derive(Debug, PartialEq)]
+struct GitUrl {
+    host: String,
+    /// Owner, including any subgroups (`group/subgroup`)
+    owner: String,
+    repo: String,
+}
+
+/// Repository URL without a web UI suffix such as `/tree/main` or `/-/blob/main/README.md`
+fn strip_web_path_suffix(url: &str) -> &str {
+    ["/-/tree/", "/-/blob/", "/tree/", "/blob/"]
+        .iter()
+        .filter_map(|marker| url.find(marker))
+        .min()
+        .map_or(url, |index| &url[..index])
+}
+
+/// Parse `https://host/owner/repo(.git)`, `ssh://git@host[:port]/owner/repo.git` and `git@host:owner/repo.git`
+///
+/// `/tree/...` and `/blob/...` suffixes copied from a browser are ignored. URLs starting
+/// with `-` are rejected so they cannot be mistaken for `git` options.
+fn parse_git_url(url: &str) -> Option<GitUrl> {
+    let url = strip_web_path_suffix(url.trim());
+    if url.starts_with('-') {
+        return None;
+    }
+    let (host, path) = if let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .or_else(|| url.strip_prefix("ssh://"))
+    {
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        (host.split(':').next()?, path)
+    } else {
+        // scp-like syntax: [user@]host:owner/repo
+        let (authority, path) = url.split_once(':')?;
+        (authority.rsplit('@').next()?, path)
+    };
+
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.rsplit_once('/')?;
+    if host.is_empty() || owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+
+    Some(GitUrl {
+        host: host.to_string(),
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+    })
+}
+
+/// A repository to clone, independent of the hosting service
+struct CloneRequest {
+    clone_url: String,
+    /// Human-readable origin such as `owner/repo`
+    source: String,
+    repo_name: String,
+    /// Tag assigned when `AddConfig::tags` is not set
+    default_tag: &'static str,
+    /// Token for HTTPS authentication (GitLab private repositories)
+    token: Option<String>,
+    /// Clone with the `git` CLI so the user's SSH keys and credential helpers apply
+    use_git_cli: bool,
+}
+
+/// Clone a repository into `target_dir` and, unless disabled, add it to PM
+async fn clone_and_register(
+    request: &CloneRequest,
+    target_dir: &Path,
+    show_progress: bool,
+    add_config: &AddConfig,
+) -> Result<()> {
+    let Some(project) = clone_project(request, target_dir, show_progress, add_config)? else {
+        return Ok(());
+    };
+
+    let hook_context = HookContext::for_project(&project);
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    config.add_project(project);
+    save_config(&config).await?;
+
+    if show_progress {
+        println!("✅ Successfully cloned and added {} to PM", request.source);
+        println!("📁 Location: {}", target_dir.display());
+    }
 
-    // Determine target directory
-    let target_dir = if let Some(dir) = directory {
-        dir.to_path_buf()
-    } else {
-        // Default: <current_dir>/<owner>/<repo>
-        std::env::current_dir()?.join(&owner).join(&repo_name)
-    };
+    dispatch_hook(HookEvent::ProjectAdd, &hook_context).await;
+    Ok(())
+}
+
+/// Clone a repository into `target_dir`, returning the project to add unless
+/// `add_config.add_to_pm` is disabled
+fn clone_project(
+    request: &CloneRequest,
+    target_dir: &Path,
+    show_progress: bool,
+    add_config: &AddConfig,
+) -> Result<Option<Project>> {
+    let source = &request.source;
 
     if target_dir.exists() {
         return Err(anyhow::anyhow!(
@@ -1145,10 +3824,8 @@ async fn load_repository_internal(repo: &str, directory: Option<&Path>, show_pro
         fs::create_dir_all(parent)?;
     }
 
-    let clone_url = format!("https://github.com/{}/{}.git", owner, repo_name);
-    
     if show_progress {
-        println!("📥 Cloning {}/{} to {}", owner, repo_name, target_dir.display());
+        println!("📥 Cloning {} to {}", source, target_dir.display());
 
         // Clone the repository with progress spinner
         let pb = ProgressBar::new_spinner();
@@ -1159,40 +3836,88 @@ async fn load_repository_internal(repo: &str, directory: Option<&Path>, show_pro
         );
         pb.set_message("Cloning repository...");
 
-        let _repo = Repository::clone(&clone_url, &target_dir)
-            .map_err(|e| anyhow::anyhow!("Failed to clone repository: {}", e))?;
+        clone_repository(request, target_dir)?;
 
         pb.finish_and_clear();
     } else {
         // Silent clone without progress display
-        let _repo = Repository::clone(&clone_url, &target_dir)
-            .map_err(|e| anyhow::anyhow!("Failed to clone repository: {}", e))?;
+        clone_repository(request, target_dir)?;
+    }
+
+    if !add_config.add_to_pm {
+        if show_progress {
+            println!("✅ Successfully cloned {}", source);
+            println!("📁 Location: {}", target_dir.display());
+        }
+        return Ok(None);
     }
 
-    // Add to PM
-    let git_updated_at = get_last_git_commit_time(&target_dir).ok().flatten();
+    let git_updated_at = get_last_git_commit_time(target_dir).ok().flatten();
+    let project_name = add_config
+        .name
+        .clone()
+        .unwrap_or_else(|| request.repo_name.clone());
 
     let project = Project {
         id: Uuid::new_v4(),
-        name: repo_name.clone(),
-        path: target_dir.clone(),
-        tags: vec!["github".to_string()],
-        description: Some(format!("Cloned from {}/{}", owner, repo_name)),
+        name: project_name,
+        path: target_dir.to_path_buf(),
+        tags: add_config
+            .tags
+            .clone()
+            .unwrap_or_else(|| vec![request.default_tag.to_string()]),
+        description: Some(format!("Cloned from {}", source)),
         created_at: Utc::now(),
         updated_at: Utc::now(),
         git_updated_at,
         is_git_repository: true, // Cloned repositories are always Git repositories
+        color: None,
+        remote_url: None,
+        priority: add_config.priority.unwrap_or(DEFAULT_PROJECT_PRIORITY),
     };
 
-    let mut config = load_config().await?;
-    config.add_project(project);
-    save_config(&config).await?;
+    Ok(Some(project))
+}
 
-    if show_progress {
-        println!("✅ Successfully cloned and added {}/{} to PM", owner, repo_name);
-        println!("📁 Location: {}", target_dir.display());
+fn clone_repository(request: &CloneRequest, target_dir: &Path) -> Result<()> {
+    if request.use_git_cli {
+        let status = std::process::Command::new("git")
+            .arg("clone")
+            .arg("--quiet")
+            .arg("--")
+            .arg(&request.clone_url)
+            .arg(target_dir)
+            .status()
+            .map_err(|e| anyhow::anyhow!("Failed to run git clone: {}", e))?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("Failed to clone repository: git clone exited with {}", status));
+        }
+        return Ok(());
+    }
+
+    let mut builder = git2::build::RepoBuilder::new();
+
+    if let Some(token) = request.token.clone() {
+        let mut callbacks = git2::RemoteCallbacks::new();
+        // libgit2 asks again after a rejected credential; offer the token once so a bad
+        // token fails the clone instead of looping
+        let mut attempts = 0;
+        callbacks.credentials(move |_url, _username, _allowed| {
+            attempts += 1;
+            if attempts > 1 {
+                return Err(git2::Error::from_str("authentication failed: the access token was rejected"));
+            }
+            git2::Cred::userpass_plaintext("oauth2", &token)
+        });
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+        builder.fetch_options(fetch_options);
     }
 
+    builder
+        .clone(&request.clone_url, target_dir)
+        .map_err(|e| anyhow::anyhow!("Failed to clone repository: {}", e))?;
+
     Ok(())
 }
 
@@ -1304,9 +4029,20 @@ fn contains_project_files(path: &Path) -> bool {
 
 // Project removal functionality
 
-pub async fn handle_remove(name: Option<&str>, skip_confirm: bool) -> Result<()> {
-    let mut config = load_config().await?;
-    
+pub async fn handle_remove(
+    name: Option<&str>,
+    tags: &[String],
+    delete_directory: bool,
+    skip_confirm: bool,
+) -> Result<()> {
+    // Prompts and directory deletion run against an unlocked snapshot; `remove_projects`
+    // takes the lock to save
+    let config = load_config().await?;
+
+    if !tags.is_empty() {
+        return handle_remove_by_tags(&config, tags, skip_confirm).await;
+    }
+
     let project_to_remove = match name {
         Some(name) => {
             let matches = find_projects_by_name(&config, name);
@@ -1334,18 +4070,305 @@ pub async fn handle_remove(name: Option<&str>, skip_confirm: bool) -> Result<()>
     };
     
     // Confirm removal
-    if confirm_removal(&project_to_remove, &config, skip_confirm).await? {
-        config.remove_project(project_to_remove.id)?;
-        save_config(&config).await?;
-        
-        println!("✅ Project '{}' removed successfully", project_to_remove.name);
-    } else {
+    if !confirm_removal(&project_to_remove, &config, skip_confirm).await? {
         println!("❌ Removal cancelled");
+        return Ok(());
     }
-    
+
+    if delete_directory {
+        if !confirm_directory_deletion(&project_to_remove)? {
+            println!("❌ Removal cancelled, nothing was deleted");
+            return Ok(());
+        }
+
+        if project_to_remove.path.exists() {
+            if let Err(e) = fs::remove_dir_all(&project_to_remove.path) {
+                display_error(
+                    "Failed to delete directory",
+                    &format!("{}: {}", project_to_remove.path.display(), e),
+                );
+                println!("💡 The project is still tracked by PM");
+                return Err(PmError::InvalidPath.into());
+            }
+            println!("🗑️  Deleted directory: {}", project_to_remove.path.display());
+        }
+    }
+
+    if remove_projects(&[project_to_remove.id]).await? == 0 {
+        display_warning(&format!(
+            "'{}' was already removed by another PM process",
+            project_to_remove.name
+        ));
+        return Ok(());
+    }
+    println!("✅ Project '{}' removed successfully", project_to_remove.name);
+
+    Ok(())
+}
+
+/// Show a warning banner and ask twice before deleting a project directory
+fn confirm_directory_deletion(project: &Project) -> Result<bool> {
+    let path = &project.path;
+    if !path.exists() {
+        display_warning(&format!("Directory does not exist: {}", path.display()));
+        return Ok(true);
+    }
+
+    let size = crate::utils::calculate_directory_size(path);
+
+    println!();
+    println!("{}", "⚠️  DANGER: THIS WILL PERMANENTLY DELETE FILES FROM DISK".red().bold());
+    println!("{}", format!("   Path: {}", path.display()).red());
+    println!("{}", format!("   Size: {}", crate::utils::format_size(size)).red());
+    println!("{}", "   This cannot be undone.".red());
+    println!();
+
+    let first = handle_inquire_error(
+        Confirm::new(&format!("Delete {} and everything in it?", path.display()))
+            .with_default(false)
+            .prompt(),
+    )?;
+    if !first {
+        return Ok(false);
+    }
+
+    let typed_name = handle_inquire_error(
+        Text::new(&format!("Type the project name '{}' to confirm deletion:", project.name))
+            .prompt(),
+    )?;
+
+    Ok(typed_name.trim() == project.name)
+}
+
+/// Remove every project carrying all of the given tags
+async fn handle_remove_by_tags(config: &Config, tags: &[String], skip_confirm: bool) -> Result<()> {
+    let mut matches: Vec<Project> = config
+        .projects
+        .values()
+        .filter(|p| tags.iter().all(|tag| tags_match(tag, &p.tags)))
+        .cloned()
+        .collect();
+
+    if matches.is_empty() {
+        println!("❌ No projects found with tags: {}", tags.join(", "));
+        return Ok(());
+    }
+
+    matches.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if !confirm_batch_removal(&matches, skip_confirm)? {
+        println!("❌ Removal cancelled");
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = matches.iter().map(|p| p.id).collect();
+    let removed = remove_projects(&ids).await?;
+    println!("✅ Removed {} projects", removed);
+
+    Ok(())
+}
+
+/// Why projects were grouped as duplicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DuplicateKind {
+    SamePath,
+    SameName,
+}
+
+/// Groups of projects sharing a canonical path, then groups sharing a name
+///
+/// Groups are sorted by their first project's name; projects within a group are oldest first.
+fn find_duplicate_groups(config: &Config) -> Vec<(DuplicateKind, Vec<Project>)> {
+    let mut by_path: HashMap<PathBuf, Vec<Project>> = HashMap::new();
+    let mut by_name: HashMap<String, Vec<Project>> = HashMap::new();
+    for project in config.projects.values() {
+        by_path.entry(canonical_path(&project.path)).or_default().push(project.clone());
+        by_name.entry(project.name.clone()).or_default().push(project.clone());
+    }
+
+    let collect = |groups: Vec<Vec<Project>>, kind| {
+        let mut groups: Vec<(DuplicateKind, Vec<Project>)> = groups
+            .into_iter()
+            .filter(|group| group.len() > 1)
+            .map(|mut group| {
+                group.sort_by_key(|p| p.created_at);
+                (kind, group)
+            })
+            .collect();
+        groups.sort_by(|a, b| a.1[0].name.cmp(&b.1[0].name));
+        groups
+    };
+
+    let mut groups = collect(by_path.into_values().collect(), DuplicateKind::SamePath);
+    groups.extend(collect(by_name.into_values().collect(), DuplicateKind::SameName));
+    groups
+}
+
+/// Detect duplicate projects and interactively keep one per group
+pub async fn handle_dedup(dry_run: bool) -> Result<()> {
+    // Decisions are collected against an unlocked snapshot and applied under the lock
+    let mut config = load_config().await?;
+    let groups = find_duplicate_groups(&config);
+
+    if groups.is_empty() {
+        display_success("No duplicate projects found");
+        return Ok(());
+    }
+
+    println!("🔍 Found {} duplicate group(s)", groups.len());
+
+    // (kept project, tags to merge into it, projects to remove)
+    let mut resolutions: Vec<(Uuid, Vec<String>, Vec<Uuid>)> = Vec::new();
+    for (kind, group) in groups {
+        // A project removed while resolving an earlier group may shrink this one
+        let group: Vec<Project> = group
+            .into_iter()
+            .filter(|p| config.projects.contains_key(&p.id))
+            .collect();
+        if group.len() < 2 {
+            continue;
+        }
+
+        println!();
+        match kind {
+            DuplicateKind::SamePath => println!(
+                "{} {}",
+                "Same path:".bold(),
+                canonical_path(&group[0].path).display()
+            ),
+            DuplicateKind::SameName => println!("{} {}", "Same name:".bold(), group[0].name),
+        }
+        for project in &group {
+            let (_, access_count) = config.get_project_access_info(project.id);
+            println!(
+                "   - {} ({}) tags: [{}], added {}, opened {} times",
+                project.name,
+                project.path.display(),
+                project.tags.join(", "),
+                project.created_at.format("%Y-%m-%d"),
+                access_count
+            );
+        }
+
+        if dry_run {
+            continue;
+        }
+
+        let mut options: Vec<String> = group
+            .iter()
+            .map(|p| format!("Keep '{}' ({})", p.name, p.path.display()))
+            .collect();
+        options.push("Skip this group".to_string());
+        let selection = handle_inquire_error(
+            Select::new("Which project should be kept?", options).raw_prompt(),
+        )?;
+        // The last option skips the group
+        let Some(kept) = group.get(selection.index) else {
+            continue;
+        };
+
+        let others: Vec<&Project> = group.iter().filter(|p| p.id != kept.id).collect();
+        let extra_tags: Vec<String> = others
+            .iter()
+            .flat_map(|p| p.tags.iter())
+            .filter(|tag| !kept.tags.contains(tag))
+            .cloned()
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let merge = !extra_tags.is_empty()
+            && handle_inquire_error(
+                Confirm::new(&format!("Merge tags [{}] into '{}'?", extra_tags.join(", "), kept.name))
+                    .with_default(true)
+                    .prompt(),
+            )?;
+
+        let other_ids: Vec<Uuid> = others.iter().map(|p| p.id).collect();
+        for id in &other_ids {
+            config.remove_project(*id)?;
+        }
+        resolutions.push((kept.id, if merge { extra_tags } else { Vec::new() }, other_ids));
+    }
+
+    if dry_run {
+        println!("\n💡 Dry run: no changes made. Run without --dry-run to resolve duplicates");
+        return Ok(());
+    }
+
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let mut removed = 0;
+    let mut merged_tags = 0;
+    for (kept_id, extra_tags, other_ids) in resolutions {
+        // Skip groups another PM process changed while the prompts were open
+        if !config.projects.contains_key(&kept_id) {
+            continue;
+        }
+        for id in other_ids {
+            if config.projects.contains_key(&id) {
+                config.remove_project(id)?;
+                removed += 1;
+            }
+        }
+        if let Some(project) = config.projects.get_mut(&kept_id) {
+            let before = project.tags.len();
+            add_missing_tags(&mut project.tags, extra_tags);
+            if project.tags.len() > before {
+                merged_tags += project.tags.len() - before;
+                project.updated_at = Utc::now();
+            }
+        }
+    }
+
+    if removed > 0 || merged_tags > 0 {
+        save_config(&config).await?;
+    }
+    display_success(&format!(
+        "Removed {} duplicate project(s), merged {} tag(s)",
+        removed, merged_tags
+    ));
     Ok(())
 }
 
+/// Remove a batch of projects under the config lock and save the configuration once
+///
+/// Projects another PM process already removed are skipped; returns how many were removed
+async fn remove_projects(project_ids: &[Uuid]) -> Result<usize> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let mut removed = 0;
+    for &project_id in project_ids {
+        if config.projects.contains_key(&project_id) {
+            config.remove_project(project_id)?;
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        save_config(&config).await?;
+    }
+    Ok(removed)
+}
+
+fn confirm_batch_removal(projects: &[Project], skip_confirm: bool) -> Result<bool> {
+    println!();
+    println!("🗑️ About to remove {} projects:", projects.len());
+    for project in projects {
+        println!("   - {} ({})", project.name, project.path.display());
+    }
+    println!();
+
+    if skip_confirm {
+        return Ok(true);
+    }
+
+    handle_inquire_error(
+        Confirm::new(&format!("Remove these {} projects?", projects.len()))
+            .with_default(false)
+            .prompt(),
+    )
+}
+
 fn find_projects_by_name(config: &Config, name: &str) -> Vec<Project> {
     config.projects.values()
         .filter(|p| p.name == name)
@@ -1507,3 +4530,441 @@ async fn confirm_removal(project: &Project, config: &Config, skip_confirm: bool)
     
     Ok(confirmed)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_source_repository(path: &Path) {
+        let repo = Repository::init(path).unwrap();
+        fs::write(path.join("README.md"), "# source\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_find_duplicate_groups() {
+        let temp = TempDir::new().unwrap();
+        let project = |name: &str, dir: &str, days_ago: i64| {
+            let mut project = new_project(&temp.path().join(dir), name.to_string(), vec![], None);
+            project.created_at = Utc::now() - chrono::Duration::days(days_ago);
+            project
+        };
+
+        let mut config = Config::default();
+        config.add_project(project("api", "api", 1));
+        config.add_project(project("api-old", "api", 5));
+        config.add_project(project("web", "web", 1));
+        config.add_project(project("web", "web-v2", 2));
+        config.add_project(project("cli", "cli", 1));
+
+        let groups = find_duplicate_groups(&config);
+        assert_eq!(groups.len(), 2);
+
+        let (kind, group) = &groups[0];
+        assert_eq!(*kind, DuplicateKind::SamePath);
+        // Oldest first
+        assert_eq!(group.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), ["api-old", "api"]);
+
+        let (kind, group) = &groups[1];
+        assert_eq!(*kind, DuplicateKind::SameName);
+        assert_eq!(group.len(), 2);
+        assert!(group.iter().all(|p| p.name == "web"));
+    }
+
+    #[test]
+    fn test_normalize_description() {
+        assert_eq!(normalize_description("  A CLI tool\n"), Some("A CLI tool".to_string()));
+        assert_eq!(
+            normalize_description("\nFirst line\n\nMore detail\n\n"),
+            Some("First line\n\nMore detail".to_string())
+        );
+        assert_eq!(normalize_description(" \n\t"), None);
+    }
+
+    #[test]
+    fn test_partition_path_matches() {
+        let mut config = Config::default();
+        for (name, path, tags) in [
+            ("web", "/work/web", vec!["work".to_string()]),
+            ("api", "/work/api", vec![]),
+            ("nested", "/work/group/cli", vec![]),
+            ("notes", "/home/notes", vec![]),
+        ] {
+            config.add_project(new_project(Path::new(path), name.to_string(), tags, None));
+        }
+        let names = |projects: Vec<&Project>| projects.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+
+        let (tagged, to_tag) = partition_path_matches(&config, &glob::Pattern::new("/work/*").unwrap(), "work");
+        assert_eq!(names(tagged), ["web"]);
+        assert_eq!(names(to_tag), ["api", "nested"]);
+
+        let (tagged, to_tag) = partition_path_matches(&config, &glob::Pattern::new("/srv/*").unwrap(), "work");
+        assert!(tagged.is_empty() && to_tag.is_empty());
+    }
+
+    #[test]
+    fn test_parse_batch_file() {
+        let entries = parse_batch_file("# projects\n./api\n\n./web\tfrontend\trust, web ,\n");
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].line_number, 2);
+        assert_eq!(entries[0].path, PathBuf::from("./api"));
+        assert!(entries[0].name.is_none());
+        assert!(entries[0].tags.is_empty());
+        assert_eq!(entries[1].line_number, 4);
+        assert_eq!(entries[1].name.as_deref(), Some("frontend"));
+        assert_eq!(entries[1].tags, ["rust", "web"]);
+    }
+
+    #[test]
+    fn test_batch_entry_skips_duplicates_and_invalid_lines() {
+        let temp = TempDir::new().unwrap();
+        for dir in ["api", "web", "other"] {
+            fs::create_dir_all(temp.path().join(dir)).unwrap();
+        }
+        let mut config = Config::default();
+        let api = validate_path(&temp.path().join("api"), false).unwrap();
+        config.add_project(new_project(&api, "api".to_string(), vec![], None));
+
+        let entry = |line: &str| parse_batch_file(line).remove(0);
+        let result = |line: &str| batch_entry_project(&config, &entry(line), temp.path());
+
+        let project = result("web\tfrontend\trust").ok().unwrap();
+        assert_eq!(project.name, "frontend");
+        assert_eq!(project.tags, ["rust"]);
+        assert!(matches!(result("api\tnew-name"), Err(BatchSkip::Duplicate(_))));
+        assert!(matches!(result("other\tapi"), Err(BatchSkip::Duplicate(_))));
+        assert!(matches!(result("missing"), Err(BatchSkip::Invalid(_))));
+        assert!(matches!(result("other\t\tbad tag!"), Err(BatchSkip::Invalid(_))));
+    }
+
+    #[test]
+    fn test_since_modified_uses_commit_time_or_mtime() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("notes")).unwrap();
+        let project = |name: &str, git_updated_at: Option<DateTime<Utc>>| {
+            let mut project = new_project(&temp.path().join(name), name.to_string(), vec![], None);
+            project.git_updated_at = git_updated_at;
+            project.is_git_repository = git_updated_at.is_some();
+            project
+        };
+
+        let mut config = Config::default();
+        config.add_project(project("notes", None));
+        config.add_project(project("old-repo", Some(Utc::now() - chrono::Duration::days(90))));
+        config.add_project(project("missing", None));
+
+        let options = ListOptions {
+            since_modified: Some("7d".to_string()),
+            ..Default::default()
+        };
+        let data = get_filtered_project_data(&config, &options).unwrap();
+        let names: Vec<_> = data.iter().map(|(project, _, _)| project.name.as_str()).collect();
+        assert_eq!(names, ["notes"]);
+    }
+
+    #[test]
+    fn test_effective_offset() {
+        let options = |page, offset| ListOptions {
+            limit: Some(10),
+            page,
+            offset,
+            ..Default::default()
+        };
+        assert_eq!(options(None, Some(5)).effective_offset(), 5);
+        assert_eq!(options(Some(3), Some(5)).effective_offset(), 20);
+        assert_eq!(options(Some(usize::MAX), None).effective_offset(), usize::MAX);
+    }
+
+    #[test]
+    fn test_exists_filter() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("present")).unwrap();
+
+        let mut config = Config::default();
+        for name in ["present", "missing"] {
+            config.add_project(new_project(&temp.path().join(name), name.to_string(), vec![], None));
+        }
+
+        let names = |exists| {
+            let options = ListOptions {
+                exists: Some(exists),
+                ..Default::default()
+            };
+            get_filtered_project_data(&config, &options)
+                .unwrap()
+                .into_iter()
+                .map(|(project, _, _)| project.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(true), ["present"]);
+        assert_eq!(names(false), ["missing"]);
+    }
+
+    #[test]
+    fn test_missing_description_and_tags_filters() {
+        let mut config = config_with_projects(&[
+            ("complete", &["rust"]),
+            ("untagged", &[]),
+            ("blank", &["rust"]),
+            ("bare", &[]),
+        ]);
+        for project in config.projects.values_mut() {
+            project.description = match project.name.as_str() {
+                "complete" | "untagged" => Some("A tool".to_string()),
+                "blank" => Some("  ".to_string()),
+                _ => None,
+            };
+        }
+
+        let names = |missing_description, missing_tags| {
+            let options = ListOptions {
+                missing_description,
+                missing_tags,
+                ..Default::default()
+            };
+            let mut names: Vec<_> = get_filtered_project_data(&config, &options)
+                .unwrap()
+                .into_iter()
+                .map(|(project, _, _)| project.name)
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(true, false), ["bare", "blank"]);
+        assert_eq!(names(false, true), ["bare", "untagged"]);
+        assert_eq!(names(true, true), ["bare"]);
+    }
+
+    #[test]
+    fn test_clone_without_add_returns_no_project() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        create_source_repository(&source);
+
+        let target = temp.path().join("clones").join("source");
+        let add_config = AddConfig {
+            add_to_pm: false,
+            ..AddConfig::default()
+        };
+
+        let request = CloneRequest {
+            clone_url: source.to_str().unwrap().to_string(),
+            source: "local/source".to_string(),
+            repo_name: "source".to_string(),
+            default_tag: "github",
+            token: None,
+            use_git_cli: false,
+        };
+
+        let project = clone_project(&request, &target, false, &add_config).unwrap();
+
+        assert!(target.join("README.md").exists());
+        assert!(project.is_none());
+
+        let add_config = AddConfig {
+            tags: Some(vec!["work".to_string()]),
+            ..AddConfig::default()
+        };
+        let project = clone_project(&request, &temp.path().join("again"), false, &add_config)
+            .unwrap()
+            .unwrap();
+        assert_eq!(project.name, "source");
+        assert_eq!(project.tags, vec!["work"]);
+    }
+
+    #[test]
+    fn test_stale_filter_sorts_oldest_first() {
+        let now = Utc::now();
+        let mut config = config_with_projects(&[("fresh", &[]), ("old", &[]), ("older", &[]), ("never", &[])]);
+        for project in config.projects.values_mut() {
+            let days_ago = match project.name.as_str() {
+                "fresh" => Some(2),
+                "old" => Some(90),
+                "older" => Some(400),
+                _ => None,
+            };
+            project.git_updated_at = days_ago.map(|days| now - chrono::Duration::days(days));
+            project.is_git_repository = true;
+        }
+
+        let options = ListOptions {
+            stale_threshold: Some(chrono::Duration::days(30)),
+            ..Default::default()
+        };
+        let names: Vec<String> = get_filtered_project_data(&config, &options)
+            .unwrap()
+            .into_iter()
+            .map(|(project, _, _)| project.name)
+            .collect();
+
+        assert_eq!(names, vec!["never", "older", "old"]);
+    }
+
+    #[test]
+    fn test_parse_git_url() {
+        let expected = |host: &str, owner: &str, repo: &str| {
+            Some(GitUrl {
+                host: host.to_string(),
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            })
+        };
+
+        assert_eq!(parse_git_url("https://github.com/rust-lang/rust"), expected("github.com", "rust-lang", "rust"));
+        assert_eq!(parse_git_url("https://github.com/rust-lang/rust.git/"), expected("github.com", "rust-lang", "rust"));
+        assert_eq!(parse_git_url("git@github.com:zdpk/project-manager.git"), expected("github.com", "zdpk", "project-manager"));
+        assert_eq!(
+            parse_git_url("ssh://git@git.example.com:2222/team/infra/deploy.git"),
+            expected("git.example.com", "team/infra", "deploy")
+        );
+        assert_eq!(parse_git_url("https://github.com/rust-lang/rust/tree/master/src"), expected("github.com", "rust-lang", "rust"));
+        assert_eq!(
+            parse_git_url("https://gitlab.com/group/sub/app/-/blob/main/README.md"),
+            expected("gitlab.com", "group/sub", "app")
+        );
+        assert_eq!(parse_git_url("https://github.com/rust-lang"), None);
+        assert_eq!(parse_git_url("not a url"), None);
+        assert_eq!(parse_git_url("-uoption=touch /tmp/x:owner/repo"), None);
+    }
+
+    #[test]
+    fn test_sort_by_priority_then_commit_time() {
+        let now = Utc::now();
+        let mut config = config_with_projects(&[("low", &[]), ("old-high", &[]), ("new-high", &[]), ("default", &[])]);
+        for project in config.projects.values_mut() {
+            let (priority, days_ago) = match project.name.as_str() {
+                "low" => (1, 0),
+                "old-high" => (5, 30),
+                "new-high" => (5, 1),
+                _ => (3, 2),
+            };
+            project.priority = priority;
+            project.git_updated_at = Some(now - chrono::Duration::days(days_ago));
+        }
+
+        let options = ListOptions {
+            sort: ListSort::Priority,
+            ..Default::default()
+        };
+        let names: Vec<String> = get_filtered_project_data(&config, &options)
+            .unwrap()
+            .into_iter()
+            .map(|(project, _, _)| project.name)
+            .collect();
+        assert_eq!(names, vec!["new-high", "old-high", "default", "low"]);
+    }
+
+    #[test]
+    fn test_unique_paths_keeps_newest_entry_before_priority_sort() {
+        let now = Utc::now();
+        let mut config = config_with_projects(&[("stale-high", &[]), ("fresh-low", &[])]);
+        for project in config.projects.values_mut() {
+            let (priority, days_ago) = if project.name == "stale-high" { (5, 10) } else { (1, 0) };
+            project.path = PathBuf::from("/tmp/shared");
+            project.priority = priority;
+            project.updated_at = now - chrono::Duration::days(days_ago);
+        }
+
+        let options = ListOptions {
+            sort: ListSort::Priority,
+            unique_paths: true,
+            ..Default::default()
+        };
+        let names: Vec<String> = get_filtered_project_data(&config, &options)
+            .unwrap()
+            .into_iter()
+            .map(|(project, _, _)| project.name)
+            .collect();
+        assert_eq!(names, vec!["fresh-low"]);
+    }
+
+    #[test]
+    fn test_find_project_by_id_prefix() {
+        let mut config = Config::default();
+        let first = new_project(Path::new("/tmp/first"), "first".to_string(), vec![], None);
+        let second = new_project(Path::new("/tmp/second"), "second".to_string(), vec![], None);
+        let (first_id, second_id) = (first.id, second.id);
+        config.add_project(first);
+        config.add_project(second);
+
+        let found = find_project_by_id_prefix(&config, &first_id.to_string()).unwrap();
+        assert_eq!(found.map(|project| project.id), Some(first_id));
+
+        let short = crate::display::short_project_id(&config.projects[&second_id]).to_uppercase();
+        let found = find_project_by_id_prefix(&config, &short).unwrap();
+        assert_eq!(found.map(|project| project.id), Some(second_id));
+
+        assert!(find_project_by_id_prefix(&config, "zzzz").unwrap().is_none());
+        assert!(find_project_by_id_prefix(&config, "").is_err());
+        assert!(find_project_by_id_prefix(&config, "   ").is_err());
+        assert!(find_project_by_id_prefix(&config, &first_id.to_string()[..3]).is_err());
+    }
+
+    #[test]
+    fn test_add_config_defaults_to_adding() {
+        let add_config = AddConfig::default();
+        assert!(add_config.add_to_pm);
+        assert!(add_config.name.is_none());
+        assert!(add_config.tags.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_in_project_captures_output_and_exit_code() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("marker"), "").unwrap();
+
+        let result = run_in_project("ls; echo oops >&2; exit 3", "demo".to_string(), temp.path()).await;
+        assert_eq!(result.exit_code, Some(3));
+        assert!(!result.succeeded());
+        assert_eq!(result.output, "marker\noops\n");
+
+        let missing = run_in_project("true", "gone".to_string(), &temp.path().join("gone")).await;
+        assert_eq!(missing.exit_code, None);
+        assert!(missing.status_label().starts_with("❌ Failed"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_exec_command_line_quotes_multiple_arguments() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(exec_command_line(&args(&["git status | head -1"])), "git status | head -1");
+        assert_eq!(exec_command_line(&args(&["grep", "foo bar", "it's"])), "grep 'foo bar' 'it'\\''s'");
+
+        let temp = TempDir::new().unwrap();
+        let command = exec_command_line(&args(&["printf", "%s|", "foo bar", "$HOME", ""]));
+        let result = run_in_project(&command, "demo".to_string(), temp.path()).await;
+        assert_eq!(result.output, "foo bar|$HOME||");
+    }
+
+    #[test]
+    fn test_find_project_roots_respects_depth() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::write(root.join("api/Cargo.toml"), "").unwrap();
+        fs::create_dir_all(root.join("group/web")).unwrap();
+        fs::write(root.join("group/web/package.json"), "{}").unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("node_modules/pkg/package.json"), "{}").unwrap();
+        fs::create_dir_all(root.join("notes")).unwrap();
+        fs::create_dir_all(root.join("api/crates/core")).unwrap();
+        fs::write(root.join("api/crates/core/Cargo.toml"), "").unwrap();
+
+        assert_eq!(find_project_roots(root, 1), vec![root.join("api")]);
+        assert_eq!(find_project_roots(root, 3).iter().filter(|p| p.starts_with(root.join("api"))).count(), 1);
+
+        let mut found = find_project_roots(root, 2);
+        found.sort();
+        assert_eq!(found, vec![root.join("api"), root.join("group/web")]);
+    }
+}
@@ -1,12 +1,37 @@
 use crate::config::{get_machine_id, load_config, Config};
+use crate::display::watch_for_changes;
 use crate::utils::{
-    detect_project_language, get_git_current_branch, get_git_remote_url,
-    get_last_git_commit_time, is_git_repository,
+    check_project_update, detect_project_language, get_git_branch_name, get_git_remote_url,
+    get_last_git_commit_time, invalidate_git_dirty_cache, is_git_repository,
+    is_git_working_tree_dirty,
 };
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::env;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a cached git status stays valid (status runs on every prompt)
+const GIT_STATUS_CACHE_TTL_SECS: i64 = 3;
+
+/// Working tree state of a project, read with git2
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitStatusInfo {
+    pub branch_name: Option<String>,
+    pub is_dirty: bool,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GitStatusCacheEntry {
+    project_path: PathBuf,
+    cached_at: DateTime<Utc>,
+    status: GitStatusInfo,
+}
 
 pub async fn handle_status(format: &str, quiet: bool) -> Result<()> {
     let current_dir = env::current_dir()?;
@@ -30,7 +55,13 @@ pub async fn handle_status(format: &str, quiet: bool) -> Result<()> {
 
     if let Some(project) = project {
         // Get additional information
-        let git_info = get_git_info(&current_dir).await;
+        let git_status = get_cached_git_status(&project.path);
+        // Quiet mode runs on every prompt, so skip the slower remote/commit lookups there
+        let git_info = if quiet {
+            json!({})
+        } else {
+            get_git_info(&current_dir, git_status.as_ref()).await
+        };
         let machine_id = get_machine_id();
         let machine_metadata = config.machine_metadata.get(&machine_id);
 
@@ -42,7 +73,7 @@ pub async fn handle_status(format: &str, quiet: bool) -> Result<()> {
                         "tags": project.tags,
                         "path": project.path,
                         "description": project.description,
-                        "language": detect_project_language(&project.path).unwrap_or_else(|| "unknown".to_string())
+                        "language": detect_project_language(&project.path).into_iter().next().unwrap_or_else(|| "unknown".to_string())
                     },
                     "git": git_info,
                     "metadata": {
@@ -56,8 +87,10 @@ pub async fn handle_status(format: &str, quiet: bool) -> Result<()> {
                     let minimal = json!({
                         "name": project.name,
                         "tags": project.tags.join(","),
-                        "git_branch": git_info.get("branch").and_then(|v| v.as_str()).unwrap_or(""),
-                        "git_changes": git_info.get("has_changes").and_then(|v| v.as_bool()).unwrap_or(false)
+                        "git_branch": git_status.as_ref().and_then(|s| s.branch_name.as_deref()).unwrap_or(""),
+                        "git_changes": git_status.as_ref().is_some_and(|s| s.is_dirty),
+                        "ahead": git_status.as_ref().map_or(0, |s| s.ahead),
+                        "behind": git_status.as_ref().map_or(0, |s| s.behind)
                     });
                     println!("{}", minimal);
                 } else {
@@ -74,20 +107,20 @@ pub async fn handle_status(format: &str, quiet: bool) -> Result<()> {
                         format!(" ({})", project.tags.join(", "))
                     };
 
-                    let git_str =
-                        if let Some(branch) = git_info.get("branch").and_then(|v| v.as_str()) {
-                            if git_info
-                                .get("has_changes")
-                                .and_then(|v| v.as_bool())
-                                .unwrap_or(false)
-                            {
-                                format!(" [{}*]", branch)
+                    let git_str = match &git_status {
+                        Some(GitStatusInfo {
+                            branch_name: Some(branch),
+                            is_dirty,
+                            ..
+                        }) => {
+                            if *is_dirty {
+                                format!(" ({}*)", branch)
                             } else {
-                                format!(" [{}]", branch)
+                                format!(" ({})", branch)
                             }
-                        } else {
-                            String::new()
-                        };
+                        }
+                        _ => String::new(),
+                    };
 
                     println!("{}{}{}", project.name, tags_str, git_str);
                 } else {
@@ -117,6 +150,9 @@ pub async fn handle_status(format: &str, quiet: bool) -> Result<()> {
                         };
                         println!("🌿 Git: {}{}", branch, changes_str);
                     }
+                    if let Some(status) = git_status.as_ref().filter(|s| s.ahead > 0 || s.behind > 0) {
+                        println!("🔀 Upstream: {} ahead, {} behind", status.ahead, status.behind);
+                    }
 
 
                     // Access information
@@ -147,6 +183,45 @@ pub async fn handle_status(format: &str, quiet: bool) -> Result<()> {
     Ok(())
 }
 
+/// Redraw the status block every `interval_secs` seconds and whenever project files change
+pub async fn handle_status_watch(format: &str, quiet: bool, interval_secs: u64) -> Result<()> {
+    let interval = Duration::from_secs(interval_secs.max(1));
+    let current_dir = env::current_dir()?;
+    let project_path = match load_config().await {
+        Ok(config) => find_project_by_current_path(&config, &current_dir).map(|p| p.path.clone()),
+        Err(_) => None,
+    };
+    let project_path = project_path.as_deref();
+
+    watch_for_changes(
+        project_path,
+        notify::RecursiveMode::Recursive,
+        is_relevant_change,
+        Some(interval),
+        |changed| async move {
+            if let (true, Some(path)) = (changed, project_path) {
+                invalidate_git_status_cache(path);
+            }
+
+            // Clear the screen and move the cursor home before redrawing
+            print!("\x1B[2J\x1B[1;1H");
+            handle_status(format, quiet).await?;
+            std::io::stdout().flush()?;
+            Ok(())
+        },
+    )
+    .await
+}
+
+/// Ignore churn inside .git (e.g. index refreshes) except branch and ref updates
+pub(crate) fn is_relevant_change(path: &Path) -> bool {
+    let in_git_dir = path.components().any(|c| c.as_os_str() == ".git");
+    !in_git_dir
+        || path
+            .components()
+            .any(|c| c.as_os_str() == "HEAD" || c.as_os_str() == "refs")
+}
+
 fn find_project_by_current_path<'a>(
     config: &'a Config,
     current_dir: &PathBuf,
@@ -163,16 +238,21 @@ fn find_project_by_current_path<'a>(
         .find(|&project| current_dir.starts_with(&project.path))
 }
 
-async fn get_git_info(path: &PathBuf) -> serde_json::Value {
+async fn get_git_info(path: &Path, status: Option<&GitStatusInfo>) -> serde_json::Value {
     let mut git_info = serde_json::Map::new();
 
     git_info.insert("is_repository".to_string(), json!(is_git_repository(path)));
 
-    if is_git_repository(path) {
-        if let Ok(Some(branch)) = get_git_current_branch(path) {
+    if let Some(status) = status {
+        if let Some(branch) = &status.branch_name {
             git_info.insert("branch".to_string(), json!(branch));
         }
+        git_info.insert("has_changes".to_string(), json!(status.is_dirty));
+        git_info.insert("ahead".to_string(), json!(status.ahead));
+        git_info.insert("behind".to_string(), json!(status.behind));
+    }
 
+    if is_git_repository(path) {
         if let Ok(Some(remote_url)) = get_git_remote_url(path) {
             git_info.insert("remote_url".to_string(), json!(remote_url));
         }
@@ -180,27 +260,83 @@ async fn get_git_info(path: &PathBuf) -> serde_json::Value {
         if let Ok(Some(last_commit)) = get_last_git_commit_time(path) {
             git_info.insert("last_commit".to_string(), json!(last_commit));
         }
-
-        // Check for uncommitted changes
-        git_info.insert("has_changes".to_string(), json!(has_git_changes(path)));
     }
 
     json!(git_info)
 }
 
-fn has_git_changes(path: &PathBuf) -> bool {
-    use std::process::Command;
+/// Read branch, dirty state and divergence from `origin` for a repository
+pub fn read_git_status(path: &Path) -> Option<GitStatusInfo> {
+    if !is_git_repository(path) {
+        return None;
+    }
 
-    let output = Command::new("git")
-        .arg("status")
-        .arg("--porcelain")
-        .current_dir(path)
-        .output();
+    // Compares with the local origin ref only; nothing is fetched
+    let (ahead, behind) = check_project_update(path, false, Duration::ZERO)
+        .map_or((0, 0), |update| (update.ahead as u32, update.behind as u32));
 
-    if let Ok(output) = output {
-        !output.stdout.is_empty()
-    } else {
-        false
+    Some(GitStatusInfo {
+        branch_name: get_git_branch_name(path),
+        is_dirty: is_git_working_tree_dirty(path).unwrap_or(false),
+        ahead,
+        behind,
+    })
+}
+
+/// Per-user cache file for a project's git status, named by a stable hash of its path
+fn git_status_cache_path(project_path: &Path) -> Option<PathBuf> {
+    let digest = md5::compute(project_path.as_os_str().as_encoded_bytes());
+    Some(dirs::cache_dir()?.join("pm").join(format!("status-{:x}.json", digest)))
+}
+
+/// Write a cache entry through a temp file in the same directory and rename it into place
+fn write_git_status_cache(cache_path: &Path, content: &str) -> std::io::Result<()> {
+    let dir = cache_path.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir)?;
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    std::io::Write::write_all(&mut tmp, content.as_bytes())?;
+    tmp.persist(cache_path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Drop the cached git status so the next lookup reads the repository again
+pub fn invalidate_git_status_cache(project_path: &Path) {
+    invalidate_git_dirty_cache(project_path);
+    if let Some(cache_path) = git_status_cache_path(project_path) {
+        let _ = std::fs::remove_file(cache_path);
+    }
+}
+
+/// Git status for a project, served from a short-lived cache file
+pub fn get_cached_git_status(project_path: &Path) -> Option<GitStatusInfo> {
+    if !is_git_repository(project_path) {
+        return None;
+    }
+
+    let cache_path = git_status_cache_path(project_path);
+    let cached = cache_path
+        .as_ref()
+        .and_then(|cache_path| std::fs::read_to_string(cache_path).ok())
+        .and_then(|content| serde_json::from_str::<GitStatusCacheEntry>(&content).ok())
+        .filter(|entry| {
+            entry.project_path == project_path
+                && (Utc::now() - entry.cached_at).num_seconds() < GIT_STATUS_CACHE_TTL_SECS
+        });
+    if let Some(entry) = cached {
+        return Some(entry.status);
     }
+
+    let status = read_git_status(project_path)?;
+    let entry = GitStatusCacheEntry {
+        project_path: project_path.to_path_buf(),
+        cached_at: Utc::now(),
+        status: status.clone(),
+    };
+    // The cache is best-effort; a failed write only costs the next prompt a git2 lookup
+    if let (Some(cache_path), Ok(content)) = (cache_path, serde_json::to_string(&entry)) {
+        let _ = write_git_status_cache(&cache_path, &content);
+    }
+
+    Some(status)
 }
 
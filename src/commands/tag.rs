@@ -1,13 +1,85 @@
-use crate::config::{load_config, save_config};
+use crate::config::{load_config, lock_config, save_config, Config};
+use crate::utils::{convert_remote_url, get_git_remote_url, UrlFormat};
 use crate::display::display_error;
 use crate::error::PmError;
-use crate::tag_commands::{add_tags, list_tags, remove_tags, show_tags};
-use anyhow::Result;
+use crate::validation::{validate_tag_name, validate_tag_names, TagNamingPolicy};
+use crate::tag_commands::{
+    add_tags, collect_tag_counts, list_tags, remove_tags, show_tags, TagListOptions,
+};
+use crate::Project;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Split a tag into its parent and optional child (`work/rust` -> `("work", Some("rust"))`)
+pub fn parse_tag_hierarchy(tag: &str) -> (&str, Option<&str>) {
+    match tag.split_once('/') {
+        Some((parent, child)) => (parent, Some(child)),
+        None => (tag, None),
+    }
+}
+
+/// Whether `filter` matches any of `project_tags`
+///
+/// A top-level filter (`work`) also matches its sub-tags (`work/rust`);
+/// a sub-tag filter (`work/rust`) only matches exactly.
+pub fn tags_match(filter: &str, project_tags: &[String]) -> bool {
+    let is_parent_filter = parse_tag_hierarchy(filter).1.is_none();
+    project_tags.iter().any(|tag| match parse_tag_hierarchy(tag) {
+        _ if tag == filter => true,
+        (parent, Some(_)) => is_parent_filter && parent == filter,
+        (_, None) => false,
+    })
+}
+
+/// Output format for `pm tag list`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TagListFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+    Csv,
+}
+
+/// Output format for `pm tag show --all-projects`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TagMatrixFormat {
+    #[default]
+    Text,
+    Csv,
+}
+
+/// Projects (rows) against tags (columns)
+#[derive(Debug, PartialEq)]
+pub struct TagMatrix {
+    pub tags: Vec<String>,
+    /// Project name and, per column, whether the project has that tag
+    pub rows: Vec<(String, Vec<bool>)>,
+}
+
+/// One tag with the projects using it
+#[derive(Debug, Serialize)]
+struct TagUsage {
+    tag: String,
+    projects: Vec<String>,
+    count: u32,
+}
 
 pub async fn handle_tag_add(project_name: &str, tags: &[String]) -> Result<()> {
+    let _lock = lock_config().await?;
     let mut config = load_config().await?;
 
-    match add_tags(project_name, tags, &mut config).await {
+    let tags = match validate_tag_names(tags, config.settings.tag_naming_policy) {
+        Ok(tags) => tags,
+        Err(e) => {
+            display_error("Invalid tags", &e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    match add_tags(project_name, &tags, &mut config).await {
         Ok(_) => {
             save_config(&config).await?;
             Ok(())
@@ -20,6 +92,7 @@ pub async fn handle_tag_add(project_name: &str, tags: &[String]) -> Result<()> {
 }
 
 pub async fn handle_tag_remove(project_name: &str, tags: &[String]) -> Result<()> {
+    let _lock = lock_config().await?;
     let mut config = load_config().await?;
 
     match remove_tags(project_name, tags, &mut config).await {
@@ -34,10 +107,15 @@ pub async fn handle_tag_remove(project_name: &str, tags: &[String]) -> Result<()
     }
 }
 
-pub async fn handle_tag_list() -> Result<()> {
+pub async fn handle_tag_list(options: &TagListOptions, format: TagListFormat) -> Result<()> {
     let config = load_config().await?;
 
-    match list_tags(&config).await {
+    if format != TagListFormat::Text {
+        print!("{}", serialize_tag_list(&config, options, format)?);
+        return Ok(());
+    }
+
+    match list_tags(&config, options).await {
         Ok(_) => Ok(()),
         Err(e) => {
             display_error("Failed to list tags", &e.to_string());
@@ -57,3 +135,514 @@ pub async fn handle_tag_show(project_name: Option<&str>) -> Result<()> {
         }
     }
 }
+
+pub async fn handle_tag_matrix(min_count: u32, format: TagMatrixFormat) -> Result<()> {
+    let config = load_config().await?;
+    let matrix = build_tag_matrix(&config, min_count);
+
+    if matrix.tags.is_empty() {
+        println!("No tags are used by at least {} projects (see --min-count)", min_count);
+        return Ok(());
+    }
+
+    print!("{}", render_tag_matrix(&matrix, format));
+    Ok(())
+}
+
+/// Matrix of tags used by at least `min_count` projects (most used first) against the
+/// projects carrying any of them, sorted by name
+pub fn build_tag_matrix(config: &Config, min_count: u32) -> TagMatrix {
+    let options = TagListOptions {
+        min_count: Some(min_count),
+        ..Default::default()
+    };
+    let tags: Vec<String> = collect_tag_counts(config, &options)
+        .into_iter()
+        .map(|(tag, _)| tag)
+        .collect();
+
+    let mut rows: Vec<(String, Vec<bool>)> = config
+        .projects
+        .values()
+        .map(|project| {
+            let cells: Vec<bool> = tags.iter().map(|tag| project.tags.contains(tag)).collect();
+            (project.name.clone(), cells)
+        })
+        .filter(|(_, cells)| cells.contains(&true))
+        .collect();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+    TagMatrix { tags, rows }
+}
+
+/// Render the matrix as an aligned table (✓ marks) or CSV (1/0 cells)
+pub fn render_tag_matrix(matrix: &TagMatrix, format: TagMatrixFormat) -> String {
+    let mut output = String::new();
+
+    match format {
+        TagMatrixFormat::Csv => {
+            let header: Vec<String> = std::iter::once("project".to_string())
+                .chain(matrix.tags.iter().map(|tag| csv_field(tag)))
+                .collect();
+            output.push_str(&header.join(","));
+            output.push('\n');
+            for (project, cells) in &matrix.rows {
+                output.push_str(&csv_field(project));
+                for &has_tag in cells {
+                    output.push_str(if has_tag { ",1" } else { ",0" });
+                }
+                output.push('\n');
+            }
+        }
+        TagMatrixFormat::Text => {
+            let name_width = matrix
+                .rows
+                .iter()
+                .map(|(name, _)| name.chars().count())
+                .chain(std::iter::once("Project".len()))
+                .max()
+                .unwrap_or_default();
+
+            output.push_str(&format!("{:<width$}", "Project", width = name_width));
+            for tag in &matrix.tags {
+                output.push_str(&format!("  {}", tag));
+            }
+            output.push('\n');
+
+            for (project, cells) in &matrix.rows {
+                output.push_str(&format!("{:<width$}", project, width = name_width));
+                for (tag, &has_tag) in matrix.tags.iter().zip(cells) {
+                    let mark = if has_tag { "✓" } else { "·" };
+                    output.push_str(&format!("  {:^width$}", mark, width = tag.chars().count()));
+                }
+                output.push('\n');
+            }
+        }
+    }
+
+    output
+}
+
+/// Serialize the tag taxonomy (tags, counts and project names) as JSON, YAML or CSV
+///
+/// CSV rows are `tag,count,projects` with project names separated by semicolons.
+pub fn serialize_tag_list(
+    config: &Config,
+    options: &TagListOptions,
+    format: TagListFormat,
+) -> Result<String> {
+    let usages: Vec<TagUsage> = collect_tag_counts(config, options)
+        .into_iter()
+        .map(|(tag, count)| {
+            let mut projects: Vec<String> = config
+                .projects
+                .values()
+                .filter(|project| project.tags.contains(&tag))
+                .map(|project| project.name.clone())
+                .collect();
+            projects.sort();
+            TagUsage { tag, projects, count }
+        })
+        .collect();
+
+    let output = match format {
+        TagListFormat::Json => serde_json::to_string_pretty(&usages)? + "\n",
+        TagListFormat::Yaml => serde_yaml::to_string(&usages)?,
+        TagListFormat::Csv => {
+            let mut csv = String::from("tag,count,projects\n");
+            for usage in &usages {
+                csv.push_str(&format!(
+                    "{},{},{}\n",
+                    csv_field(&usage.tag),
+                    usage.count,
+                    csv_field(&usage.projects.join(";"))
+                ));
+            }
+            csv
+        }
+        TagListFormat::Text => {
+            let mut text = String::new();
+            for usage in &usages {
+                text.push_str(&format!("{} ({})\n", usage.tag, usage.count));
+            }
+            text
+        }
+    };
+
+    Ok(output)
+}
+
+/// Quote a CSV field when it contains separators or quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A rule from a tag rule file: add `tag` to every project matching `condition`
+#[derive(Debug, Clone, Deserialize)]
+pub struct TagRule {
+    pub tag: String,
+    pub condition: TagCondition,
+}
+
+/// Conditions of a tag rule; every condition that is set must hold
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TagCondition {
+    /// File or directory relative to the project root
+    pub file_exists: Option<String>,
+    /// Glob pattern for the project path (`~` is expanded)
+    pub path_matches: Option<String>,
+    /// Host of the `origin` remote, e.g. `github.com`
+    pub remote_host: Option<String>,
+    /// Project field check
+    pub field: Option<FieldCondition>,
+}
+
+/// Compare a project field (`name`, `description` or `color`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldCondition {
+    pub name: String,
+    pub equals: Option<String>,
+    pub contains: Option<String>,
+}
+
+impl TagCondition {
+    fn is_empty(&self) -> bool {
+        self.file_exists.is_none()
+            && self.path_matches.is_none()
+            && self.remote_host.is_none()
+            && self.field.is_none()
+    }
+
+    pub fn matches(&self, project: &Project) -> bool {
+        if let Some(file) = &self.file_exists {
+            if !project.path.join(file).exists() {
+                return false;
+            }
+        }
+
+        if let Some(pattern) = &self.path_matches {
+            let matched = glob::Pattern::new(&shellexpand::tilde(pattern))
+                .map(|pattern| pattern.matches_path(&project.path))
+                .unwrap_or(false);
+            if !matched {
+                return false;
+            }
+        }
+
+        if let Some(host) = &self.remote_host {
+            let remote_host = get_git_remote_url(&project.path)
+                .ok()
+                .flatten()
+                .and_then(|url| convert_remote_url(&url, UrlFormat::Https))
+                .and_then(|url| {
+                    url.trim_start_matches("https://")
+                        .split('/')
+                        .next()
+                        .map(str::to_string)
+                });
+            if !remote_host.is_some_and(|remote_host| remote_host.eq_ignore_ascii_case(host)) {
+                return false;
+            }
+        }
+
+        if let Some(field) = &self.field {
+            let value = match field.name.as_str() {
+                "name" => Some(project.name.as_str()),
+                "description" => project.description.as_deref(),
+                "color" => project.color.as_deref(),
+                _ => None,
+            };
+            let Some(value) = value else {
+                return false;
+            };
+            if field.equals.as_deref().is_some_and(|expected| value != expected)
+                || field.contains.as_deref().is_some_and(|needle| !value.contains(needle))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Read and validate a YAML tag rule file; rule tags are checked against `policy`
+pub fn load_tag_rules(path: &Path, policy: TagNamingPolicy) -> Result<Vec<TagRule>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tag rule file: {}", path.display()))?;
+    let mut rules: Vec<TagRule> = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse tag rule file: {}", path.display()))?;
+
+    for rule in &mut rules {
+        rule.tag = validate_tag_name(&rule.tag, policy)?;
+        if rule.condition.is_empty() {
+            return Err(anyhow::anyhow!("Rule for tag '{}' has no condition", rule.tag));
+        }
+        if let Some(field) = &rule.condition.field {
+            if !matches!(field.name.as_str(), "name" | "description" | "color") {
+                return Err(anyhow::anyhow!(
+                    "Rule for tag '{}': unknown field '{}' (use name, description or color)",
+                    rule.tag,
+                    field.name
+                ));
+            }
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Apply `rules` to every project; returns the tags added per project name, sorted by name
+pub fn apply_tag_rules(config: &mut Config, rules: &[TagRule], dry_run: bool) -> Vec<(String, Vec<String>)> {
+    let mut changes = Vec::new();
+
+    for project in config.projects.values_mut() {
+        let new_tags: Vec<String> = rules
+            .iter()
+            .filter(|rule| !project.tags.contains(&rule.tag) && rule.condition.matches(project))
+            .map(|rule| rule.tag.clone())
+            .fold(Vec::new(), |mut tags, tag| {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+                tags
+            });
+
+        if new_tags.is_empty() {
+            continue;
+        }
+        if !dry_run {
+            project.tags.extend(new_tags.iter().cloned());
+            project.updated_at = Utc::now();
+        }
+        changes.push((project.name.clone(), new_tags));
+    }
+
+    changes.sort();
+    changes
+}
+
+/// `pm tag auto-assign`: apply a rule file to all projects
+pub async fn handle_tag_auto_assign(rules_file: &Path, dry_run: bool) -> Result<()> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let rules = load_tag_rules(rules_file, config.settings.tag_naming_policy).map_err(|e| {
+        display_error("Invalid tag rule file", &format!("{:#}", e));
+        PmError::ValidationFailed(e.to_string())
+    })?;
+    let changes = apply_tag_rules(&mut config, &rules, dry_run);
+
+    println!("🏷️  Applying {} tag rule(s) from {}", rules.len(), rules_file.display());
+    if changes.is_empty() {
+        println!("✅ All projects already match the rules");
+        return Ok(());
+    }
+
+    for (project_name, tags) in &changes {
+        println!("   + {}: {}", project_name, tags.join(", "));
+    }
+
+    if dry_run {
+        println!("\n🔍 Dry run: {} project(s) would be tagged", changes.len());
+    } else {
+        save_config(&config).await?;
+        println!("\n✅ Tagged {} project(s)", changes.len());
+    }
+
+    Ok(())
+}
+
+/// Prefix that marks tags synced from manifest keywords
+pub const KEYWORD_TAG_PREFIX: &str = "kw:";
+
+/// `kw:`-prefixed tags for manifest keywords (lowercased, whitespace replaced with `-`)
+pub fn keyword_tags(keywords: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for keyword in keywords {
+        let words: Vec<&str> = keyword.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+        let tag = format!("{}{}", KEYWORD_TAG_PREFIX, words.join("-").to_lowercase());
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// `pm tag-sync`: add `kw:` tags from `package.json`, `Cargo.toml` or `pyproject.toml` keywords
+pub async fn handle_tag_sync(project_name: Option<&str>, all: bool, dry_run: bool) -> Result<()> {
+    let _lock = lock_config().await?;
+    let mut config = load_config().await?;
+    let policy = config.settings.tag_naming_policy;
+
+    let mut ids: Vec<uuid::Uuid> = if all {
+        config.projects.keys().copied().collect()
+    } else {
+        let project = match project_name {
+            Some(name) => config.find_project_by_name(name),
+            None => config.find_project_by_path(&std::env::current_dir()?),
+        };
+        let Some(project) = project else {
+            display_error(crate::constants::ERROR_PROJECT_NOT_FOUND, project_name.unwrap_or("for the current directory"));
+            return Err(PmError::ProjectNotFound.into());
+        };
+        vec![project.id]
+    };
+    ids.sort_by_key(|id| config.projects[id].name.clone());
+
+    let mut changed = 0;
+    for id in ids {
+        let Some(project) = config.projects.get_mut(&id) else {
+            continue;
+        };
+        let keywords = crate::utils::read_manifest_keywords(&project.path);
+        if keywords.is_empty() {
+            if !all {
+                println!("📦 {}: no keywords in package.json, Cargo.toml or pyproject.toml", project.name);
+            }
+            continue;
+        }
+
+        let mut new_tags = Vec::new();
+        for tag in keyword_tags(&keywords) {
+            match crate::validation::validate_tag_name(&tag, policy) {
+                Ok(tag) if !project.tags.contains(&tag) => new_tags.push(tag),
+                Ok(_) => {}
+                Err(e) => println!("   ⚠️  {}: skipped {}", project.name, e),
+            }
+        }
+
+        if new_tags.is_empty() {
+            if !all {
+                println!("✅ {}: keyword tags are up to date", project.name);
+            }
+            continue;
+        }
+
+        println!("   + {}: {}", project.name, new_tags.join(", "));
+        if !dry_run {
+            project.tags.extend(new_tags);
+            project.updated_at = Utc::now();
+        }
+        changed += 1;
+    }
+
+    if changed == 0 {
+        if all {
+            println!("✅ All keyword tags are up to date");
+        }
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("\n🔍 Dry run: {} project(s) would be tagged", changed);
+    } else {
+        save_config(&config).await?;
+        println!("\n✅ Tagged {} project(s)", changed);
+    }
+
+    Ok(())
+}
+
+/// Apply `settings.tag_rules_file` if configured (used after `pm scan`)
+pub async fn apply_configured_tag_rules() -> Result<()> {
+    let config = load_config().await?;
+    let Some(rules_file) = config.settings.tag_rules_file.as_deref() else {
+        return Ok(());
+    };
+
+    let rules_file = std::path::PathBuf::from(shellexpand::tilde(rules_file).into_owned());
+    println!();
+    handle_tag_auto_assign(&rules_file, false).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::project::{config_with_projects, new_project};
+
+    #[test]
+    fn test_keyword_tags() {
+        let keywords = vec!["CLI".to_string(), "command line".to_string(), "cli".to_string(), " ".to_string()];
+        assert_eq!(keyword_tags(&keywords), vec!["kw:cli", "kw:command-line"]);
+    }
+
+    #[test]
+    fn test_tags_match_hierarchy() {
+        let tags = vec!["work/rust".to_string(), "cli".to_string()];
+
+        assert!(tags_match("work", &tags));
+        assert!(tags_match("work/rust", &tags));
+        assert!(tags_match("cli", &tags));
+        assert!(!tags_match("work/go", &tags));
+        assert!(!tags_match("rust", &tags));
+        assert!(!tags_match("wor", &tags));
+        assert!(!tags_match("cli/extra", &tags));
+    }
+
+    #[test]
+    fn test_tag_condition_matches() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("Cargo.toml"), "").unwrap();
+        let project = new_project(temp.path(), "tool".to_string(), vec![], Some("Internal CLI".to_string()));
+
+        let rules: Vec<TagRule> = serde_yaml::from_str(
+            "- tag: rust\n  condition: { file_exists: Cargo.toml }\n\
+             - tag: node\n  condition: { file_exists: package.json }\n\
+             - tag: internal\n  condition: { field: { name: description, contains: Internal } }\n\
+             - tag: github\n  condition: { remote_host: github.com }\n",
+        )
+        .unwrap();
+
+        let matched: Vec<&str> = rules
+            .iter()
+            .filter(|rule| rule.condition.matches(&project))
+            .map(|rule| rule.tag.as_str())
+            .collect();
+        assert_eq!(matched, vec!["rust", "internal"]);
+    }
+
+    #[test]
+    fn test_serialize_tag_list() {
+        let config = config_with_projects(&[("web", &["work", "a,b"]), ("api", &["work"])]);
+        let options = TagListOptions::default();
+
+        assert_eq!(
+            serialize_tag_list(&config, &options, TagListFormat::Csv).unwrap(),
+            "tag,count,projects\nwork,2,api;web\n\"a,b\",1,web\n"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&serialize_tag_list(&config, &options, TagListFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["tag"], "work");
+        assert_eq!(json[0]["count"], 2);
+        assert_eq!(json[0]["projects"], serde_json::json!(["api", "web"]));
+
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(&serialize_tag_list(&config, &options, TagListFormat::Yaml).unwrap()).unwrap();
+        assert_eq!(yaml[1]["tag"].as_str(), Some("a,b"));
+    }
+
+    #[test]
+    fn test_tag_matrix() {
+        let config = config_with_projects(&[("web", &["work", "node"]), ("api", &["work", "rust"]), ("notes", &[])]);
+
+        let shared = build_tag_matrix(&config, 2);
+        assert_eq!(shared.tags, vec!["work"]);
+        assert_eq!(shared.rows.len(), 2);
+
+        let all = build_tag_matrix(&config, 1);
+        assert_eq!(
+            render_tag_matrix(&all, TagMatrixFormat::Csv),
+            "project,work,node,rust\napi,1,0,1\nweb,1,1,0\n"
+        );
+    }
+}
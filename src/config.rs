@@ -1,5 +1,7 @@
+use crate::backup::{add_backup_entry, create_backup, BackupReason};
 use crate::constants::*;
-use crate::utils::is_git_repository;
+use crate::error::PmError;
+use crate::utils::{canonical_path, is_git_repository};
 use crate::{MachineMetadata, Project};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -35,7 +37,7 @@ pub struct Config {
     pub machine_metadata: HashMap<String, MachineMetadata>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 #[schemars(
     title = "Configuration Settings",
     description = "Application-specific settings"
@@ -47,6 +49,63 @@ pub struct ConfigSettings {
     #[serde(default = "default_recent_projects_limit")]
     #[schemars(description = "Maximum number of recent projects to display")]
     pub recent_projects_limit: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "GitLab instance URL used by 'pm clone --gitlab' (defaults to https://gitlab.com)")]
+    pub gitlab_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Editor command (overridden by $VISUAL, takes precedence over $EDITOR)")]
+    pub editor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "IDE used by 'pm open' when --ide is not given (vscode, vim, emacs, idea, goland or xcode)")]
+    pub default_ide: Option<crate::utils::Ide>,
+    #[serde(default)]
+    #[schemars(description = "Run extensions inside an OS sandbox using their manifest permissions")]
+    pub extension_sandbox: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Tag rule file applied automatically after 'pm scan' (see 'pm tag auto-assign')")]
+    pub tag_rules_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Backup retention policy applied after new backups are recorded (keep-n:<n>, keep-days:<d> or keep-size:<mb>)")]
+    pub backup_retention_policy: Option<crate::backup::RetentionPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Warn in 'pm backup status --size' when backups use more than this many MB")]
+    pub backup_size_warning_mb: Option<u64>,
+    #[serde(default = "default_allow_unicode_paths")]
+    #[schemars(description = "Allow non-ASCII characters in project paths")]
+    pub allow_unicode_paths: bool,
+    #[serde(default)]
+    #[schemars(description = "Tag naming rules: strict, lowercase-only or permissive")]
+    pub tag_naming_policy: crate::validation::TagNamingPolicy,
+    #[serde(default)]
+    #[schemars(description = "Where 'pm add' takes project names from: directory, cargo-package, node-package or git-repo")]
+    pub default_name_strategy: crate::utils::NameStrategy,
+    #[serde(default = "default_registry_cache_ttl_hours")]
+    #[schemars(description = "Hours a cached registry index stays valid for 'pm ext search --cached'")]
+    pub registry_cache_ttl_hours: u64,
+    #[serde(default)]
+    #[schemars(description = "Tag projects added with 'pm add' with their detected languages")]
+    pub auto_detect_language: bool,
+}
+
+impl Default for ConfigSettings {
+    fn default() -> Self {
+        Self {
+            show_git_status: default_show_git_status(),
+            recent_projects_limit: default_recent_projects_limit(),
+            gitlab_url: None,
+            editor: None,
+            default_ide: None,
+            extension_sandbox: false,
+            tag_rules_file: None,
+            backup_retention_policy: None,
+            backup_size_warning_mb: None,
+            allow_unicode_paths: default_allow_unicode_paths(),
+            tag_naming_policy: Default::default(),
+            default_name_strategy: Default::default(),
+            registry_cache_ttl_hours: default_registry_cache_ttl_hours(),
+            auto_detect_language: false,
+        }
+    }
 }
 
 
@@ -54,6 +113,14 @@ fn default_show_git_status() -> bool {
     true
 }
 
+fn default_allow_unicode_paths() -> bool {
+    true
+}
+
+fn default_registry_cache_ttl_hours() -> u64 {
+    crate::extensions::remote::DEFAULT_REGISTRY_CACHE_TTL_HOURS
+}
+
 fn default_recent_projects_limit() -> u32 {
     10
 }
@@ -121,8 +188,13 @@ pub fn get_schema_path() -> Result<PathBuf> {
     Ok(config_dir.join("config.schema.json"))
 }
 
+/// JSON Schema describing the config file
+pub fn config_schema() -> schemars::schema::RootSchema {
+    schema_for!(Config)
+}
+
 pub async fn generate_schema() -> Result<()> {
-    let schema = schema_for!(Config);
+    let schema = config_schema();
     let schema_content = serde_json::to_string_pretty(&schema)?;
 
     let schema_path = get_schema_path()?;
@@ -146,7 +218,19 @@ pub async fn load_config() -> Result<Config> {
             crate::utils::get_binary_name()
         ));
     }
-    let content = fs::read_to_string(path).await?;
+    let mut content = fs::read_to_string(&path).await?;
+
+    // Older configs may not deserialize until migrated, so check the raw version first
+    let value: serde_yaml::Value = serde_yaml::from_str(&content)?;
+    if needs_migration(&get_config_value_version(&value)) {
+        if let Some((from_version, _, _)) = migrate_config_file().await? {
+            eprintln!(
+                "🔄 Migrated configuration v{} → v{} (previous version backed up)",
+                from_version, CONFIG_VERSION
+            );
+        }
+        content = fs::read_to_string(&path).await?;
+    }
     let mut config: Config = serde_yaml::from_str(&content)?;
 
     // Migration: Check if any projects need git repository status update
@@ -159,8 +243,17 @@ pub async fn load_config() -> Result<Config> {
         }
     }
 
-    // Save config if migration was needed
+    // Save config if migration was needed, re-reading under the lock so a concurrent
+    // save by another PM process is not overwritten
     if needs_migration {
+        let _lock = lock_config().await?;
+        let content = fs::read_to_string(&path).await?;
+        config = serde_yaml::from_str(&content)?;
+        for project in config.projects.values_mut() {
+            if !project.is_git_repository && is_git_repository(&project.path) {
+                project.is_git_repository = true;
+            }
+        }
         save_config(&config).await?;
     }
 
@@ -170,6 +263,31 @@ pub async fn load_config() -> Result<Config> {
     Ok(config)
 }
 
+/// Back up the config file and migrate it to CONFIG_VERSION
+///
+/// Returns the version it was migrated from, the applied steps and the backup ID, or `None`
+/// when the file is already current (for example because another PM process migrated it first).
+pub async fn migrate_config_file() -> Result<Option<(String, Vec<String>, String)>> {
+    let path = get_config_path()?;
+    let _lock = lock_config().await?;
+    let content = fs::read_to_string(&path).await?;
+    let value: serde_yaml::Value = serde_yaml::from_str(&content)?;
+    let from_version = get_config_value_version(&value);
+    if !needs_migration(&from_version) {
+        return Ok(None);
+    }
+
+    let backup = create_backup(&path, BackupReason::ConfigMigration).await?;
+    let (migrated, applied) = migrate_config_value(value)?;
+    let config: Config = serde_yaml::from_value(migrated)
+        .map_err(|e| PmError::ValidationFailed(format!("migrated config is invalid: {}", e)))?;
+    save_config(&config).await?;
+    let backup_id = backup.id.clone();
+    add_backup_entry(backup).await?;
+
+    Ok(Some((from_version, applied, backup_id)))
+}
+
 pub async fn save_config(config: &Config) -> Result<()> {
     let path = get_config_path()?;
     let parent_dir = path.parent().context("Failed to get parent directory")?;
@@ -188,7 +306,7 @@ pub async fn save_config(config: &Config) -> Result<()> {
         yaml_content
     );
 
-    fs::write(path, content).await?;
+    write_config_atomically(&path, content.as_bytes()).await?;
 
     // Auto-generate schema file
     if let Err(e) = generate_schema().await {
@@ -198,6 +316,307 @@ pub async fn save_config(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Lock file guarding writes to the config file at `config_path`
+pub fn get_config_lock_path(config_path: &Path) -> PathBuf {
+    let mut name = config_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    config_path.with_file_name(name)
+}
+
+/// Config lock held by this process, so nested `lock_config_at` calls do not deadlock
+static HELD_CONFIG_LOCK: std::sync::Mutex<Option<(PathBuf, std::fs::File)>> =
+    std::sync::Mutex::new(None);
+
+/// Exclusive lock on the config file, released when the outermost guard is dropped
+///
+/// Commands that modify the config take this before `load_config` and keep it until
+/// after `save_config`, so another PM process cannot save in between and have its
+/// changes overwritten.
+pub struct ConfigLock {
+    outermost: bool,
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        if self.outermost {
+            if let Ok(mut held) = HELD_CONFIG_LOCK.lock() {
+                held.take();
+            }
+        }
+    }
+}
+
+/// Lock the user's config file for a load → modify → save sequence
+///
+/// Does nothing when PM is not initialized yet; `load_config` reports that case.
+pub async fn lock_config() -> Result<ConfigLock> {
+    let path = get_config_path()?;
+    if !path.exists() {
+        return Ok(ConfigLock { outermost: false });
+    }
+    lock_config_at(&path).await
+}
+
+async fn lock_config_at(config_path: &Path) -> Result<ConfigLock> {
+    let already_held = HELD_CONFIG_LOCK
+        .lock()
+        .map(|held| matches!(held.as_ref(), Some((path, _)) if path == config_path))
+        .unwrap_or(false);
+    if already_held {
+        return Ok(ConfigLock { outermost: false });
+    }
+
+    let file = lock_config_file(config_path).await?;
+    if let Ok(mut held) = HELD_CONFIG_LOCK.lock() {
+        *held = Some((config_path.to_path_buf(), file));
+    }
+    Ok(ConfigLock { outermost: true })
+}
+
+/// Write `content` to a temporary file next to `path` and rename it into place
+///
+/// Holds the config lock for the duration so concurrent PM processes cannot interleave
+/// writes. Callers that already hold it through `lock_config` keep it after the rename.
+async fn write_config_atomically(path: &Path, content: &[u8]) -> Result<()> {
+    let _lock = lock_config_at(path).await?;
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = fs::File::create(&tmp_path)
+        .await
+        .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+    tokio::io::AsyncWriteExt::write_all(&mut file, content).await?;
+    file.sync_all().await?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)
+        .await
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// Take the exclusive config lock, waiting up to `CONFIG_LOCK_TIMEOUT_SECS`
+///
+/// The holder's PID is written into the lock file so `pm init --force-unlock` can report it.
+async fn lock_config_file(config_path: &Path) -> Result<std::fs::File> {
+    use fs2::FileExt;
+    use std::io::Write;
+
+    let lock_path = get_config_lock_path(config_path);
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open lock file {}", lock_path.display()))?;
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(CONFIG_LOCK_TIMEOUT_SECS);
+    loop {
+        match FileExt::try_lock_exclusive(&file) {
+            Ok(()) => {
+                file.set_len(0)?;
+                write!(file, "{}", std::process::id())?;
+                return Ok(file);
+            }
+            Err(e) if is_lock_contended(&e) && std::time::Instant::now() < deadline => {
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            }
+            Err(e) if is_lock_contended(&e) => {
+                let holder = read_lock_holder(&lock_path)
+                    .map(|pid| format!("PID {}", pid))
+                    .unwrap_or_else(|| "unknown PID".to_string());
+                anyhow::bail!(
+                    "Configuration is locked by another PM process ({}). Wait for it to finish and try again",
+                    holder
+                );
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", lock_path.display()));
+            }
+        }
+    }
+}
+
+/// Whether a failed `try_lock_*` means another process holds the lock
+fn is_lock_contended(error: &std::io::Error) -> bool {
+    error.raw_os_error() == fs2::lock_contended_error().raw_os_error()
+}
+
+/// PID recorded in the lock file by the process that last took the lock
+fn read_lock_holder(lock_path: &Path) -> Option<u32> {
+    std::fs::read_to_string(lock_path).ok()?.trim().parse().ok()
+}
+
+/// Who currently holds the config lock
+#[derive(Debug, PartialEq)]
+pub enum ConfigLockStatus {
+    /// No lock file exists yet
+    Missing,
+    /// The lock file exists but no process holds it
+    Free,
+    /// Another process holds the lock; the PID is known if the holder recorded it
+    Held(Option<u32>),
+}
+
+/// Report who holds the config lock without touching it
+///
+/// The lock is an OS file lock that is released automatically when its holder exits,
+/// so there is never a stale lock to remove; deleting the file would only let two
+/// processes lock different inodes at the same time.
+pub fn config_lock_status() -> Result<ConfigLockStatus> {
+    config_lock_status_at(&get_config_path()?)
+}
+
+fn config_lock_status_at(config_path: &Path) -> Result<ConfigLockStatus> {
+    let lock_path = get_config_lock_path(config_path);
+    let file = match std::fs::File::open(&lock_path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ConfigLockStatus::Missing),
+        Err(e) => return Err(e).with_context(|| format!("Failed to open {}", lock_path.display())),
+    };
+
+    match fs2::FileExt::try_lock_shared(&file) {
+        Ok(()) => Ok(ConfigLockStatus::Free),
+        Err(e) if is_lock_contended(&e) => Ok(ConfigLockStatus::Held(read_lock_holder(&lock_path))),
+        Err(e) => {
+            Err(e).with_context(|| format!("Failed to check {}", lock_path.display()))
+        }
+    }
+}
+
+/// A step in the configuration migration chain
+///
+/// Each step upgrades a raw config to the next version and sets `version` to it. Configs that
+/// are already at or past that version are returned unchanged.
+pub type ConfigMigration = fn(serde_yaml::Value) -> Result<serde_yaml::Value>;
+
+/// All known migrations, ordered from oldest to newest
+pub fn migrations() -> Vec<ConfigMigration> {
+    vec![migrate_0_1_0_to_0_1_1]
+}
+
+/// 0.1.0 → 0.1.1: drop the workspace/editor keys, add settings and git repository flags
+fn migrate_0_1_0_to_0_1_1(mut value: serde_yaml::Value) -> Result<serde_yaml::Value> {
+    if !needs_migration_to(&get_config_value_version(&value), "0.1.1") {
+        return Ok(value);
+    }
+
+    let root = value
+        .as_mapping_mut()
+        .context("Configuration root must be a mapping")?;
+
+    root.remove("projects_root_dir");
+    root.remove("editor");
+
+    let settings = root
+        .entry("settings".into())
+        .or_insert_with(|| serde_yaml::Value::Mapping(Default::default()));
+    if let Some(settings) = settings.as_mapping_mut() {
+        settings
+            .entry("show_git_status".into())
+            .or_insert(default_show_git_status().into());
+        settings
+            .entry("recent_projects_limit".into())
+            .or_insert(default_recent_projects_limit().into());
+    }
+
+    root.entry("machine_metadata".into())
+        .or_insert_with(|| serde_yaml::Value::Mapping(Default::default()));
+
+    if let Some(projects) = root
+        .get_mut("projects")
+        .and_then(|projects| projects.as_mapping_mut())
+    {
+        for project in projects.values_mut() {
+            if let Some(project) = project.as_mapping_mut() {
+                let is_git = project
+                    .get("path")
+                    .and_then(|path| path.as_str())
+                    .map(|path| is_git_repository(Path::new(path)))
+                    .unwrap_or(false);
+                project
+                    .entry("is_git_repository".into())
+                    .or_insert(is_git.into());
+            }
+        }
+    }
+
+    root.insert("version".into(), "0.1.1".into());
+    Ok(value)
+}
+
+/// Compare two dotted version strings numerically (missing parts count as 0)
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| -> Vec<u32> {
+        v.trim_start_matches('v')
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let ordering = a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0));
+        if ordering != std::cmp::Ordering::Equal {
+            return ordering;
+        }
+    }
+    std::cmp::Ordering::Equal
+}
+
+/// Check whether a config with the given version needs migrating to CONFIG_VERSION
+pub fn needs_migration(version: &str) -> bool {
+    needs_migration_to(version, CONFIG_VERSION)
+}
+
+fn needs_migration_to(version: &str, target: &str) -> bool {
+    compare_versions(version, target) == std::cmp::Ordering::Less
+}
+
+/// Read the version from a raw config value (configs predating the field are 0.1.0)
+pub fn get_config_value_version(value: &serde_yaml::Value) -> String {
+    value
+        .get("version")
+        .and_then(|version| version.as_str())
+        .unwrap_or("0.1.0")
+        .to_string()
+}
+
+/// Run every migration newer than the config's version and bump it to CONFIG_VERSION.
+/// Returns the migrated value along with the applied steps ("from → to").
+pub fn migrate_config_value(
+    mut value: serde_yaml::Value,
+) -> Result<(serde_yaml::Value, Vec<String>)> {
+    let original_version = get_config_value_version(&value);
+    if compare_versions(&original_version, CONFIG_VERSION) == std::cmp::Ordering::Greater {
+        return Err(anyhow::anyhow!(
+            "Configuration version {} is newer than this binary supports ({})",
+            original_version,
+            CONFIG_VERSION
+        ));
+    }
+
+    let mut applied = Vec::new();
+    for (step, migration) in migrations().into_iter().enumerate() {
+        let from_version = get_config_value_version(&value);
+        value = migration(value)
+            .with_context(|| format!("Migration step {} from v{} failed", step + 1, from_version))?;
+        let to_version = get_config_value_version(&value);
+        if to_version != from_version {
+            applied.push(format!("{} → {}", from_version, to_version));
+        }
+    }
+
+    if let Some(root) = value.as_mapping_mut() {
+        root.insert("version".into(), CONFIG_VERSION.into());
+    }
+
+    Ok((value, applied))
+}
+
 impl Config {
     pub fn add_project(&mut self, project: Project) {
         self.projects.insert(project.id, project);
@@ -215,18 +634,76 @@ impl Config {
         self.projects.values().find(|p| path.starts_with(&p.path))
     }
 
+    /// Projects registered more than once under the same canonical path, grouped by path
+    pub fn find_duplicate_paths(&self) -> Vec<(PathBuf, Vec<&Project>)> {
+        let mut by_path: std::collections::BTreeMap<PathBuf, Vec<&Project>> =
+            std::collections::BTreeMap::new();
+        for project in self.projects.values() {
+            by_path.entry(canonical_path(&project.path)).or_default().push(project);
+        }
+
+        by_path
+            .into_iter()
+            .filter(|(_, projects)| projects.len() > 1)
+            .map(|(path, mut projects)| {
+                projects.sort_by(|a, b| a.name.cmp(&b.name));
+                (path, projects)
+            })
+            .collect()
+    }
+
     pub fn record_project_access(&mut self, project_id: Uuid) {
         let machine_id = get_machine_id();
         let metadata = self.machine_metadata.entry(machine_id).or_default();
 
         // Update last accessed time
-        metadata.last_accessed.insert(project_id, Utc::now());
+        let now = Utc::now();
+        metadata.last_accessed.insert(project_id, now);
+        let recent = metadata.recent_accesses.entry(project_id).or_default();
+        recent.insert(0, now);
+        recent.truncate(MAX_RECENT_ACCESSES);
 
         // Update access count
         let count = metadata.access_counts.entry(project_id).or_insert(0);
         *count += 1;
     }
 
+    /// Record a switch to `project_id` at the front of this machine's switch history
+    ///
+    /// Like a browser, switching after `--back` discards the entries that `--forward`
+    /// would have returned to.
+    pub fn record_switch(&mut self, project_id: Uuid) {
+        let metadata = self.machine_metadata.entry(get_machine_id()).or_default();
+        let position = metadata.switch_history_position.min(metadata.switch_history.len());
+        metadata.switch_history.drain(..position);
+        metadata.switch_history_position = 0;
+
+        if metadata.switch_history.first() != Some(&project_id) {
+            metadata.switch_history.insert(0, project_id);
+            metadata.switch_history.truncate(MAX_SWITCH_HISTORY);
+        }
+    }
+
+    /// Move through this machine's switch history, returning the project now current
+    ///
+    /// Positive steps go back (older), negative steps go forward. Returns `None` when
+    /// there is no entry in that direction.
+    pub fn step_switch_history(&mut self, steps: isize) -> Option<Uuid> {
+        let metadata = self.machine_metadata.get_mut(&get_machine_id())?;
+        let position = metadata.switch_history_position.checked_add_signed(steps)?;
+        let project_id = *metadata.switch_history.get(position)?;
+        metadata.switch_history_position = position;
+        Some(project_id)
+    }
+
+    /// This machine's switch history (most recent first) and the current position in it
+    pub fn get_switch_history(&self) -> (&[Uuid], usize) {
+        match self.machine_metadata.get(&get_machine_id()) {
+            Some(metadata) => (&metadata.switch_history, metadata.switch_history_position),
+            None => (&[], 0),
+        }
+    }
+
     pub fn remove_project(&mut self, project_id: Uuid) -> anyhow::Result<()> {
         // Remove project from main collection
         self.projects.remove(&project_id);
@@ -235,6 +712,16 @@ impl Config {
         for metadata in self.machine_metadata.values_mut() {
             metadata.last_accessed.remove(&project_id);
             metadata.access_counts.remove(&project_id);
+            metadata.recent_accesses.remove(&project_id);
+
+            let position = metadata.switch_history_position.min(metadata.switch_history.len());
+            let removed_before = metadata.switch_history[..position]
+                .iter()
+                .filter(|id| **id == project_id)
+                .count();
+            metadata.switch_history.retain(|id| *id != project_id);
+            metadata.switch_history_position = (position - removed_before)
+                .min(metadata.switch_history.len().saturating_sub(1));
         }
         
         Ok(())
@@ -256,6 +743,14 @@ impl Config {
         }
     }
 
+    /// This machine's most recent access times for a project, newest first
+    pub fn get_recent_accesses(&self, project_id: Uuid) -> &[DateTime<Utc>] {
+        self.machine_metadata
+            .get(&get_machine_id())
+            .and_then(|metadata| metadata.recent_accesses.get(&project_id))
+            .map_or(&[], Vec::as_slice)
+    }
+
     #[allow(dead_code)]
     pub fn get_total_access_count(&self, project_id: Uuid) -> u32 {
         self.machine_metadata
@@ -313,3 +808,131 @@ pub fn get_machine_id() -> String {
             .unwrap_or_else(|_| "unknown".to_string())
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_config_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILENAME);
+        std::fs::write(&path, "old").unwrap();
+
+        write_config_atomically(&path, b"new").await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert!(!dir.path().join("config.yml.tmp").exists());
+        assert_eq!(get_config_lock_path(&path), dir.path().join("config.yml.lock"));
+
+        // The lock is released once the write finishes
+        let lock = std::fs::File::open(get_config_lock_path(&path)).unwrap();
+        assert!(fs2::FileExt::try_lock_exclusive(&lock).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_config_lock_is_held_across_writes_and_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILENAME);
+        assert_eq!(config_lock_status_at(&path).unwrap(), ConfigLockStatus::Missing);
+
+        let guard = lock_config_at(&path).await.unwrap();
+        // Nested writes reuse the held lock instead of waiting on it
+        write_config_atomically(&path, b"new").await.unwrap();
+        assert_eq!(
+            config_lock_status_at(&path).unwrap(),
+            ConfigLockStatus::Held(Some(std::process::id()))
+        );
+
+        drop(guard);
+        assert_eq!(config_lock_status_at(&path).unwrap(), ConfigLockStatus::Free);
+        assert!(get_config_lock_path(&path).exists());
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        use std::cmp::Ordering;
+        assert_eq!(compare_versions("0.1.0", "0.1.1"), Ordering::Less);
+        assert_eq!(compare_versions("0.2", "0.1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("v0.1.1", "0.1.1"), Ordering::Equal);
+        assert!(needs_migration("0.1.0"));
+        assert!(!needs_migration(CONFIG_VERSION));
+    }
+
+    #[test]
+    fn test_migrate_legacy_config() {
+        let legacy = r#"
+version: "0.1.0"
+config_path: /tmp/pm
+projects_root_dir: ~/workspace
+editor: hx
+projects: {}
+"#;
+        let value: serde_yaml::Value = serde_yaml::from_str(legacy).unwrap();
+        let (migrated, applied) = migrate_config_value(value).unwrap();
+
+        assert_eq!(applied, vec!["0.1.0 → 0.1.1".to_string()]);
+        assert!(migrated.get("projects_root_dir").is_none());
+        assert!(migrated.get("editor").is_none());
+
+        let config: Config = serde_yaml::from_value(migrated.clone()).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(config.settings.show_git_status);
+        assert_eq!(config.settings.recent_projects_limit, 10);
+
+        // Every step leaves an already migrated config alone
+        for migration in migrations() {
+            assert_eq!(migration(migrated.clone()).unwrap(), migrated);
+        }
+        let (_, applied) = migrate_config_value(migrated).unwrap();
+        assert!(applied.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_version() {
+        let value: serde_yaml::Value =
+            serde_yaml::from_str("version: \"99.0.0\"\nprojects: {}\n").unwrap();
+        assert!(migrate_config_value(value).is_err());
+    }
+
+    #[test]
+    fn test_switch_history_back_forward() {
+        let mut config = Config::default();
+        let [a, b, c] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        config.record_switch(a);
+        config.record_switch(b);
+        config.record_switch(b);
+
+        assert_eq!(config.get_switch_history(), (&[b, a][..], 0));
+        assert_eq!(config.step_switch_history(1), Some(a));
+        assert_eq!(config.step_switch_history(1), None);
+        assert_eq!(config.step_switch_history(-1), Some(b));
+        assert_eq!(config.step_switch_history(-1), None);
+
+        // Switching after going back drops the forward entries
+        config.step_switch_history(1);
+        config.record_switch(c);
+        assert_eq!(config.get_switch_history(), (&[c, a][..], 0));
+
+        config.step_switch_history(1);
+        config.remove_project(c).unwrap();
+        assert_eq!(config.get_switch_history(), (&[a][..], 0));
+    }
+
+    #[test]
+    fn test_recent_accesses_keep_newest() {
+        let mut config = Config::default();
+        let project_id = Uuid::new_v4();
+        for _ in 0..MAX_RECENT_ACCESSES + 2 {
+            config.record_project_access(project_id);
+        }
+
+        let recent = config.get_recent_accesses(project_id);
+        assert_eq!(recent.len(), MAX_RECENT_ACCESSES);
+        assert!(recent.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!(config.get_project_access_info(project_id), (Some(recent[0]), 7));
+
+        config.remove_project(project_id).unwrap();
+        assert!(config.get_recent_accesses(project_id).is_empty());
+    }
+}
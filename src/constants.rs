@@ -13,16 +13,53 @@ pub const CONFIG_SUBDIR_NAME: &str = "pm";
 #[allow(dead_code)]
 pub const DEFAULT_WORKSPACE_DIR: &str = "~/workspace";
 pub const DEFAULT_RECENT_DAYS: i64 = 7;
+pub const DEFAULT_GITLAB_URL: &str = "https://gitlab.com";
+pub const DEFAULT_EDITOR: &str = "nano";
+/// Priority of projects added without --priority (range 1-5, 5 is most important)
+pub const DEFAULT_PROJECT_PRIORITY: u8 = 3;
+pub const MAX_PROJECT_PRIORITY: u8 = 5;
+/// Switch history entries kept per machine for `pm switch --back/--forward`
+pub const MAX_SWITCH_HISTORY: usize = 50;
+/// Access times kept per project and machine, shown by `pm info`
+pub const MAX_RECENT_ACCESSES: usize = 5;
+/// `pm info` stops counting project files here
+pub const PROJECT_FILE_COUNT_LIMIT: usize = 100_000;
+/// Entries shown by `pm switch --history`
+pub const SWITCH_HISTORY_DISPLAY_LIMIT: usize = 10;
+/// Bytes of README.md read by `pm add --detect-description`
+pub const README_DESCRIPTION_READ_BYTES: usize = 1024;
+/// Longest description `pm add --detect-description` stores
+pub const MAX_DETECTED_DESCRIPTION_CHARS: usize = 200;
+/// Largest config `pm init --from` downloads
+pub const MAX_REMOTE_CONFIG_BYTES: usize = 1024 * 1024;
 
 // Time constants
 pub const GIT_UPDATE_INTERVAL_HOURS: i64 = 1;
+/// How long `save_config` waits for another PM process to release the config lock
+pub const CONFIG_LOCK_TIMEOUT_SECS: u64 = 5;
+/// How long `is_git_working_tree_dirty` reuses a result for the same path
+pub const GIT_DIRTY_CACHE_SECS: u64 = 10;
+/// How long watch modes let a burst of filesystem events settle before reacting
+pub const WATCH_DEBOUNCE_MS: u64 = 200;
+/// Above this many projects `pm list --exists/--not-exists` warns about slow filesystems
+pub const PATH_EXISTS_WARNING_THRESHOLD: usize = 50;
 
 // Display constants
+/// Length of the short project ID shown by `pm list --show-id` (like a git short hash)
+pub const PROJECT_SHORT_ID_LENGTH: usize = 8;
+/// Shortest ID prefix `pm info --id` accepts
+pub const MIN_PROJECT_ID_PREFIX_LENGTH: usize = 4;
 pub const PROJECT_NAME_WIDTH: usize = 20;
 pub const PROJECT_PATH_WIDTH: usize = 40;
 pub const PROJECT_GIT_WIDTH: usize = 5;
 pub const PROJECT_TAGS_WIDTH: usize = 15;
 pub const PROJECT_TIME_WIDTH: usize = 15;
+pub const PROJECT_REMOTE_WIDTH: usize = 50;
+
+// Project color labels (hex codes like #ff6600 are accepted as well)
+pub const PROJECT_COLORS: &[&str] = &[
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
 
 // Error messages
 pub const ERROR_CONFIG_LOAD: &str = "Failed to load configuration";
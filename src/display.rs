@@ -1,6 +1,10 @@
 use crate::constants::*;
 use crate::Project;
 use chrono::{DateTime, Utc};
+use colored::{Color, ColoredString, Colorize};
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
 
 pub fn format_relative_time(time: DateTime<Utc>) -> String {
     let now = Utc::now();
@@ -53,7 +57,65 @@ pub fn format_relative_time(time: DateTime<Utc>) -> String {
     }
 }
 
-pub fn display_project_simple(project: &Project, access_time: Option<DateTime<Utc>>) {
+/// Parse a project color label (basic terminal color name or #rgb/#rrggbb hex)
+pub fn parse_project_color(color: &str) -> Option<Color> {
+    if let Some(hex) = color.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let hex = if hex.len() == 3 {
+            hex.chars().flat_map(|c| [c, c]).collect::<String>()
+        } else {
+            hex.to_string()
+        };
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::TrueColor {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+
+    if PROJECT_COLORS.contains(&color.to_lowercase().as_str()) {
+        color.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Project name rendered with its color label, if any
+pub fn colorize_project_name(project: &Project) -> ColoredString {
+    match project.color.as_deref().and_then(parse_project_color) {
+        Some(color) => project.name.color(color),
+        None => project.name.normal(),
+    }
+}
+
+/// Shorten `text` to at most `max_chars` characters, marking the cut with an ellipsis
+pub fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+pub fn display_project_simple(
+    project: &Project,
+    access_time: Option<DateTime<Utc>>,
+    show_id: bool,
+    show_remote_url: bool,
+    path_depth: Option<usize>,
+) {
+    let path_display = match path_depth {
+        Some(depth) => crate::utils::truncate_path_to_depth(&project.path, depth),
+        None => project.path.display().to_string(),
+    };
+
     let tags_display = if project.tags.is_empty() {
         "".to_string()
     } else {
@@ -78,14 +140,41 @@ pub fn display_project_simple(project: &Project, access_time: Option<DateTime<Ut
         "".to_string()
     };
 
+    let description_display = project
+        .description
+        .as_deref()
+        .and_then(|desc| desc.lines().next())
+        .map(|line| format!("  {}", line).dimmed().to_string())
+        .unwrap_or_default();
+
+    let remote_display = if show_remote_url {
+        let remote_url = project.remote_url.as_deref().unwrap_or("-");
+        format!(
+            " {:<width_remote$}",
+            truncate_with_ellipsis(remote_url, PROJECT_REMOTE_WIDTH),
+            width_remote = PROJECT_REMOTE_WIDTH
+        )
+    } else {
+        String::new()
+    };
+
+    let id_display = if show_id {
+        format!("{} ", short_project_id(project))
+    } else {
+        String::new()
+    };
+
     println!(
-        "{:<width_name$} {:<width_path$} {:<width_git$} {:<width_tags$} {:<width_time$}{}",
-        project.name,
-        project.path.display().to_string(),
+        "{}{:<width_name$} {:<width_path$} {:<width_git$} {:<width_tags$} {:<width_time$}{}{}{}",
+        id_display,
+        colorize_project_name(project),
+        path_display,
         git_status,
         tags_display,
         last_updated_display,
+        remote_display,
         access_display,
+        description_display,
         width_name = PROJECT_NAME_WIDTH,
         width_path = PROJECT_PATH_WIDTH,
         width_git = PROJECT_GIT_WIDTH,
@@ -99,14 +188,35 @@ pub fn display_project_detailed(
     access_time: Option<DateTime<Utc>>,
     access_count: u32,
 ) {
-    println!("\n{}", project.name);
+    println!("\n{}", colorize_project_name(project));
     if !project.tags.is_empty() {
         println!("  Tags: {}", project.tags.join(", "));
     }
     println!("  Path: {}", project.path.display());
+    if project.is_git_repository {
+        if let Some(branch) = crate::utils::get_git_branch_name(&project.path) {
+            let dirty = crate::utils::is_git_working_tree_dirty(&project.path).unwrap_or(false);
+            if dirty {
+                println!("  Branch: {} (uncommitted changes)", branch);
+            } else {
+                println!("  Branch: {}", branch);
+            }
+        }
+    }
+    if let Some(remote_url) = &project.remote_url {
+        println!("  Remote: {}", remote_url);
+    }
     if let Some(desc) = &project.description {
-        println!("  Description: {}", desc);
+        let mut lines = desc.lines();
+        println!("  Description: {}", lines.next().unwrap_or_default());
+        for line in lines {
+            println!("               {}", line);
+        }
+    }
+    if let Some(color) = &project.color {
+        println!("  Color: {}", color);
     }
+    println!("  Priority: {}", format_priority_stars(project.priority));
     println!("  ID: {}", project.id);
     println!(
         "  Created: {}",
@@ -135,16 +245,58 @@ pub fn display_project_detailed(
     }
 }
 
-pub fn display_project_list_header(count: usize) {
+/// Priority as filled and empty stars, e.g. `★★★☆☆`
+pub fn format_priority_stars(priority: u8) -> String {
+    let filled = priority.min(MAX_PROJECT_PRIORITY) as usize;
+    format!(
+        "{}{}",
+        "★".repeat(filled),
+        "☆".repeat(MAX_PROJECT_PRIORITY as usize - filled)
+    )
+}
+
+/// First characters of the project UUID, enough to identify it in a list
+pub fn short_project_id(project: &Project) -> String {
+    project.id.to_string()[..PROJECT_SHORT_ID_LENGTH].to_string()
+}
+
+pub fn display_project_list_header(count: usize, show_id: bool, show_remote_url: bool) {
     println!("📋 Active Projects ({} found)", count);
     println!();
+    display_project_list_columns(show_id, show_remote_url);
+}
+
+pub fn display_project_list_page_header(
+    offset: usize,
+    count: usize,
+    total: usize,
+    show_id: bool,
+    show_remote_url: bool,
+) {
+    if count == 0 {
+        println!("📋 Active Projects (none on this page, {} total)", total);
+    } else {
+        println!(
+            "📋 Active Projects (showing {}-{} of {})",
+            offset + 1,
+            offset + count,
+            total
+        );
+    }
+    println!();
+    display_project_list_columns(show_id, show_remote_url);
+}
+
+fn display_project_list_columns(show_id: bool, show_remote_url: bool) {
     println!(
-        "{:<width_name$} {:<width_path$} {:<width_git$} {:<width_tags$} {:<width_time$}",
+        "{}{:<width_name$} {:<width_path$} {:<width_git$} {:<width_tags$} {:<width_time$}{}",
+        if show_id { format!("{:<width_id$} ", "ID", width_id = PROJECT_SHORT_ID_LENGTH) } else { String::new() },
         "NAME",
         "PATH",
         "GIT",
         "TAGS",
         "TIME",
+        if show_remote_url { " REMOTE" } else { "" },
         width_name = PROJECT_NAME_WIDTH,
         width_path = PROJECT_PATH_WIDTH,
         width_git = PROJECT_GIT_WIDTH,
@@ -200,6 +352,135 @@ pub fn display_error(context: &str, error: &str) {
     eprintln!("❌ {}: {}", context, error);
 }
 
+/// Print a `PmError` with its recovery hint, if any
+pub fn display_pm_error(error: &crate::error::PmError) {
+    eprintln!("❌ {}", error.user_message());
+    if let Some(hint) = error.recovery_hint() {
+        eprintln!("💡 {}", hint);
+    }
+}
+
+/// Clear the screen and redraw `render`'s output whenever the file at `path` changes
+///
+/// Changes are detected through filesystem notifications on the parent directory, since
+/// the config is replaced by a rename rather than written in place. With `poll_interval`
+/// the file is also re-read on that interval, for filesystems without notification
+/// support (NFS, FUSE). Only content changes trigger a redraw. Returns on Ctrl-C.
+pub async fn watch_and_display<F, Fut>(
+    path: &Path,
+    poll_interval: Option<Duration>,
+    mut render: F,
+) -> anyhow::Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    use std::io::Write;
+
+    let file_name = path.file_name().map(|name| name.to_os_string());
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let mut last_content = None;
+
+    watch_for_changes(
+        Some(parent),
+        notify::RecursiveMode::NonRecursive,
+        move |changed| changed.file_name() == file_name.as_deref(),
+        poll_interval,
+        |_| {
+            let content = std::fs::read(path).ok();
+            let redraw = (content != last_content).then(&mut render);
+            last_content = content;
+            async move {
+                if let Some(redraw) = redraw {
+                    print!("\x1B[2J\x1B[1;1H");
+                    redraw.await?;
+                    println!("\n{}", "Watching for changes... (Ctrl-C to exit)".dimmed());
+                    std::io::stdout().flush()?;
+                }
+                Ok(())
+            }
+        },
+    )
+    .await
+}
+
+/// Call `on_wake` once, then again after every `poll_interval` and every burst of
+/// filesystem events under `path` that `is_relevant` accepts, until Ctrl-C
+///
+/// `on_wake` is told whether a file change woke it. Bursts (editor saves, git
+/// operations) are debounced by `WATCH_DEBOUNCE_MS`. Without a `path` only the interval
+/// applies; when notifications are unavailable the loop falls back to polling if
+/// `poll_interval` is set and fails otherwise.
+pub async fn watch_for_changes<R, F, Fut>(
+    path: Option<&Path>,
+    mode: notify::RecursiveMode,
+    is_relevant: R,
+    poll_interval: Option<Duration>,
+    mut on_wake: F,
+) -> anyhow::Result<()>
+where
+    R: Fn(&Path) -> bool + Send + 'static,
+    F: FnMut(bool) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    use notify::{EventKind, Watcher};
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let _watcher = match path {
+        Some(path) => {
+            let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    if !matches!(event.kind, EventKind::Access(_))
+                        && event.paths.iter().any(|p| is_relevant(p))
+                    {
+                        let _ = tx.send(());
+                    }
+                }
+            })
+            .and_then(|mut watcher| {
+                watcher.watch(path, mode)?;
+                Ok(watcher)
+            });
+            match watcher {
+                Ok(watcher) => Some(watcher),
+                Err(e) if poll_interval.is_some() => {
+                    display_warning(&format!("File notifications unavailable ({}); polling only", e));
+                    None
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        None => None,
+    };
+
+    // A single listener for the whole loop so a Ctrl-C pressed mid-redraw is not lost
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    let mut changed = false;
+    loop {
+        on_wake(changed).await?;
+
+        let poll = async {
+            match poll_interval {
+                Some(interval) => tokio::time::sleep(interval).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = poll => changed = false,
+            Some(()) = rx.recv() => {
+                tokio::time::sleep(Duration::from_millis(WATCH_DEBOUNCE_MS)).await;
+                while rx.try_recv().is_ok() {}
+                changed = true;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub fn display_warning(message: &str) {
     eprintln!("⚠️  {}", message);
 }
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::path::PathBuf;
 
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -18,6 +19,71 @@ pub enum PmError {
     ValidationFailed(String),
     GitOperationFailed,
     OperationCancelled,
+    /// A path component is longer than the filesystem limit
+    PathComponentTooLong(String),
+    PathContainsNul,
+    /// A path component consists only of whitespace
+    PathComponentBlank,
+    /// The path is a filesystem or drive root
+    PathIsRoot(String),
+    /// A path component contains non-ASCII characters while `settings.allow_unicode_paths` is off
+    PathNotAscii(String),
+    /// A tag violates `settings.tag_naming_policy`
+    InvalidTagName(String),
+    ExtensionNotFound {
+        name: String,
+    },
+    /// A registry could not be reached (after retries)
+    RegistryConnectionFailed {
+        url: String,
+        cause: String,
+    },
+    /// A backup file or the backup metadata could not be parsed
+    BackupCorrupted {
+        path: PathBuf,
+    },
+}
+
+impl PmError {
+    /// Message shown to the user, including any details carried by the variant
+    pub fn user_message(&self) -> String {
+        self.to_string()
+    }
+
+    /// What the user can do about the error, if there is a concrete next step
+    pub fn recovery_hint(&self) -> Option<&'static str> {
+        match self {
+            PmError::ConfigLoadFailed | PmError::InitializationFailed => {
+                Some("Run 'pm init' to create a configuration, or 'pm config validate' to check it")
+            }
+            PmError::ProjectNotFound => Some("Run 'pm ls' to see registered projects"),
+            PmError::ProjectPathNotFound => {
+                Some("Remove the project with 'pm rm' or re-add it from its new location")
+            }
+            PmError::DuplicateProject => Some("Use 'pm ls' to find the existing entry"),
+            PmError::NoProjectsFound => Some("Add a project with 'pm add <path>'"),
+            PmError::EditorLaunchFailed => {
+                Some("Set $VISUAL, $EDITOR or 'pm config set settings.editor <command>'")
+            }
+            PmError::PathComponentTooLong(_) | PmError::PathComponentBlank => {
+                Some("Rename the directory and try again")
+            }
+            PmError::PathNotAscii(_) => Some("Run 'pm config set settings.allow_unicode_paths true'"),
+            PmError::InvalidTagName(_) => {
+                Some("Check settings.tag_naming_policy with 'pm config get settings.tag_naming_policy'")
+            }
+            PmError::ExtensionNotFound { .. } => {
+                Some("Run 'pm ext list' to see installed extensions or 'pm ext search' to find one")
+            }
+            PmError::RegistryConnectionFailed { .. } => {
+                Some("Check your network connection and the registry URL with 'pm ext registry ping'")
+            }
+            PmError::BackupCorrupted { .. } => {
+                Some("Run 'pm backup list' and restore from a different backup")
+            }
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for PmError {
@@ -38,6 +104,28 @@ impl fmt::Display for PmError {
             PmError::ValidationFailed(msg) => write!(f, "Validation failed: {}", msg),
             PmError::GitOperationFailed => write!(f, "Git operation failed"),
             PmError::OperationCancelled => write!(f, "Operation cancelled by user"),
+            PmError::PathComponentTooLong(component) => write!(
+                f,
+                "Path component exceeds {} bytes: {}",
+                crate::validation::MAX_PATH_COMPONENT_BYTES,
+                component
+            ),
+            PmError::PathContainsNul => write!(f, "Path contains a NUL byte"),
+            PmError::PathComponentBlank => write!(f, "Path contains a component made only of whitespace"),
+            PmError::PathIsRoot(path) => write!(f, "Path is a filesystem root: {}", path),
+            PmError::PathNotAscii(component) => write!(
+                f,
+                "Path component contains non-ASCII characters: {} (settings.allow_unicode_paths is false)",
+                component
+            ),
+            PmError::InvalidTagName(msg) => write!(f, "Invalid tag name: {}", msg),
+            PmError::ExtensionNotFound { name } => write!(f, "Extension '{}' is not installed", name),
+            PmError::RegistryConnectionFailed { url, cause } => {
+                write!(f, "Could not connect to registry {}: {}", url, cause)
+            }
+            PmError::BackupCorrupted { path } => {
+                write!(f, "Backup is corrupted or unreadable: {}", path.display())
+            }
         }
     }
 }
@@ -45,20 +133,21 @@ impl fmt::Display for PmError {
 impl std::error::Error for PmError {}
 
 pub fn handle_error(error: anyhow::Error, context: &str) -> ! {
-    eprintln!("❌ {}: {}", context, error);
+    match error.downcast_ref::<PmError>() {
+        Some(pm_error) => crate::display::display_pm_error(pm_error),
+        None => crate::display::display_error(context, &format!("{:#}", error)),
+    }
     std::process::exit(1);
 }
 
 /// Handle inquire errors, specifically checking for interruption (Ctrl-C)
-/// Returns true if the operation should continue, false if cancelled
+///
+/// Escape and Ctrl-C both become `PmError::OperationCancelled`, which the binaries treat
+/// as a clean exit; other prompt errors are passed through.
 pub fn handle_inquire_error<T>(result: Result<T, inquire::InquireError>) -> anyhow::Result<T> {
     match result {
         Ok(value) => Ok(value),
-        Err(inquire::InquireError::OperationCanceled) => {
-            println!("\n💭 Operation cancelled");
-            Err(PmError::OperationCancelled.into())
-        }
-        Err(inquire::InquireError::OperationInterrupted) => {
+        Err(inquire::InquireError::OperationCanceled | inquire::InquireError::OperationInterrupted) => {
             println!("\n💭 Operation cancelled");
             Err(PmError::OperationCancelled.into())
         }
@@ -66,6 +155,20 @@ pub fn handle_inquire_error<T>(result: Result<T, inquire::InquireError>) -> anyh
     }
 }
 
+/// Whether `error` comes from the user cancelling a prompt
+///
+/// Also recognizes inquire cancellations that reached the top level without going
+/// through `handle_inquire_error`.
+pub fn is_cancellation(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        matches!(cause.downcast_ref::<PmError>(), Some(PmError::OperationCancelled))
+            || matches!(
+                cause.downcast_ref::<inquire::InquireError>(),
+                Some(inquire::InquireError::OperationCanceled | inquire::InquireError::OperationInterrupted)
+            )
+    })
+}
+
 #[allow(dead_code)]
 pub trait ErrorContext<T> {
     fn with_pm_context(self, context: &str) -> Result<T, anyhow::Error>;
@@ -79,3 +182,41 @@ where
         self.map_err(|e| anyhow::Error::new(e).context(context.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_is_cancellation() {
+        let cancelled = handle_inquire_error::<()>(Err(inquire::InquireError::OperationInterrupted)).unwrap_err();
+        assert!(is_cancellation(&cancelled));
+
+        let raw: anyhow::Result<()> = Err(inquire::InquireError::OperationCanceled.into());
+        assert!(is_cancellation(&raw.context("Failed to select backup").unwrap_err()));
+
+        let other = handle_inquire_error::<()>(Err(inquire::InquireError::NotTTY)).unwrap_err();
+        assert!(!is_cancellation(&other));
+        assert!(!is_cancellation(&PmError::ProjectNotFound.into()));
+    }
+
+    #[test]
+    fn test_recovery_hints_use_valid_config_keys() {
+        let errors = [
+            PmError::EditorLaunchFailed,
+            PmError::PathNotAscii("ü".to_string()),
+            PmError::InvalidTagName("A".to_string()),
+        ];
+        for error in errors {
+            let hint = error.recovery_hint().unwrap();
+            let key = hint
+                .split("pm config ")
+                .nth(1)
+                .and_then(|command| command.split_whitespace().nth(1))
+                .map(|key| key.trim_end_matches('\''))
+                .unwrap();
+            assert!(crate::commands::config::VALID_KEYS.contains(&key), "invalid key in hint: {}", hint);
+        }
+    }
+}
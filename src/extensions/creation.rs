@@ -2,7 +2,7 @@ use crate::extensions::{ExtensionManifest, ExtensionCommand};
 use crate::ExtensionType;
 use anyhow::{Context, Result};
 use chrono::Datelike;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
 /// Context for extension template generation
@@ -18,6 +18,8 @@ pub struct TemplateContext {
 }
 
 /// Create a new extension with interactive prompts for missing information
+///
+/// Returns the created directory, or None if the user cancelled.
 pub async fn create_extension(
     name: String,
     ext_type: Option<ExtensionType>,
@@ -25,13 +27,27 @@ pub async fn create_extension(
     description: Option<String>,
     author: Option<String>,
     non_interactive: bool,
-) -> Result<()> {
+    from_template: Option<String>,
+) -> Result<Option<PathBuf>> {
     println!("🚀 Creating new PM extension...");
     println!();
 
     // Validate extension name
     validate_extension_name(&name)?;
 
+    if let Some(url) = from_template {
+        return super::templates::create_from_template(super::templates::RemoteTemplateOptions {
+            url,
+            name,
+            directory,
+            description,
+            author,
+            non_interactive,
+        })
+        .await
+        .map(Some);
+    }
+
     // Build template context with interactive prompts or defaults
     let context = if non_interactive {
         build_context_with_defaults(name, ext_type, directory, description, author).await?
@@ -45,7 +61,7 @@ pub async fn create_extension(
     // Confirm creation (unless non-interactive)
     if !non_interactive && !confirm_creation()? {
         println!("❌ Extension creation cancelled.");
-        return Ok(());
+        return Ok(None);
     }
 
     // Create extension directory structure
@@ -54,9 +70,234 @@ pub async fn create_extension(
     // Display success message and next steps
     display_success_message(&context);
 
+    Ok(Some(context.directory))
+}
+
+/// Create a GitHub repository for a new extension and push its initial commit
+///
+/// Authenticates through the GitHub CLI (`gh auth login`).
+#[cfg(feature = "create-github-repo")]
+pub async fn create_github_repository(directory: &std::path::Path, name: &str, private: bool) -> Result<String> {
+    crate::commands::project::ensure_github_cli().await?;
+    let token = crate::commands::project::get_gh_token()
+        .await
+        .context("Failed to read GitHub token from 'gh auth token'")?;
+    let octocrab = octocrab::Octocrab::builder().personal_token(token).build()?;
+
+    let description = ExtensionManifest::load_from_file(&directory.join("extension.yml"))
+        .await
+        .map(|manifest| manifest.description)
+        .unwrap_or_default();
+
+    println!();
+    println!("📡 Creating GitHub repository '{}'...", name);
+    let body = serde_json::json!({
+        "name": name,
+        "description": description,
+        "private": private,
+    });
+    let repo: octocrab::models::Repository = octocrab
+        .post("/user/repos", Some(&body))
+        .await
+        .context("Failed to create GitHub repository")?;
+
+    let repo_url = repo
+        .html_url
+        .as_ref()
+        .map(|url| url.to_string())
+        .unwrap_or_else(|| format!("https://github.com/{}", repo.full_name.clone().unwrap_or_default()));
+    let clone_url = repo
+        .clone_url
+        .as_ref()
+        .map(|url| url.to_string())
+        .context("GitHub did not return a clone URL")?;
+
+    let git = |args: &[&str]| -> Result<()> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(directory)
+            .args(args)
+            .output()
+            .context("Failed to execute git")?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    };
+
+    if !directory.join(".git").exists() {
+        git(&["init"])?;
+    }
+    git(&["add", "-A"])?;
+    git(&["commit", "-m", "Initial commit"])?;
+    git(&["remote", "add", "origin", &clone_url])?;
+
+    println!("📤 Pushing initial commit...");
+    if let Err(e) = git(&["push", "-u", "origin", "HEAD"]) {
+        crate::display::display_warning(&format!("Repository created but push failed: {}", e));
+        println!("💡 Push manually: cd {} && git push -u origin HEAD", directory.display());
+    }
+
+    println!("✅ GitHub repository: {}", repo_url);
+    Ok(repo_url)
+}
+
+/// Add a command to an existing extension: register it in extension.yml and scaffold a stub
+pub async fn add_command(directory: &Path, command_name: &str, description: Option<String>) -> Result<()> {
+    validate_command_name(command_name)?;
+
+    let manifest_path = directory.join("extension.yml");
+    if !manifest_path.exists() {
+        return Err(anyhow::anyhow!(
+            "No extension.yml found in {}",
+            directory.display()
+        ));
+    }
+    let mut manifest = ExtensionManifest::load_from_file(&manifest_path).await?;
+
+    if manifest.commands.iter().any(|command| {
+        command.name == command_name
+            || command.aliases.iter().flatten().any(|alias| alias == command_name)
+    }) {
+        return Err(anyhow::anyhow!(
+            "Command '{}' already exists in extension '{}'",
+            command_name,
+            manifest.name
+        ));
+    }
+
+    let ext_type = detect_extension_type(directory).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Could not detect extension type in {} (expected bash/, python/, deno/ or Cargo.toml)",
+            directory.display()
+        )
+    })?;
+
+    let help = description.unwrap_or_else(|| format!("{} command", command_name));
+    let stub_path = generate_command_stub(directory, ext_type, &manifest.name, command_name, &help).await?;
+
+    manifest.commands.push(ExtensionCommand {
+        name: command_name.to_string(),
+        help,
+        aliases: None,
+        args: None,
+    });
+    manifest.save_to_file(&manifest_path).await?;
+
+    println!("✅ Added command '{}' to extension '{}'", command_name, manifest.name);
+    println!("📝 Files updated:");
+    println!("  - extension.yml");
+    println!("  - {}", stub_path.strip_prefix(directory).unwrap_or(&stub_path).display());
+    println!();
+    println!("🎯 Next steps:");
+    match ext_type {
+        ExtensionType::Bash | ExtensionType::Python | ExtensionType::Deno => {
+            println!("  1. # Edit {} to implement the command", stub_path.display());
+        }
+        ExtensionType::Rust => {
+            let module = rust_module_name(command_name);
+            println!("  1. # Add `mod {};` to src/main.rs and dispatch \"{}\" to {}::run", module, command_name, module);
+            println!("  2. # Edit {} to implement the command", stub_path.display());
+        }
+    }
+    println!("  {}. pm ext install . --local --force  # Reinstall to pick up the new command",
+             match ext_type { ExtensionType::Rust => 3, _ => 2 });
+
+    Ok(())
+}
+
+/// Detect the extension type of a source directory from its layout
+fn detect_extension_type(directory: &Path) -> Option<ExtensionType> {
+    if directory.join("Cargo.toml").exists() {
+        Some(ExtensionType::Rust)
+    } else if directory.join("deno").is_dir() {
+        Some(ExtensionType::Deno)
+    } else if directory.join("python").is_dir() {
+        Some(ExtensionType::Python)
+    } else if directory.join("bash").is_dir() {
+        Some(ExtensionType::Bash)
+    } else {
+        None
+    }
+}
+
+/// Validate command name format
+fn validate_command_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(anyhow::anyhow!("Command name cannot be empty"));
+    }
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(anyhow::anyhow!(
+            "Command name can only contain alphanumeric characters, hyphens, and underscores"
+        ));
+    }
+
+    if name.starts_with('-') || name.ends_with('-') {
+        return Err(anyhow::anyhow!("Command name cannot start or end with a hyphen"));
+    }
+
     Ok(())
 }
 
+/// Rust module name for a command (hyphens are not valid in identifiers)
+fn rust_module_name(command_name: &str) -> String {
+    command_name.replace('-', "_")
+}
+
+/// Write the stub script for a new command, returning its path
+async fn generate_command_stub(
+    directory: &Path,
+    ext_type: ExtensionType,
+    extension_name: &str,
+    command_name: &str,
+    help: &str,
+) -> Result<PathBuf> {
+    let (stub_path, content) = match ext_type {
+        ExtensionType::Bash => (
+            directory.join("bash").join(format!("{}.sh", command_name)),
+            generate_bash_command_stub(extension_name, command_name, help),
+        ),
+        ExtensionType::Python => (
+            directory.join("python").join(format!("{}.py", command_name)),
+            generate_python_command_stub(extension_name, command_name, help),
+        ),
+        ExtensionType::Rust => (
+            directory.join("src").join(format!("{}.rs", rust_module_name(command_name))),
+            generate_rust_command_stub(command_name, help),
+        ),
+        ExtensionType::Deno => (
+            directory.join("deno").join(format!("{}.ts", command_name)),
+            generate_deno_command_stub(extension_name, command_name, help),
+        ),
+    };
+
+    if stub_path.exists() {
+        return Err(anyhow::anyhow!("File already exists: {}", stub_path.display()));
+    }
+    if let Some(parent) = stub_path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(&stub_path, content).await
+        .with_context(|| format!("Failed to write file: {}", stub_path.display()))?;
+
+    // Set executable permissions on Unix
+    #[cfg(unix)]
+    if matches!(ext_type, ExtensionType::Bash) {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&stub_path).await?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&stub_path, perms).await?;
+    }
+
+    Ok(stub_path)
+}
+
 /// Validate extension name format
 fn validate_extension_name(name: &str) -> Result<()> {
     if name.is_empty() {
@@ -159,7 +400,8 @@ fn prompt_extension_type() -> Result<ExtensionType> {
     println!("  1. bash   - Shell scripts (fast, simple)");
     println!("  2. python - Python scripts (versatile, rich ecosystem)");
     println!("  3. rust   - Rust binary (fast, safe, compiled)");
-    print!("Choose type [1-3] (1): ");
+    println!("  4. deno   - TypeScript on Deno (typed, secure by default)");
+    print!("Choose type [1-4] (1): ");
     
     let input = read_user_input()?;
     let input = input.trim();
@@ -168,6 +410,7 @@ fn prompt_extension_type() -> Result<ExtensionType> {
         "" | "1" | "bash" => Ok(ExtensionType::Bash),
         "2" | "python" => Ok(ExtensionType::Python),
         "3" | "rust" => Ok(ExtensionType::Rust),
+        "4" | "deno" => Ok(ExtensionType::Deno),
         _ => {
             println!("Invalid choice. Using bash as default.");
             Ok(ExtensionType::Bash)
@@ -224,7 +467,7 @@ fn prompt_directory(name: &str) -> Result<PathBuf> {
 }
 
 /// Get git user name
-fn get_git_name() -> Option<String> {
+pub(super) fn get_git_name() -> Option<String> {
     std::process::Command::new("git")
         .args(&["config", "user.name"])
         .output()
@@ -259,7 +502,7 @@ async fn get_git_email() -> Option<String> {
 }
 
 /// Read user input from stdin
-fn read_user_input() -> Result<String> {
+pub(super) fn read_user_input() -> Result<String> {
     use std::io::{self, Write};
     
     io::stdout().flush()?;
@@ -316,6 +559,7 @@ async fn create_extension_structure(context: &TemplateContext) -> Result<()> {
         ExtensionType::Bash => generate_bash_extension(context).await?,
         ExtensionType::Python => generate_python_extension(context).await?,
         ExtensionType::Rust => generate_rust_extension(context).await?,
+        ExtensionType::Deno => create_deno_extension(context).await?,
     }
 
     // Generate common files
@@ -350,7 +594,7 @@ async fn generate_manifest(context: &TemplateContext) -> Result<()> {
                 args: None,
             },
         ],
-        ExtensionType::Rust => vec![
+        ExtensionType::Rust | ExtensionType::Deno => vec![
             ExtensionCommand {
                 name: "run".to_string(),
                 help: "Run the main functionality".to_string(),
@@ -374,6 +618,8 @@ async fn generate_manifest(context: &TemplateContext) -> Result<()> {
         homepage: None,
         pm_version: Some(">=0.1.0".to_string()),
         commands,
+        sandbox: None,
+        hooks: Default::default(),
     };
 
     let manifest_path = context.directory.join("extension.yml");
@@ -435,6 +681,27 @@ async fn generate_rust_extension(context: &TemplateContext) -> Result<()> {
     Ok(())
 }
 
+/// Generate deno extension files: deno.json, deno/main.ts and a CI workflow
+async fn create_deno_extension(context: &TemplateContext) -> Result<()> {
+    let deno_dir = context.directory.join("deno");
+    fs::create_dir_all(&deno_dir).await?;
+
+    let deno_json = generate_deno_json(context);
+    let deno_json_path = context.directory.join("deno.json");
+    fs::write(&deno_json_path, deno_json).await?;
+
+    let main_ts = generate_deno_main_script(context);
+    let main_path = deno_dir.join("main.ts");
+    fs::write(&main_path, main_ts).await?;
+
+    let workflows_dir = context.directory.join(".github").join("workflows");
+    fs::create_dir_all(&workflows_dir).await?;
+    let workflow_path = workflows_dir.join("ci.yml");
+    fs::write(&workflow_path, generate_deno_workflow()).await?;
+
+    Ok(())
+}
+
 /// Generate README.md
 async fn generate_readme(context: &TemplateContext) -> Result<()> {
     let readme_content = format!(r#"# {}
@@ -473,11 +740,12 @@ MIT License - see LICENSE file for details.
             ExtensionType::Bash => "This is a Bash extension. Edit `bash/example.sh` to implement your functionality.",
             ExtensionType::Python => "This is a Python extension. Edit `python/main.py` to implement your functionality.\n\nInstall dependencies:\n```bash\npip install -r requirements.txt\n```",
             ExtensionType::Rust => "This is a Rust extension. Edit `src/main.rs` to implement your functionality.\n\nBuild the extension:\n```bash\ncargo build --release\n```",
+            ExtensionType::Deno => "This is a Deno extension. Edit `deno/main.ts` to implement your functionality.\n\nRun it without installing:\n```bash\ndeno task dev run\n```",
         },
         match context.ext_type {
             ExtensionType::Bash => "- `example` - Example command",
             ExtensionType::Python => "- `run` - Run the main functionality\n- `help` - Show help information",
-            ExtensionType::Rust => "- `run` - Run the main functionality\n- `version` - Show version information",
+            ExtensionType::Rust | ExtensionType::Deno => "- `run` - Run the main functionality\n- `version` - Show version information",
         }
     );
 
@@ -716,6 +984,166 @@ fn show_help() {{
 "#, context.name, context.version, context.author, context.description, context.name, context.name, context.name, context.name, context.description)
 }
 
+/// Generate deno.json for Deno extension
+fn generate_deno_json(context: &TemplateContext) -> String {
+    format!(r#"{{
+  "name": "@pm/{}",
+  "version": "{}",
+  "exports": "./deno/main.ts",
+  "tasks": {{
+    "dev": "deno run --allow-env --allow-read deno/main.ts",
+    "check": "deno check deno/main.ts"
+  }}
+}}
+"#, context.name, context.version)
+}
+
+/// Generate Deno main.ts using Cliffy
+fn generate_deno_main_script(context: &TemplateContext) -> String {
+    format!(r#"// {} - A Deno extension for PM
+// {}
+
+import {{ Command }} from "jsr:@cliffy/command@^1.0.0-rc.7";
+
+function runMainFunctionality(): void {{
+  console.log("🦕 {} Extension - Run Command");
+  console.log("🔧 Deno extension is working correctly!");
+
+  // Access PM environment variables
+  console.log();
+  console.log("📍 PM Environment:");
+  console.log(`  Config: ${{Deno.env.get("PM_CONFIG_PATH") ?? "Not set"}}`);
+  console.log(`  Version: ${{Deno.env.get("PM_VERSION") ?? "Not set"}}`);
+  console.log(`  Extension Dir: ${{Deno.env.get("PM_EXTENSION_DIR") ?? "Not set"}}`);
+
+  // Parse current project info
+  const projectJson = Deno.env.get("PM_CURRENT_PROJECT") ?? "{{}}";
+  try {{
+    const project = JSON.parse(projectJson);
+    if (project.name) {{
+      console.log(`  Project: ${{project.name}}`);
+    }}
+    if (project.path) {{
+      console.log(`  Path: ${{project.path}}`);
+    }}
+  }} catch {{
+    console.log(`  Project: ${{projectJson}}`);
+  }}
+}}
+
+await new Command()
+  .name("{}")
+  .version("{}")
+  .description("{}")
+  .action(function () {{
+    this.showHelp();
+  }})
+  .command("run", "Run the main functionality")
+  .action(() => runMainFunctionality())
+  .command("version", "Show version information")
+  .alias("v")
+  .action(() => console.log("{} v{}"))
+  .parse(Deno.args);
+"#, context.name, context.description, context.name, context.name, context.version, context.description, context.name, context.version)
+}
+
+/// Generate GitHub Actions workflow for Deno extension
+fn generate_deno_workflow() -> String {
+    r#"name: CI
+
+on:
+  push:
+  pull_request:
+
+jobs:
+  check:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: denoland/setup-deno@v2
+        with:
+          deno-version: v2.x
+      - run: deno fmt --check
+      - run: deno lint
+      - run: deno check deno/*.ts
+"#
+    .to_string()
+}
+
+/// Generate bash stub for an added command
+fn generate_bash_command_stub(extension_name: &str, command_name: &str, help: &str) -> String {
+    format!(r#"#!/bin/bash
+# {} - {} command
+# {}
+
+set -euo pipefail
+
+# Get command name and arguments
+COMMAND="$1"
+shift
+
+# TODO: implement the {} command
+echo "⚠️  pm {} {} is not implemented yet"
+echo "📦 Arguments: $*"
+exit 1
+"#, extension_name, command_name, help, command_name, extension_name, command_name)
+}
+
+/// Generate python stub for an added command
+fn generate_python_command_stub(extension_name: &str, command_name: &str, help: &str) -> String {
+    format!(r#"#!/usr/bin/env python3
+"""
+{} - {} command
+{}
+"""
+
+import sys
+
+
+def main(args):
+    # TODO: implement the {} command
+    print("⚠️  pm {} {} is not implemented yet")
+    print(f"📦 Arguments: {{' '.join(args)}}")
+    return 1
+
+
+if __name__ == "__main__":
+    # argv[1] is the command name
+    sys.exit(main(sys.argv[2:]))
+"#, extension_name, command_name, help, command_name, extension_name, command_name)
+}
+
+/// Generate rust module stub for an added command
+fn generate_rust_command_stub(command_name: &str, help: &str) -> String {
+    format!(r#"//! {} command
+//! {}
+
+use anyhow::Result;
+
+pub fn run(args: &[String]) -> Result<()> {{
+    // TODO: implement the {} command
+    println!("⚠️  {} is not implemented yet");
+    println!("📦 Arguments: {{}}", args.join(" "));
+    Ok(())
+}}
+"#, command_name, help, command_name, command_name)
+}
+
+/// Generate deno stub for an added command
+fn generate_deno_command_stub(extension_name: &str, command_name: &str, help: &str) -> String {
+    format!(r#"// {} - {} command
+// {}
+
+// Deno.args[0] is the command name
+const args = Deno.args.slice(1);
+
+// TODO: implement the {} command
+console.log("⚠️  pm {} {} is not implemented yet");
+console.log(`📦 Arguments: ${{args.join(" ")}}`);
+Deno.exit(1);
+"#, extension_name, command_name, help, command_name, extension_name, command_name)
+}
+
 /// Display success message and next steps
 fn display_success_message(context: &TemplateContext) {
     println!("✅ Extension '{}' created successfully!", context.name);
@@ -736,6 +1164,11 @@ fn display_success_message(context: &TemplateContext) {
             println!("  - Cargo.toml");
             println!("  - src/main.rs");
         },
+        ExtensionType::Deno => {
+            println!("  - deno.json");
+            println!("  - deno/main.ts");
+            println!("  - .github/workflows/ci.yml");
+        },
     }
     
     println!("  - README.md");
@@ -756,10 +1189,65 @@ fn display_success_message(context: &TemplateContext) {
             println!("  2. cargo build --release           # Build your extension");
             println!("  3. # Edit src/main.rs to implement your functionality");
         },
+        ExtensionType::Deno => {
+            println!("  2. # Edit deno/main.ts to implement your functionality");
+        },
     }
     
     println!("  {}. pm ext install . --local         # Install locally for testing", 
              match context.ext_type { ExtensionType::Rust => 4, _ => 3 });
     println!("  {}. pm {} run                     # Test your extension", 
              match context.ext_type { ExtensionType::Rust => 5, _ => 4 }, context.name);
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_add_command_scaffolds_stub_and_rejects_duplicates() {
+        let temp = tempfile::tempdir().unwrap();
+        let context = build_context_with_defaults(
+            "demo".to_string(),
+            Some(ExtensionType::Bash),
+            Some(temp.path().join("demo")),
+            None,
+            Some("Tester".to_string()),
+        ).await.unwrap();
+        create_extension_structure(&context).await.unwrap();
+
+        add_command(&context.directory, "deploy", Some("Deploy things".to_string())).await.unwrap();
+
+        let manifest = ExtensionManifest::load_from_file(&context.directory.join("extension.yml")).await.unwrap();
+        let added = manifest.commands.iter().find(|command| command.name == "deploy").unwrap();
+        assert_eq!(added.help, "Deploy things");
+        assert!(context.directory.join("bash/deploy.sh").exists());
+
+        assert!(add_command(&context.directory, "deploy", None).await.is_err());
+        assert!(add_command(&context.directory, "example", None).await.is_err());
+        assert!(add_command(&context.directory, "-bad", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deno_extension_scaffold() {
+        let temp = tempfile::tempdir().unwrap();
+        let context = build_context_with_defaults(
+            "denodemo".to_string(),
+            Some(ExtensionType::Deno),
+            Some(temp.path().join("denodemo")),
+            None,
+            Some("Tester".to_string()),
+        ).await.unwrap();
+        create_extension_structure(&context).await.unwrap();
+
+        let deno_json: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(context.directory.join("deno.json")).unwrap(),
+        ).unwrap();
+        assert_eq!(deno_json["name"], "@pm/denodemo");
+        assert!(std::fs::read_to_string(context.directory.join("deno/main.ts")).unwrap().contains("@cliffy/command"));
+        assert!(std::fs::read_to_string(context.directory.join(".github/workflows/ci.yml")).unwrap().contains("denoland/setup-deno"));
+
+        assert!(matches!(detect_extension_type(&context.directory), Some(ExtensionType::Deno)));
+        add_command(&context.directory, "deploy", None).await.unwrap();
+        assert!(context.directory.join("deno/deploy.ts").exists());
+    }
+}
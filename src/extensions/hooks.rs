@@ -0,0 +1,204 @@
+use crate::extensions::{get_extensions_dir, ExtensionManifest};
+use crate::Project;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+/// Events extensions can subscribe to through the `hooks` map in `manifest.yml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// PM started handling a command
+    PmStart,
+    /// A project is about to be switched to
+    PreSwitch,
+    /// A project was switched to
+    ProjectSwitch,
+    /// A project was added
+    ProjectAdd,
+}
+
+impl HookEvent {
+    /// Event name as used in `manifest.yml`
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::PmStart => "on_pm_start",
+            HookEvent::PreSwitch => "pre_switch",
+            HookEvent::ProjectSwitch => "on_project_switch",
+            HookEvent::ProjectAdd => "on_project_add",
+        }
+    }
+
+    /// Blocking hooks finish before PM continues; the rest run in the background
+    pub fn is_blocking(&self) -> bool {
+        matches!(self, HookEvent::PreSwitch)
+    }
+}
+
+/// Environment passed to hook scripts
+#[derive(Debug, Default, Clone)]
+pub struct HookContext {
+    vars: Vec<(String, String)>,
+}
+
+impl HookContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Context describing a project (PM_PROJECT_ID, _NAME, _PATH, _TAGS)
+    pub fn for_project(project: &Project) -> Self {
+        Self::new()
+            .with("PM_PROJECT_ID", project.id.to_string())
+            .with("PM_PROJECT_NAME", &project.name)
+            .with("PM_PROJECT_PATH", project.path.display().to_string())
+            .with("PM_PROJECT_TAGS", project.tags.join(","))
+    }
+
+    pub fn with(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.vars.push((key.to_string(), value.into()));
+        self
+    }
+}
+
+/// A hook registered by an extension: (extension name, extension directory, script as written)
+type RegisteredHook = (String, PathBuf, String);
+
+/// Hooks registered for `event` by the extensions installed in `extensions_dir`
+///
+/// Only manifests are read, so commands pay nothing beyond a directory listing when
+/// no extension registers hooks.
+async fn find_hooks(extensions_dir: &Path, event: HookEvent) -> Result<Vec<RegisteredHook>> {
+    let mut hooks = Vec::new();
+    if !extensions_dir.exists() {
+        return Ok(hooks);
+    }
+
+    let mut entries = tokio::fs::read_dir(extensions_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let extension_dir = entry.path();
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let Ok(manifest) = ExtensionManifest::load_from_file(&extension_dir.join("manifest.yml")).await else {
+            continue;
+        };
+        if let Some(script) = manifest.hooks.get(event.name()) {
+            hooks.push((name, extension_dir, script.clone()));
+        }
+    }
+
+    hooks.sort();
+    Ok(hooks)
+}
+
+/// Resolve a manifest's hook `script` inside `extension_dir`
+///
+/// Absolute paths and `..` components that lead outside the extension directory
+/// (directly or through symlinks) are rejected.
+fn resolve_hook_script(extension_dir: &Path, script: &str) -> Result<PathBuf> {
+    let extension_dir = extension_dir
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("extension directory not found: {}", e))?;
+    let path = extension_dir
+        .join(script)
+        .canonicalize()
+        .map_err(|_| anyhow::anyhow!("script not found: {}", script))?;
+    if !path.starts_with(&extension_dir) {
+        return Err(anyhow::anyhow!("script is outside the extension directory: {}", script));
+    }
+    Ok(path)
+}
+
+/// Run every installed extension's hook for `event`
+///
+/// Failures are reported as warnings and never abort the calling command.
+pub async fn dispatch_hook(event: HookEvent, context: &HookContext) {
+    let hooks = match get_extensions_dir() {
+        Ok(extensions_dir) => find_hooks(&extensions_dir, event).await,
+        Err(e) => Err(e),
+    };
+    let hooks = match hooks {
+        Ok(hooks) => hooks,
+        Err(e) => {
+            crate::display::display_warning(&format!("Failed to look up {} hooks: {}", event.name(), e));
+            return;
+        }
+    };
+
+    for (extension_name, extension_dir, script) in hooks {
+        let result = match resolve_hook_script(&extension_dir, &script) {
+            Ok(script) => run_hook(event, &extension_name, &script, context).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            crate::display::display_warning(&format!(
+                "Hook {} from extension '{}' failed: {}",
+                event.name(),
+                extension_name,
+                e
+            ));
+        }
+    }
+}
+
+async fn run_hook(event: HookEvent, extension_name: &str, script: &Path, context: &HookContext) -> Result<()> {
+    let mut cmd = crate::extensions::manager::extension_command(extension_name, script).await?;
+    cmd.command_mut()
+        .env("PM_HOOK_EVENT", event.name())
+        .envs(context.vars.iter().map(|(key, value)| (key, value)));
+
+    if event.is_blocking() {
+        // Hook output goes to stderr so it cannot be mistaken for PM output parsed by the shell
+        cmd.command_mut().stdout(std::io::stderr());
+        let status = cmd.status()?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("exited with {}", status));
+        }
+    } else {
+        cmd.command_mut().stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+        cmd.spawn()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_hook_script_stays_in_extension_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let extension_dir = temp.path().join("ext");
+        std::fs::create_dir_all(extension_dir.join("hooks")).unwrap();
+        std::fs::write(extension_dir.join("hooks/switch.sh"), "#!/bin/sh\n").unwrap();
+        std::fs::write(temp.path().join("outside.sh"), "#!/bin/sh\n").unwrap();
+        std::os::unix::fs::symlink(temp.path().join("outside.sh"), extension_dir.join("link.sh")).unwrap();
+
+        assert!(resolve_hook_script(&extension_dir, "hooks/switch.sh").is_ok());
+        assert!(resolve_hook_script(&extension_dir, "hooks/../hooks/switch.sh").is_ok());
+        assert!(resolve_hook_script(&extension_dir, "../outside.sh").is_err());
+        assert!(resolve_hook_script(&extension_dir, "/bin/sh").is_err());
+        assert!(resolve_hook_script(&extension_dir, "link.sh").is_err());
+        assert!(resolve_hook_script(&extension_dir, "hooks/missing.sh").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_find_hooks_reads_manifests() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(find_hooks(&temp.path().join("missing"), HookEvent::PmStart).await.unwrap().is_empty());
+
+        let extension_dir = temp.path().join("notify");
+        std::fs::create_dir_all(&extension_dir).unwrap();
+        std::fs::write(
+            extension_dir.join("manifest.yml"),
+            "name: notify\nversion: 0.1.0\ndescription: test\ncommands:\n  - name: run\n    help: Run\nhooks:\n  on_project_switch: hooks/switch.sh\n",
+        )
+        .unwrap();
+
+        let hooks = find_hooks(temp.path(), HookEvent::ProjectSwitch).await.unwrap();
+        assert_eq!(hooks, vec![("notify".to_string(), extension_dir, "hooks/switch.sh".to_string())]);
+        assert!(find_hooks(temp.path(), HookEvent::PmStart).await.unwrap().is_empty());
+    }
+}
@@ -1,13 +1,21 @@
 use crate::extensions::{
-    discovery, ensure_extensions_dir, find_extension_binary, 
-    get_extension_dir, ExtensionManifest, creation, remote, remote_install
+    discovery, ensure_extensions_dir, find_extension_binary, get_extensions_dir,
+    get_extension_dir, ExtensionInfo, ExtensionManifest, creation, registry, remote, remote_install, sandbox
 };
+use crate::error::PmError;
+use crate::utils::format_number;
 use crate::{ExtensionAction, RegistryAction};
 use anyhow::{Context, Result};
-use std::path::Path;
-use std::process::Command;
+use chrono::{DateTime, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
+/// How long registry version lookups are reused before querying again
+const UPDATE_CHECK_CACHE_TTL_HOURS: i64 = 1;
+
 /// Extension manager for handling extension operations
 pub struct ExtensionManager;
 
@@ -21,33 +29,69 @@ impl ExtensionManager {
 /// Handle extension management commands
 pub async fn handle_extension_command(action: &ExtensionAction) -> Result<()> {
     match action {
-        ExtensionAction::Create { name, ext_type, directory, description, author, non_interactive } => {
-            creation::create_extension(
+        ExtensionAction::Create { name, ext_type, directory, description, author, non_interactive, from_template, publish, private } => {
+            if *publish && !cfg!(feature = "create-github-repo") {
+                return Err(anyhow::anyhow!(
+                    "--publish requires PM to be built with the 'create-github-repo' feature \
+                     (cargo install --features create-github-repo)"
+                ));
+            }
+
+            let created = creation::create_extension(
                 name.clone(),
                 *ext_type,
                 directory.clone(),
                 description.clone(),
                 author.clone(),
                 *non_interactive,
-            ).await
+                from_template.clone(),
+            ).await?;
+
+            match created {
+                Some(directory) if *publish => publish_extension(&directory, name, *private).await,
+                _ => Ok(()),
+            }
         }
-        ExtensionAction::Install { name, source, version, local, registry, force } => {
-            handle_install(name, source.as_deref(), version.as_deref(), *local, registry.as_deref(), *force).await
+        ExtensionAction::AddCommand { extension, command_name, description } => {
+            creation::add_command(extension, command_name, description.clone()).await
+        }
+        ExtensionAction::Install { name, source, version, local, registry, force, no_retry } => {
+            handle_install(name, source.as_deref(), version.as_deref(), *local, registry.as_deref(), *force, !*no_retry).await
         }
         ExtensionAction::Uninstall { name, force } => {
             handle_uninstall(name, *force).await
         }
-        ExtensionAction::List { all } => {
-            handle_list(*all).await
+        ExtensionAction::List {
+            all,
+            outdated,
+            format,
+            verbose,
+        } => {
+            if *outdated {
+                handle_list_outdated().await
+            } else {
+                handle_list(*all, format, *verbose).await
+            }
         }
-        ExtensionAction::Info { name } => {
-            handle_info(name).await
+        ExtensionAction::Info { name, format } => {
+            handle_info(name, format).await
         }
-        ExtensionAction::Update { name } => {
-            handle_update(name.as_deref()).await
+        ExtensionAction::Update { name, all } => {
+            // `None` updates every installed extension
+            let name = match (name.as_deref(), all) {
+                (_, true) => None,
+                (Some(name), false) => Some(name),
+                (None, false) => anyhow::bail!("Pass an extension name or --all"),
+            };
+            handle_update(name).await
         }
-        ExtensionAction::Search { query, registry, category, author, sort, limit } => {
-            handle_search(query, registry.as_deref(), category.as_deref(), author.as_deref(), sort.as_deref(), *limit).await
+        ExtensionAction::Search { query, registry, category, author, sort, limit, min_downloads, installed, not_installed, no_retry, cached } => {
+            let installed_filter = match (installed, not_installed) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            handle_search(query, registry.as_deref(), category.as_deref(), author.as_deref(), *sort, *limit, *min_downloads, installed_filter, !*no_retry, *cached).await
         }
         ExtensionAction::Registry { action } => {
             handle_registry_command(action).await
@@ -69,19 +113,15 @@ pub async fn execute_extension_command(args: &[String]) -> Result<()> {
     
     // Find the extension binary
     let binary_path = find_extension_binary(&extension_name)
-        .ok_or_else(|| anyhow::anyhow!("Extension '{}' not found", extension_name))?;
+        .ok_or_else(|| PmError::ExtensionNotFound { name: extension_name.clone() })?;
     
     // Prepare environment variables for the extension
     let current_project = get_current_project_context().await?;
-    let config_path = crate::config::get_config_path()?;
     
     // Execute the extension
-    let mut cmd = Command::new(&binary_path);
-    cmd.args(extension_args);
-    cmd.env("PM_CONFIG_PATH", config_path);
-    cmd.env("PM_CURRENT_PROJECT", current_project);
-    cmd.env("PM_VERSION", env!("CARGO_PKG_VERSION"));
-    cmd.env("PM_EXTENSION_DIR", get_extension_dir(&extension_name)?);
+    let mut cmd = extension_command(&extension_name, &binary_path).await?;
+    cmd.command_mut().args(extension_args);
+    cmd.command_mut().env("PM_CURRENT_PROJECT", current_project);
     
     let status = cmd.status()
         .with_context(|| format!("Failed to execute extension '{}'", extension_name))?;
@@ -94,8 +134,35 @@ pub async fn execute_extension_command(args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Build a command for an extension program with the common PM environment,
+/// running it inside the OS sandbox when `settings.extension_sandbox` is enabled
+pub(crate) async fn extension_command(extension_name: &str, program: &Path) -> Result<sandbox::SandboxedCommand> {
+    let extension_dir = get_extension_dir(extension_name)?;
+    let sandbox_enabled = crate::config::load_config().await
+        .map(|config| config.settings.extension_sandbox)
+        .unwrap_or(false);
+
+    let mut cmd = if sandbox_enabled {
+        let manifest_path = crate::extensions::get_extension_manifest_path(extension_name)?;
+        let policy = ExtensionManifest::load_from_file(&manifest_path).await
+            .ok()
+            .and_then(|manifest| manifest.sandbox)
+            .unwrap_or_default();
+        sandbox::SandboxedCommand::new(program, &policy, &extension_dir)?
+    } else {
+        sandbox::SandboxedCommand::unrestricted(program)
+    };
+
+    cmd.command_mut()
+        .env("PM_CONFIG_PATH", crate::config::get_config_path()?)
+        .env("PM_VERSION", env!("CARGO_PKG_VERSION"))
+        .env("PM_EXTENSION_DIR", extension_dir);
+
+    Ok(cmd)
+}
+
 /// Handle extension installation
-async fn handle_install(name: &str, source: Option<&str>, version: Option<&str>, local: bool, registry: Option<&str>, force: bool) -> Result<()> {
+async fn handle_install(name: &str, source: Option<&str>, version: Option<&str>, local: bool, registry: Option<&str>, force: bool, retry: bool) -> Result<()> {
     // Ensure extensions directory exists
     ensure_extensions_dir().await?;
     
@@ -104,7 +171,7 @@ async fn handle_install(name: &str, source: Option<&str>, version: Option<&str>,
         handle_local_install(name).await
     } else {
         // Remote installation
-        handle_remote_install(name, source, version, registry, force).await
+        handle_remote_install(name, source, version, registry, force, retry).await
     }
 }
 
@@ -157,6 +224,7 @@ async fn handle_local_install(name_or_path: &str) -> Result<()> {
             "bash" => install_bash_extension(&source_dir, &manifest).await?,
             "python" => install_python_extension(&source_dir, &manifest).await?,
             "rust" => install_rust_extension(&source_dir, &manifest).await?,
+            "deno" => install_deno_extension(&source_dir, &manifest).await?,
             _ => return Err(anyhow::anyhow!("Unsupported extension type: {}", ext_type)),
         }
     } else {
@@ -175,6 +243,8 @@ async fn determine_extension_type(source_dir: &Path) -> Result<Option<String>> {
         Ok(Some("bash".to_string()))
     } else if source_dir.join("python").exists() || source_dir.join("main.py").exists() {
         Ok(Some("python".to_string()))
+    } else if source_dir.join("deno").exists() {
+        Ok(Some("deno".to_string()))
     } else if source_dir.join("Cargo.toml").exists() {
         Ok(Some("rust".to_string()))
     } else {
@@ -236,6 +306,24 @@ async fn install_python_extension(source_dir: &Path, manifest: &ExtensionManifes
     Ok(())
 }
 
+/// Install deno extension
+async fn install_deno_extension(source_dir: &Path, manifest: &ExtensionManifest) -> Result<()> {
+    let target_dir = get_extension_dir(&manifest.name)?;
+    fs::create_dir_all(&target_dir).await?;
+
+    // Copy manifest
+    let target_manifest = target_dir.join("manifest.yml");
+    manifest.save_to_file(&target_manifest).await?;
+
+    // Copy TypeScript sources
+    copy_directory(&source_dir.join("deno"), &target_dir.join("deno")).await?;
+
+    // Create executable wrapper
+    create_deno_wrapper(&target_dir, &manifest.name).await?;
+
+    Ok(())
+}
+
 /// Install rust extension
 async fn install_rust_extension(source_dir: &Path, manifest: &ExtensionManifest) -> Result<()> {
     let target_dir = get_extension_dir(&manifest.name)?;
@@ -408,6 +496,48 @@ fi
     Ok(())
 }
 
+/// Create deno wrapper script
+async fn create_deno_wrapper(target_dir: &Path, extension_name: &str) -> Result<()> {
+    let wrapper_script = format!(r#"#!/bin/bash
+# Auto-generated wrapper for {} extension
+
+EXTENSION_DIR="$(dirname "$0")"
+DENO_DIR="$EXTENSION_DIR/deno"
+
+# Pass command name as first argument
+if [ $# -eq 0 ]; then
+    COMMAND="help"
+else
+    COMMAND="$1"
+    shift
+fi
+
+# Look for command script
+if [ -f "$DENO_DIR/$COMMAND.ts" ]; then
+    exec deno run --allow-env --allow-read "$DENO_DIR/$COMMAND.ts" "$COMMAND" "$@"
+elif [ -f "$DENO_DIR/main.ts" ]; then
+    exec deno run --allow-env --allow-read "$DENO_DIR/main.ts" "$COMMAND" "$@"
+else
+    echo "Command '$COMMAND' not found in extension '{}'"
+    exit 1
+fi
+"#, extension_name, extension_name);
+
+    let wrapper_path = target_dir.join("binary");
+    fs::write(&wrapper_path, wrapper_script).await?;
+
+    // Set executable permissions
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&wrapper_path).await?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&wrapper_path, perms).await?;
+    }
+
+    Ok(())
+}
+
 /// Extract binary name from Cargo.toml
 fn extract_binary_name_from_cargo_toml(content: &str) -> Result<String> {
     // Simple parsing to find [[bin]] name
@@ -442,7 +572,7 @@ fn extract_binary_name_from_cargo_toml(content: &str) -> Result<String> {
 /// Handle extension uninstallation
 async fn handle_uninstall(name: &str, force: bool) -> Result<()> {
     if !discovery::is_extension_installed(name).await {
-        return Err(anyhow::anyhow!("Extension '{}' is not installed", name));
+        return Err(PmError::ExtensionNotFound { name: name.to_string() }.into());
     }
     
     // Show extension info before removal
@@ -483,7 +613,20 @@ async fn handle_uninstall(name: &str, force: bool) -> Result<()> {
 }
 
 /// Handle listing extensions
-async fn handle_list(all: bool) -> Result<()> {
+async fn handle_list(all: bool, format: &str, verbose: bool) -> Result<()> {
+    let mut extensions: Vec<(String, ExtensionInfo)> =
+        discovery::discover_extensions().await?.into_iter().collect();
+    extensions.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if format == "json" {
+        let responses = extensions
+            .into_iter()
+            .map(|(name, info)| extension_info_response(&name, info))
+            .collect::<Result<Vec<_>>>()?;
+        println!("{}", serde_json::to_string_pretty(&responses)?);
+        return Ok(());
+    }
+
     if all {
         println!("📦 Available extensions (from registry):");
         println!("  (Registry listing not yet implemented)");
@@ -492,8 +635,6 @@ async fn handle_list(all: bool) -> Result<()> {
     
     println!("📦 Installed extensions:");
     
-    let extensions = discovery::discover_extensions().await?;
-    
     if extensions.is_empty() {
         println!("  No extensions installed");
         return Ok(());
@@ -502,8 +643,9 @@ async fn handle_list(all: bool) -> Result<()> {
     for (name, info) in extensions {
         println!("  {:<12} {} - {}", name, info.version, info.description);
         
-        // Show commands
-        if !info.commands.is_empty() {
+        if verbose {
+            print_extension_commands(&info, "    ");
+        } else if !info.commands.is_empty() {
             let command_names: Vec<String> = info.commands.iter()
                 .map(|cmd| cmd.name.clone())
                 .collect();
@@ -514,10 +656,173 @@ async fn handle_list(all: bool) -> Result<()> {
     Ok(())
 }
 
+/// Handle listing extensions that have newer versions in their registry
+async fn handle_list_outdated() -> Result<()> {
+    println!("🔍 Checking registries for extension updates...");
+
+    let updates = check_for_updates(true).await?;
+    if updates.is_empty() {
+        println!("  No extensions installed from a registry");
+        return Ok(());
+    }
+
+    let outdated: Vec<&ExtensionUpdateInfo> = updates.iter().filter(|u| u.is_outdated()).collect();
+
+    if outdated.is_empty() {
+        println!("✅ All {} registry extension(s) are up to date", updates.len());
+        return Ok(());
+    }
+
+    println!("📦 Outdated extensions:");
+    for update in &outdated {
+        let latest = update.latest_version.as_deref().unwrap_or_default();
+        println!(
+            "  {:<12} {} {}",
+            update.name,
+            update.installed_version,
+            format!("(→ v{})", latest).yellow()
+        );
+    }
+
+    println!();
+    println!(
+        "⬆️  {} of {} registry extension(s) outdated",
+        outdated.len(),
+        updates.len()
+    );
+    println!("💡 Run 'pm ext update --all' to update them");
+
+    Ok(())
+}
+
+/// Update status of an installed extension
+#[derive(Debug, Clone)]
+pub struct ExtensionUpdateInfo {
+    pub name: String,
+    pub installed_version: String,
+    /// Latest version in the source registry, if the lookup succeeded
+    pub latest_version: Option<String>,
+}
+
+impl ExtensionUpdateInfo {
+    /// Whether the registry has a newer version than the installed one
+    pub fn is_outdated(&self) -> bool {
+        self.latest_version
+            .as_deref()
+            .is_some_and(|latest| is_newer_version(latest, &self.installed_version))
+    }
+}
+
+/// Compare two versions with semver; unparseable versions never count as newer
+fn is_newer_version(candidate: &str, current: &str) -> bool {
+    let parse = |version: &str| semver::Version::parse(version.trim_start_matches('v')).ok();
+    match (parse(candidate), parse(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct UpdateCheckCache {
+    checked_at: DateTime<Utc>,
+    latest_versions: HashMap<String, String>,
+}
+
+fn get_update_check_cache_path() -> Result<PathBuf> {
+    Ok(get_extensions_dir()?.join("update-check.json"))
+}
+
+async fn load_update_check_cache() -> Option<UpdateCheckCache> {
+    let content = fs::read_to_string(get_update_check_cache_path().ok()?).await.ok()?;
+    let cache: UpdateCheckCache = serde_json::from_str(&content).ok()?;
+    let age = Utc::now().signed_duration_since(cache.checked_at);
+    (age.num_hours() < UPDATE_CHECK_CACHE_TTL_HOURS).then_some(cache)
+}
+
+/// Look up the latest registry version of every extension installed from a registry
+///
+/// Lookups run in parallel and are cached for an hour; pass `use_cache = false` to
+/// force fresh lookups. Locally installed extensions are not included.
+pub async fn check_for_updates(use_cache: bool) -> Result<Vec<ExtensionUpdateInfo>> {
+    let installed = discovery::discover_extensions().await?;
+    let local_registry = registry::load_registry().await.unwrap_or_default();
+    let cache = if use_cache { load_update_check_cache().await } else { None };
+
+    let mut latest_versions: HashMap<String, String> = HashMap::new();
+    let mut to_fetch = Vec::new();
+
+    for name in installed.keys() {
+        // Only registry installs record a download source
+        let Some(source) = local_registry.get_extension(name).and_then(|e| e.source.clone()) else {
+            continue;
+        };
+        match cache.as_ref().and_then(|c| c.latest_versions.get(name)) {
+            Some(version) => {
+                latest_versions.insert(name.clone(), version.clone());
+            }
+            None => to_fetch.push((name.clone(), source)),
+        }
+    }
+
+    if !to_fetch.is_empty() {
+        let registry_manager = remote::load_registry_manager().await
+            .context("Failed to load registry configuration")?;
+
+        let mut lookups = tokio::task::JoinSet::new();
+        for (name, source) in to_fetch {
+            let client = registry_manager.get_client_for_source(&source)?;
+            lookups.spawn(async move {
+                let version = client.get_extension(&name).await.ok().map(|m| m.version);
+                (name, version)
+            });
+        }
+
+        while let Some(result) = lookups.join_next().await {
+            if let Ok((name, Some(version))) = result {
+                latest_versions.insert(name, version);
+            }
+        }
+
+        // Entries reused from the cache keep its age, so they still expire on schedule
+        let checked_at = match &cache {
+            Some(cache) if latest_versions.keys().any(|name| cache.latest_versions.contains_key(name)) => {
+                cache.checked_at
+            }
+            _ => Utc::now(),
+        };
+        let cache = UpdateCheckCache {
+            checked_at,
+            latest_versions: latest_versions.clone(),
+        };
+        if let (Ok(path), Ok(content)) = (get_update_check_cache_path(), serde_json::to_string_pretty(&cache)) {
+            let _ = fs::write(path, content).await;
+        }
+    }
+
+    let mut updates: Vec<ExtensionUpdateInfo> = installed
+        .into_iter()
+        .filter(|(name, _)| local_registry.get_extension(name).is_some_and(|e| e.source.is_some()))
+        .map(|(name, info)| ExtensionUpdateInfo {
+            latest_version: latest_versions.get(&name).cloned(),
+            installed_version: info.version,
+            name,
+        })
+        .collect();
+    updates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(updates)
+}
+
 /// Handle showing extension information
-async fn handle_info(name: &str) -> Result<()> {
+async fn handle_info(name: &str, format: &str) -> Result<()> {
     let extension_info = discovery::load_extension_info(name).await
         .with_context(|| format!("Failed to load extension '{}' info", name))?;
+
+    if format == "json" {
+        let response = extension_info_response(name, extension_info)?;
+        println!("{}", serde_json::to_string_pretty(&response)?);
+        return Ok(());
+    }
     
     println!("📦 Extension: {}", extension_info.name);
     println!("Version: {}", extension_info.version);
@@ -532,43 +837,146 @@ async fn handle_info(name: &str) -> Result<()> {
     }
     
     println!("\nCommands:");
-    for cmd in &extension_info.commands {
-        println!("  {:<12} {}", cmd.name, cmd.help);
+    print_extension_commands(&extension_info, "  ");
+    
+    Ok(())
+}
+
+/// JSON details of an installed extension, shared by `pm ext info` and `pm ext list`
+fn extension_info_response(
+    name: &str,
+    info: ExtensionInfo,
+) -> Result<crate::extensions::ExtensionInfoResponse> {
+    let path = get_extension_dir(name)?;
+    let updated_at = std::fs::metadata(crate::extensions::get_extension_manifest_path(name)?)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .map(DateTime::<Utc>::from);
+    let disk_usage_bytes = crate::utils::calculate_directory_size(&path);
+
+    Ok(crate::extensions::ExtensionInfoResponse {
+        info,
+        path,
+        updated_at,
+        disk_usage_bytes,
+    })
+}
+
+/// Each command with its help, aliases and arguments
+fn print_extension_commands(info: &ExtensionInfo, indent: &str) {
+    for cmd in &info.commands {
+        println!("{}{:<12} {}", indent, cmd.name, cmd.help);
         
         if let Some(aliases) = &cmd.aliases {
             if !aliases.is_empty() {
-                println!("               Aliases: {}", aliases.join(", "));
+                println!("{}             Aliases: {}", indent, aliases.join(", "));
             }
         }
         
         if let Some(args) = &cmd.args {
             if !args.is_empty() {
-                println!("               Args: {}", args.join(" "));
+                println!("{}             Args: {}", indent, args.join(" "));
             }
         }
     }
-    
-    Ok(())
 }
 
-/// Handle extension updates
+/// Handle extension updates (a single extension, or all when `name` is None)
 async fn handle_update(name: Option<&str>) -> Result<()> {
+    use indicatif::{ProgressBar, ProgressStyle};
+
+    let installed = discovery::discover_extensions().await?;
     if let Some(name) = name {
-        println!("Updating extension '{}'...", name);
-        // TODO: Implement update logic for specific extension
-        println!("✅ Extension '{}' update placeholder completed", name);
-    } else {
-        println!("Updating all extensions...");
-        let extensions = discovery::discover_extensions().await?;
-        
-        for (ext_name, _) in extensions {
-            println!("  Updating '{}'...", ext_name);
-            // TODO: Implement update logic
+        if !installed.contains_key(name) {
+            return Err(PmError::ExtensionNotFound { name: name.to_string() }.into());
         }
-        
-        println!("✅ All extensions update placeholder completed");
     }
-    
+
+    println!("🔍 Checking registries for extension updates...");
+    let updates = check_for_updates(false).await?;
+
+    let mut names: Vec<&String> = installed
+        .keys()
+        .filter(|ext_name| name.is_none_or(|name| name == ext_name.as_str()))
+        .collect();
+    names.sort();
+
+    let registry_manager = remote::load_registry_manager().await
+        .context("Failed to load registry configuration")?;
+    let local_registry = registry::load_registry().await.unwrap_or_default();
+
+    let mut updated = Vec::new();
+    let mut skipped_local = 0;
+    let mut up_to_date = 0;
+    let mut failed = 0;
+
+    let pb = ProgressBar::new(names.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("[{pos}/{len}] {msg} {bar:40.cyan/blue} {percent}%")
+            .unwrap()
+            .progress_chars("██▓▒░"),
+    );
+
+    for ext_name in names {
+        pb.set_message(format!("Checking {}", ext_name));
+
+        let Some(update) = updates.iter().find(|u| &u.name == ext_name) else {
+            // Installed from a local directory: no registry to update from
+            skipped_local += 1;
+            pb.inc(1);
+            continue;
+        };
+
+        if !update.is_outdated() {
+            if update.latest_version.is_none() {
+                pb.println(format!("⚠️  Could not fetch the latest version of '{}'", ext_name));
+                failed += 1;
+            } else {
+                up_to_date += 1;
+            }
+            pb.inc(1);
+            continue;
+        }
+
+        let latest = update.latest_version.clone().unwrap_or_default();
+        pb.set_message(format!("Updating {} to v{}", ext_name, latest));
+        let registry_name = local_registry
+            .get_extension(ext_name)
+            .and_then(|entry| entry.source.as_deref())
+            .and_then(|source| registry_manager.registry_name_for_source(source));
+
+        let result = handle_remote_install(ext_name, None, Some(&latest), registry_name, true, true).await;
+        match result {
+            Ok(()) => updated.push((ext_name.clone(), update.installed_version.clone(), latest)),
+            Err(e) => {
+                pb.println(format!("❌ Failed to update '{}': {}", ext_name, e));
+                failed += 1;
+            }
+        }
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+
+    // Make sure every updated extension still loads and has an executable binary
+    for (ext_name, from, to) in &updated {
+        match discovery::load_extension_info(ext_name).await {
+            Ok(_) => println!("✅ {} {} → {} (validated)", ext_name, from, to),
+            Err(e) => println!("⚠️  {} {} → {} but validation failed: {}", ext_name, from, to, e),
+        }
+    }
+
+    let mut summary = format!(
+        "{} updated, {} skipped (local), {} already up-to-date",
+        updated.len(),
+        skipped_local,
+        up_to_date
+    );
+    if failed > 0 {
+        summary.push_str(&format!(", {} failed", failed));
+    }
+    println!("📊 {}", summary);
+
     Ok(())
 }
 
@@ -670,7 +1078,7 @@ async fn get_current_project_context() -> Result<String> {
 }
 
 /// Handle remote extension installation
-async fn handle_remote_install(name: &str, _source: Option<&str>, version: Option<&str>, registry: Option<&str>, force: bool) -> Result<()> {
+async fn handle_remote_install(name: &str, _source: Option<&str>, version: Option<&str>, registry: Option<&str>, force: bool, retry: bool) -> Result<()> {
     println!("Installing extension '{}' from registry...", name);
     
     // Check if extension is already installed
@@ -684,7 +1092,8 @@ async fn handle_remote_install(name: &str, _source: Option<&str>, version: Optio
     
     // Get registry client
     let client = registry_manager.get_client(registry)
-        .context("Failed to get registry client")?;
+        .context("Failed to get registry client")?
+        .with_retry(retry);
     
     // Get extension metadata
     let metadata = if let Some(version) = version {
@@ -771,7 +1180,7 @@ async fn extract_archive(archive_path: &std::path::Path, target_dir: &std::path:
 }
 
 /// Install extracted extension (detect type and build if necessary)
-async fn install_extracted_extension(ext_dir: &std::path::Path, _metadata: &remote::RemoteExtensionMetadata) -> Result<()> {
+async fn install_extracted_extension(ext_dir: &std::path::Path, metadata: &remote::RemoteExtensionMetadata) -> Result<()> {
     // Check for different extension types and install accordingly
     let cargo_toml = ext_dir.join("Cargo.toml");
     let requirements_txt = ext_dir.join("requirements.txt");
@@ -785,19 +1194,24 @@ async fn install_extracted_extension(ext_dir: &std::path::Path, _metadata: &remo
         // Python extension
         println!("🐍 Installing Python extension...");
         remote_install::install_python_extension_from_extracted(ext_dir).await?;
+    } else if ext_dir.join("deno").exists() {
+        // Deno extension
+        println!("🦕 Installing Deno extension...");
+        create_deno_wrapper(ext_dir, &metadata.name).await?;
     } else if bash_dir.exists() {
         // Bash extension
         println!("🐚 Installing Bash extension...");
         remote_install::install_bash_extension_from_extracted(ext_dir).await?;
     } else {
-        return Err(anyhow::anyhow!("Unknown extension type - no Cargo.toml, requirements.txt, or bash/ directory found"));
+        return Err(anyhow::anyhow!("Unknown extension type - no Cargo.toml, requirements.txt, deno/ or bash/ directory found"));
     }
     
     Ok(())
 }
 
 /// Updated handle_search function with registry support
-async fn handle_search(query: &str, registry: Option<&str>, category: Option<&str>, author: Option<&str>, sort: Option<&str>, limit: Option<u32>) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+async fn handle_search(query: &str, registry: Option<&str>, category: Option<&str>, author: Option<&str>, sort: Option<remote::SearchSort>, limit: Option<u32>, min_downloads: Option<u64>, installed_filter: Option<bool>, retry: bool, cached: bool) -> Result<()> {
     println!("🔍 Searching for extensions matching '{}'...", query);
     
     // Load registry manager
@@ -806,34 +1220,72 @@ async fn handle_search(query: &str, registry: Option<&str>, category: Option<&st
     
     // Get registry client
     let client = registry_manager.get_client(registry)
-        .context("Failed to get registry client")?;
+        .context("Failed to get registry client")?
+        .with_retry(retry);
     
+    // Download and installation filters run locally, so with either of them every
+    // match is fetched and `limit` is applied after filtering
+    let filter_locally = min_downloads.is_some() || installed_filter.is_some();
+
     // Build search parameters
     let params = remote::SearchParams {
         query: Some(query.to_string()),
         category: category.map(|s| s.to_string()),
         author: author.map(|s| s.to_string()),
-        sort: sort.map(|s| s.to_string()),
-        limit,
+        sort,
+        limit: if filter_locally { None } else { limit },
         ..Default::default()
     };
     
-    // Perform search
-    let results = client.search(&params).await
-        .context("Failed to search extensions")?;
+    let cached_results = if cached {
+        search_registry_cache(client.name(), &params).await
+    } else {
+        None
+    };
+
+    let results = match cached_results {
+        Some(results) => results,
+        None => {
+            let results = if filter_locally {
+                client.search_all(&params).await
+            } else {
+                client.search(&params).await.map(|results| results.extensions)
+            }
+            .context("Failed to search extensions")?;
+            // Keep the cache for offline searches; failing to write it is not an error
+            let _ = remote::cache_search_results(client.name(), &results).await;
+            results
+        }
+    };
+    
+    // Check local installation state for each result
+    let mut extensions = Vec::new();
+    for ext in results {
+        if limit.is_some_and(|limit| extensions.len() >= limit as usize) {
+            break;
+        }
+        if min_downloads.is_some_and(|min| ext.downloads < min) {
+            continue;
+        }
+        let is_installed = discovery::is_extension_installed(&ext.name).await;
+        if installed_filter.is_none_or(|wanted| wanted == is_installed) {
+            extensions.push((ext, is_installed));
+        }
+    }
     
-    if results.extensions.is_empty() {
+    if extensions.is_empty() {
         println!("No extensions found matching your criteria");
         return Ok(());
     }
     
-    println!("\n📦 Found {} extension(s):", results.extensions.len());
+    println!("\n📦 Found {} extension(s):", extensions.len());
     println!();
     
-    for ext in results.extensions {
-        println!("  {:<20} v{}", ext.name, ext.version);
+    for (ext, is_installed) in extensions {
+        let installed_marker = if is_installed { " (installed)".green().to_string() } else { String::new() };
+        println!("  {:<20} v{}{}", ext.name, ext.version, installed_marker);
         println!("  {:<20} {}", "", ext.description);
-        println!("  {:<20} by {} • {} downloads", "", ext.author, ext.downloads);
+        println!("  {:<20} by {} • {} downloads", "", ext.author, format_number(ext.downloads));
         if !ext.categories.is_empty() {
             println!("  {:<20} Categories: {}", "", ext.categories.join(", "));
         }
@@ -851,6 +1303,101 @@ async fn handle_search(query: &str, registry: Option<&str>, category: Option<&st
     Ok(())
 }
 
+/// Search the cached index of `registry`, or None (with a warning) when it is missing
+/// or older than `settings.registry_cache_ttl_hours`
+async fn search_registry_cache(registry: &str, params: &remote::SearchParams) -> Option<Vec<remote::SearchExtension>> {
+    let ttl_hours = crate::config::load_config()
+        .await
+        .map(|config| config.settings.registry_cache_ttl_hours)
+        .unwrap_or(remote::DEFAULT_REGISTRY_CACHE_TTL_HOURS);
+
+    let Some(cache) = remote::load_registry_cache(registry).await else {
+        crate::display::display_warning(&format!("No cached index for registry '{}', searching online", registry));
+        return None;
+    };
+    let Some(indexed_at) = cache.indexed_at else {
+        crate::display::display_warning(&format!(
+            "Cache for registry '{}' only holds earlier search results, searching online (run 'pm ext registry cache --refresh' to download the full index)",
+            registry
+        ));
+        return None;
+    };
+    if !cache.is_fresh(ttl_hours, Utc::now()) {
+        crate::display::display_warning(&format!(
+            "Cached index for registry '{}' is older than {} hours, searching online",
+            registry, ttl_hours
+        ));
+        return None;
+    }
+
+    println!("📦 Using cached index for '{}' ({})", registry, format_cache_age(indexed_at));
+    Some(cache.search(params))
+}
+
+fn format_cache_age(cached_at: DateTime<Utc>) -> String {
+    let age = Utc::now().signed_duration_since(cached_at);
+    if age.num_hours() > 0 {
+        format!("cached {} hours ago", age.num_hours())
+    } else {
+        format!("cached {} minutes ago", age.num_minutes())
+    }
+}
+
+/// `pm ext registry cache`: show the cached indexes, or download them again with `refresh`
+async fn handle_registry_cache(refresh: bool) -> Result<()> {
+    let registry_manager = remote::load_registry_manager().await?;
+    let mut names: Vec<String> = registry_manager
+        .list_registries()
+        .into_iter()
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+
+    if refresh {
+        println!("🔄 Refreshing registry indexes...");
+        let mut failed = 0;
+        for name in &names {
+            let client = registry_manager.get_client(Some(name))?;
+            match client.fetch_index().await {
+                Ok(extensions) => {
+                    let count = extensions.len();
+                    remote::save_registry_cache(client.name(), &remote::RegistryCache::new(extensions, Utc::now())).await?;
+                    println!("  ✅ {:<15} {} extensions", name, count);
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("  ❌ {:<15} {}", name, e);
+                }
+            }
+        }
+        if failed > 0 {
+            return Err(anyhow::anyhow!("Failed to refresh {} registry index(es)", failed));
+        }
+        return Ok(());
+    }
+
+    println!("📦 Cached registry indexes:");
+    for name in &names {
+        let client = registry_manager.get_client(Some(name))?;
+        match remote::load_registry_cache(client.name()).await {
+            Some(cache) => println!(
+                "  {:<15} {} extensions, {}",
+                name,
+                cache.extensions.len(),
+                cache
+                    .indexed_at
+                    .map(format_cache_age)
+                    .unwrap_or_else(|| "search results only".to_string())
+            ),
+            None => println!("  {:<15} {}", name, "not cached".dimmed()),
+        }
+    }
+    println!();
+    println!("💡 Refresh with: pm ext registry cache --refresh");
+
+    Ok(())
+}
+
 /// Handle registry management commands
 async fn handle_registry_command(action: &RegistryAction) -> Result<()> {
     match action {
@@ -866,8 +1413,15 @@ async fn handle_registry_command(action: &RegistryAction) -> Result<()> {
         RegistryAction::Default { name } => {
             handle_registry_default(name).await
         }
-        RegistryAction::Ping { name } => {
-            handle_registry_ping(name.as_deref()).await
+        RegistryAction::Ping { name, timeout, json } => {
+            handle_registry_ping(name.as_deref(), *timeout, *json).await
+        }
+        RegistryAction::Import { file, force, ping } => {
+            handle_registry_import(file, *force, *ping).await
+        }
+        RegistryAction::Cache { refresh } => handle_registry_cache(*refresh).await,
+        RegistryAction::Export { file, include_tokens } => {
+            handle_registry_export(file.as_deref(), *include_tokens).await
         }
     }
 }
@@ -905,6 +1459,152 @@ async fn handle_registry_add(name: &str, url: &str, token: Option<&str>, set_def
     Ok(())
 }
 
+/// One registry in a `pm ext registry import`/`export` file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct RegistryFileEntry {
+    name: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    default: bool,
+}
+
+/// Parse and validate a registry file; fails on the first invalid entry so nothing is half-imported
+fn parse_registry_file(content: &str) -> Result<Vec<remote::RegistryConfig>> {
+    let entries: Vec<RegistryFileEntry> = serde_yaml::from_str(content)
+        .context("Registry file must be a YAML list of entries with 'name' and 'url'")?;
+
+    let mut seen = std::collections::HashSet::new();
+    entries
+        .into_iter()
+        .map(|entry| {
+            if entry.name.trim().is_empty() {
+                return Err(anyhow::anyhow!("Registry entry with URL '{}' has no name", entry.url));
+            }
+            if !seen.insert(entry.name.clone()) {
+                return Err(anyhow::anyhow!("Registry '{}' is listed more than once", entry.name));
+            }
+            let url = url::Url::parse(&entry.url)
+                .with_context(|| format!("Invalid URL for registry '{}': {}", entry.name, entry.url))?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(anyhow::anyhow!(
+                    "Invalid URL for registry '{}': expected http or https, got {}",
+                    entry.name,
+                    url.scheme()
+                ));
+            }
+            Ok(remote::RegistryConfig {
+                name: entry.name,
+                url,
+                token: entry.token,
+                default: entry.default,
+            })
+        })
+        .collect()
+}
+
+/// Add every registry listed in a YAML file
+async fn handle_registry_import(file: &Path, force: bool, ping: bool) -> Result<()> {
+    let content = fs::read_to_string(file).await
+        .with_context(|| format!("Failed to read registry file: {}", file.display()))?;
+    let configs = parse_registry_file(&content)?;
+
+    if configs.is_empty() {
+        println!("No registries found in {}", file.display());
+        return Ok(());
+    }
+
+    let mut registry_manager = remote::load_registry_manager().await?;
+    let (mut added, mut replaced, mut skipped) = (0, 0, 0);
+
+    for config in configs {
+        let name = config.name.clone();
+        let exists = registry_manager.list_registries().iter().any(|(existing, _)| **existing == name);
+        if exists && !force {
+            println!("  ⏭️  {} - already configured (use --force to replace)", name);
+            skipped += 1;
+            continue;
+        }
+
+        if ping {
+            let result = remote::RegistryClient::new(config.clone())
+                .ping_timed(std::time::Duration::from_secs(5))
+                .await;
+            if !result.accessible {
+                println!("  ⚠️  {} - not accessible: {}", name, result.error.as_deref().unwrap_or("unknown error"));
+            }
+        }
+
+        println!("  {} {} {}", if exists { "🔄" } else { "✅" }, name, config.url);
+        registry_manager.add_registry(name, config);
+        if exists {
+            replaced += 1;
+        } else {
+            added += 1;
+        }
+    }
+
+    remote::save_registry_manager(&registry_manager).await?;
+
+    println!();
+    println!("📊 Summary: {} added, {} replaced, {} skipped", added, replaced, skipped);
+    Ok(())
+}
+
+/// Write the configured registries as a YAML list
+async fn handle_registry_export(file: Option<&Path>, include_tokens: bool) -> Result<()> {
+    let registry_manager = remote::load_registry_manager().await?;
+    let default_registry = registry_manager.get_default_registry();
+
+    let mut entries: Vec<RegistryFileEntry> = registry_manager
+        .list_registries()
+        .into_iter()
+        .map(|(name, config)| RegistryFileEntry {
+            name: name.clone(),
+            url: config.url.to_string(),
+            token: config.token.clone().filter(|_| include_tokens),
+            default: Some(name) == default_registry,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let yaml = serde_yaml::to_string(&entries).context("Failed to serialize registries")?;
+    match file {
+        Some(path) => {
+            let written = if include_tokens {
+                write_private_file(path, yaml.as_bytes()).await
+            } else {
+                fs::write(path, yaml).await
+            };
+            written.with_context(|| format!("Failed to write registry file: {}", path.display()))?;
+            println!("✅ Exported {} registries to {}", entries.len(), path.display());
+        }
+        None => print!("{}", yaml),
+    }
+    Ok(())
+}
+
+/// Write `contents` to `path` so only the current user can read it (mode 0600 on Unix)
+///
+/// An existing file is restricted before anything is written to it.
+async fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600)).await?;
+    }
+    file.write_all(contents).await?;
+    file.flush().await
+}
+
 /// Remove a registry
 async fn handle_registry_remove(name: &str) -> Result<()> {
     let mut registry_manager = remote::load_registry_manager().await?;
@@ -974,36 +1674,68 @@ async fn handle_registry_default(name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Test registry connectivity
-async fn handle_registry_ping(name: Option<&str>) -> Result<()> {
+/// `pm ext create --publish`: create the GitHub repository for a new extension
+#[cfg(feature = "create-github-repo")]
+async fn publish_extension(directory: &Path, name: &str, private: bool) -> Result<()> {
+    creation::create_github_repository(directory, name, private).await.map(|_| ())
+}
+
+#[cfg(not(feature = "create-github-repo"))]
+async fn publish_extension(_directory: &Path, _name: &str, _private: bool) -> Result<()> {
+    Err(anyhow::anyhow!("pm was built without create-github-repo"))
+}
+
+/// Test registry connectivity, fastest registries first
+async fn handle_registry_ping(name: Option<&str>, timeout_ms: u64, json: bool) -> Result<()> {
     let registry_manager = remote::load_registry_manager().await?;
-    
-    if let Some(name) = name {
-        // Test specific registry
-        let client = registry_manager.get_client(Some(name))?;
-        println!("🏓 Testing connectivity to registry '{}'...", name);
-        
-        if client.ping().await.unwrap_or(false) {
-            println!("✅ Registry '{}' is accessible", name);
-        } else {
-            println!("❌ Registry '{}' is not accessible", name);
+    let timeout = std::time::Duration::from_millis(timeout_ms);
+
+    let clients = match name {
+        Some(name) => vec![registry_manager.get_client(Some(name))?],
+        None => registry_manager
+            .list_registries()
+            .into_iter()
+            .map(|(_, config)| remote::RegistryClient::new(config.clone()))
+            .collect(),
+    };
+
+    if !json {
+        match name {
+            Some(name) => println!("🏓 Testing connectivity to registry '{}'...", name),
+            None => println!("🏓 Testing connectivity to all registries..."),
         }
-    } else {
-        // Test all registries
-        println!("🏓 Testing connectivity to all registries...");
         println!();
-        
-        let registries = registry_manager.list_registries();
-        for (reg_name, config) in registries {
-            let client = remote::RegistryClient::new(config.clone());
-            if client.ping().await.unwrap_or(false) {
-                println!("  ✅ {} - accessible", reg_name);
-            } else {
-                println!("  ❌ {} - not accessible", reg_name);
-            }
+    }
+
+    let mut results = Vec::new();
+    for client in &clients {
+        results.push(client.ping_timed(timeout).await);
+    }
+    // Accessible registries by response time, then the rest
+    results.sort_by_key(|result| (!result.accessible, result.response_time_ms.unwrap_or(u64::MAX)));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    for result in &results {
+        let time = result
+            .response_time_ms
+            .map(|ms| format!(" ({}ms)", ms))
+            .unwrap_or_default();
+        if result.accessible {
+            println!("  ✅ {} - accessible{}", result.name, time);
+        } else {
+            println!(
+                "  ❌ {} - not accessible{}: {}",
+                result.name,
+                time,
+                result.error.as_deref().unwrap_or("unknown error")
+            );
         }
     }
-    
+
     Ok(())
 }
 
@@ -1077,4 +1809,49 @@ pub async fn list_extensions() -> Result<()> {
     println!("Use 'pm run <extension> <command>' to execute extension commands");
     
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_write_private_file_restricts_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("registries.yml");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_file(&path, b"- name: internal\n").await.unwrap();
+
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "- name: internal\n");
+    }
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(is_newer_version("1.2.3", "1.2.0"));
+        assert!(is_newer_version("v2.0.0", "1.9.9"));
+        assert!(!is_newer_version("1.0.0", "1.0.0"));
+        assert!(!is_newer_version("1.0.0-beta", "1.0.0"));
+        assert!(!is_newer_version("latest", "1.0.0"));
+    }
+
+    #[test]
+    fn test_parse_registry_file() {
+        let configs = parse_registry_file(
+            "- name: team\n  url: https://registry.example.com/\n  default: true\n- name: mirror\n  url: http://mirror.local\n  token: secret\n",
+        )
+        .unwrap();
+        assert_eq!(configs.len(), 2);
+        assert!(configs[0].default);
+        assert_eq!(configs[1].token.as_deref(), Some("secret"));
+
+        assert!(parse_registry_file("- name: bad\n  url: not a url\n").is_err());
+        assert!(parse_registry_file("- name: ftp\n  url: ftp://example.com\n").is_err());
+        assert!(parse_registry_file("- name: a\n  url: https://a.dev\n- name: a\n  url: https://b.dev\n").is_err());
+    }
+}
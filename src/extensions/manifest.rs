@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Extension manifest structure
@@ -12,6 +13,12 @@ pub struct ExtensionManifest {
     pub homepage: Option<String>,
     pub pm_version: Option<String>,
     pub commands: Vec<ExtensionCommand>,
+    /// Permissions applied when extension sandboxing is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<crate::extensions::sandbox::SandboxPolicy>,
+    /// Scripts run on PM events, keyed by event name (e.g. `on_project_switch`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub hooks: HashMap<String, String>,
 }
 
 /// Extension command specification
@@ -1,10 +1,13 @@
 pub mod creation;
 pub mod discovery;
+pub mod hooks;
 pub mod manifest;
 pub mod manager;
 pub mod registry;
 pub mod remote;
 pub mod remote_install;
+pub mod sandbox;
+pub mod templates;
 
 pub use discovery::{discover_extensions, find_extension_binary};
 pub use manifest::{ExtensionManifest, ExtensionCommand};
@@ -27,6 +30,16 @@ pub struct ExtensionInfo {
     pub commands: Vec<ExtensionCommand>,
 }
 
+/// `pm ext info --format json` output: extension info plus installation details
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtensionInfoResponse {
+    #[serde(flatten)]
+    pub info: ExtensionInfo,
+    pub path: PathBuf,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub disk_usage_bytes: u64,
+}
+
 /// Get the extensions directory path
 pub fn get_extensions_dir() -> Result<PathBuf> {
     let config_dir = crate::config::get_config_dir()?;
@@ -2,9 +2,19 @@ use anyhow::{Context, Result};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::PathBuf;
 use url::Url;
 
+/// Delays before each retry of a transient network failure
+const RETRY_DELAYS_MS: [u64; 3] = [100, 400, 1600];
+
+/// Default for `settings.registry_cache_ttl_hours`
+pub const DEFAULT_REGISTRY_CACHE_TTL_HOURS: u64 = 24;
+
+/// Page size used when downloading a registry's full index
+const INDEX_PAGE_SIZE: u32 = 100;
+
 /// Remote extension registry configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryConfig {
@@ -96,6 +106,31 @@ pub struct SearchFacets {
     pub authors: HashMap<String, u64>,
 }
 
+/// Sort order for registry search results
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSort {
+    /// Most downloaded first
+    Downloads,
+    /// Most recently updated first
+    Updated,
+    /// Most recently published first
+    Created,
+    /// Alphabetical
+    Name,
+}
+
+impl SearchSort {
+    /// Value of the registry API `sort` query parameter
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SearchSort::Downloads => "downloads",
+            SearchSort::Updated => "updated",
+            SearchSort::Created => "created",
+            SearchSort::Name => "name",
+        }
+    }
+}
+
 /// Search parameters
 #[derive(Debug, Clone, Default)]
 pub struct SearchParams {
@@ -103,7 +138,7 @@ pub struct SearchParams {
     pub category: Option<String>,
     pub author: Option<String>,
     pub keywords: Vec<String>,
-    pub sort: Option<String>, // "downloads", "updated", "created", "name"
+    pub sort: Option<SearchSort>,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
 }
@@ -112,6 +147,7 @@ pub struct SearchParams {
 pub struct RegistryClient {
     client: Client,
     config: RegistryConfig,
+    retry: bool,
 }
 
 impl RegistryClient {
@@ -138,7 +174,36 @@ impl RegistryClient {
             .build()
             .expect("Failed to create HTTP client");
         
-        Self { client, config }
+        Self { client, config, retry: true }
+    }
+
+    /// Name of the registry this client talks to
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// Enable or disable retrying transient network failures (enabled by default)
+    pub fn with_retry(mut self, retry: bool) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Send a GET request, retrying connection errors, timeouts and 5xx responses
+    async fn get_with_retry(&self, url: impl reqwest::IntoUrl + Clone) -> Result<reqwest::Response> {
+        retry_with_backoff(self.retry, || async {
+            let response = self.client.get(url.clone()).send().await.map_err(|e| {
+                let failure = crate::error::PmError::RegistryConnectionFailed {
+                    url: self.config.url.to_string(),
+                    cause: e.to_string(),
+                };
+                anyhow::Error::new(e).context(failure)
+            })?;
+            if response.status().is_server_error() {
+                response.error_for_status_ref()?;
+            }
+            Ok(response)
+        })
+        .await
     }
     
     /// Get extension metadata
@@ -147,9 +212,8 @@ impl RegistryClient {
             .join(&format!("api/v1/extensions/{}", name))
             .context("Failed to construct extension URL")?;
         
-        let response = self.client
-            .get(url)
-            .send()
+        let response = self
+            .get_with_retry(url)
             .await
             .context("Failed to fetch extension metadata")?;
         
@@ -175,9 +239,8 @@ impl RegistryClient {
             .join(&format!("api/v1/extensions/{}/{}", name, version))
             .context("Failed to construct extension version URL")?;
         
-        let response = self.client
-            .get(url)
-            .send()
+        let response = self
+            .get_with_retry(url)
             .await
             .context("Failed to fetch extension version metadata")?;
         
@@ -223,8 +286,8 @@ impl RegistryClient {
             query_pairs.append_pair("keyword", keyword);
         }
         
-        if let Some(sort) = &params.sort {
-            query_pairs.append_pair("sort", sort);
+        if let Some(sort) = params.sort {
+            query_pairs.append_pair("sort", sort.as_str());
         }
         
         if let Some(limit) = params.limit {
@@ -237,9 +300,8 @@ impl RegistryClient {
         
         drop(query_pairs);
         
-        let response = self.client
-            .get(url)
-            .send()
+        let response = self
+            .get_with_retry(url)
             .await
             .context("Failed to search extensions")?;
         
@@ -258,11 +320,34 @@ impl RegistryClient {
         Ok(result)
     }
     
+    /// Every extension in the registry, fetched page by page
+    pub async fn fetch_index(&self) -> Result<Vec<SearchExtension>> {
+        self.search_all(&SearchParams::default()).await
+    }
+
+    /// Every search result for `params`, fetched page by page (`limit` and `offset` are ignored)
+    pub async fn search_all(&self, params: &SearchParams) -> Result<Vec<SearchExtension>> {
+        let mut extensions = Vec::new();
+        loop {
+            let params = SearchParams {
+                limit: Some(INDEX_PAGE_SIZE),
+                offset: Some(extensions.len() as u32),
+                ..params.clone()
+            };
+            let page = self.search(&params).await?;
+            let fetched = page.extensions.len();
+            extensions.extend(page.extensions);
+            if fetched == 0 || extensions.len() as u64 >= page.total {
+                break;
+            }
+        }
+        Ok(extensions)
+    }
+    
     /// Download extension archive
     pub async fn download_extension(&self, metadata: &RemoteExtensionMetadata, target_path: &PathBuf) -> Result<()> {
-        let response = self.client
-            .get(&metadata.dist.tarball)
-            .send()
+        let response = self
+            .get_with_retry(&metadata.dist.tarball)
             .await
             .context("Failed to download extension archive")?;
         
@@ -326,6 +411,247 @@ impl RegistryClient {
             Err(_) => Ok(false),
         }
     }
+
+    /// Ping the registry, measuring the round-trip time and classifying failures
+    pub async fn ping_timed(&self, timeout: std::time::Duration) -> RegistryPingResult {
+        let mut result = RegistryPingResult {
+            name: self.config.name.clone(),
+            url: self.config.url.to_string(),
+            accessible: false,
+            response_time_ms: None,
+            status: None,
+            error: None,
+        };
+
+        let url = match self.config.url.join("api/v1/") {
+            Ok(url) => url,
+            Err(e) => {
+                result.error = Some(format!("invalid URL: {}", e));
+                return result;
+            }
+        };
+
+        let start = std::time::Instant::now();
+        let response = self.client.get(url).timeout(timeout).send().await;
+        let elapsed = start.elapsed().as_millis() as u64;
+
+        match response {
+            Ok(response) => {
+                let status = response.status();
+                result.response_time_ms = Some(elapsed);
+                result.status = Some(status.as_u16());
+                if status.is_success() {
+                    result.accessible = true;
+                } else {
+                    result.error = Some(format!("HTTP {}", status));
+                }
+            }
+            Err(e) => result.error = Some(classify_request_error(&e, timeout)),
+        }
+
+        result
+    }
+}
+
+/// Outcome of `pm ext registry ping` for one registry
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryPingResult {
+    pub name: String,
+    pub url: String,
+    pub accessible: bool,
+    /// Round-trip time, present whenever the registry responded
+    pub response_time_ms: Option<u64>,
+    /// HTTP status code of the response
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Short description of why a request failed (timeout, DNS, TLS, connection)
+fn classify_request_error(error: &reqwest::Error, timeout: std::time::Duration) -> String {
+    if error.is_timeout() {
+        return format!("timed out after {}ms", timeout.as_millis());
+    }
+
+    // The useful detail (resolver, TLS) is buried in the source chain
+    let mut details = Vec::new();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        details.push(cause.to_string());
+        source = cause.source();
+    }
+    let detail = details.last().cloned().unwrap_or_else(|| error.to_string());
+    let chain = details.join(": ").to_lowercase();
+
+    if chain.contains("dns") || chain.contains("lookup address") || chain.contains("resolve") {
+        format!("DNS failure: {}", detail)
+    } else if chain.contains("certificate") || chain.contains("tls") || chain.contains("ssl") {
+        format!("TLS error: {}", detail)
+    } else if error.is_connect() {
+        format!("connection failed: {}", detail)
+    } else {
+        format!("request failed: {}", detail)
+    }
+}
+
+/// Run `operation`, retrying transient network failures with exponential backoff
+///
+/// Waits 100ms, 400ms and 1600ms between attempts. Only connection errors, timeouts and
+/// 5xx responses are retried; anything else (including 4xx responses) is returned at once.
+/// Attempts are logged to stderr when `PM_DEBUG` is set.
+pub async fn retry_with_backoff<F, Fut, T>(retry: bool, mut operation: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delays = RETRY_DELAYS_MS.iter();
+    loop {
+        let error = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+
+        let delay = match delays.next() {
+            Some(delay) if retry && is_transient_error(&error) => *delay,
+            _ => return Err(error),
+        };
+        if std::env::var_os("PM_DEBUG").is_some() {
+            eprintln!("[debug] {:#}; retrying in {}ms", error, delay);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+    }
+}
+
+fn is_transient_error(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|error| match error.status() {
+            Some(status) => status.is_server_error(),
+            None => error.is_connect() || error.is_timeout(),
+        })
+}
+
+/// Extensions of one registry saved for `pm ext search --cached`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegistryCache {
+    /// When the registry's full index was last downloaded; `None` while the cache only
+    /// holds the results of individual searches
+    #[serde(default)]
+    pub indexed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub extensions: Vec<SearchExtension>,
+}
+
+impl RegistryCache {
+    /// Cache holding a registry's full index, downloaded at `now`
+    pub fn new(extensions: Vec<SearchExtension>, now: chrono::DateTime<chrono::Utc>) -> Self {
+        Self { indexed_at: Some(now), extensions }
+    }
+
+    /// Whether the cache holds a full index younger than `ttl_hours`
+    ///
+    /// A TTL too large to represent never expires.
+    pub fn is_fresh(&self, ttl_hours: u64, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let Some(indexed_at) = self.indexed_at else {
+            return false;
+        };
+        match i64::try_from(ttl_hours).ok().and_then(chrono::Duration::try_hours) {
+            Some(ttl) => now.signed_duration_since(indexed_at) < ttl,
+            None => true,
+        }
+    }
+
+    /// Add or replace `extensions` (matched by name)
+    ///
+    /// Search results are only part of the registry, so this does not make the cache
+    /// fresh; only a full index download does.
+    pub fn merge(&mut self, extensions: &[SearchExtension]) {
+        for extension in extensions {
+            match self.extensions.iter_mut().find(|cached| cached.name == extension.name) {
+                Some(cached) => *cached = extension.clone(),
+                None => self.extensions.push(extension.clone()),
+            }
+        }
+    }
+
+    /// Apply `params` to the cached extensions the way the registry API does
+    ///
+    /// The query matches names, descriptions and keywords case-insensitively.
+    /// Sorting by creation date uses the update date, which is all the cache has.
+    pub fn search(&self, params: &SearchParams) -> Vec<SearchExtension> {
+        let query = params.query.as_deref().map(str::to_lowercase);
+        let contains = |text: &str, query: &str| text.to_lowercase().contains(query);
+
+        let mut results: Vec<SearchExtension> = self
+            .extensions
+            .iter()
+            .filter(|ext| {
+                query.as_deref().is_none_or(|q| {
+                    contains(&ext.name, q)
+                        || contains(&ext.description, q)
+                        || ext.keywords.iter().any(|keyword| contains(keyword, q))
+                })
+            })
+            .filter(|ext| {
+                params
+                    .category
+                    .as_deref()
+                    .is_none_or(|category| ext.categories.iter().any(|c| c.eq_ignore_ascii_case(category)))
+            })
+            .filter(|ext| params.author.as_deref().is_none_or(|author| ext.author.eq_ignore_ascii_case(author)))
+            .filter(|ext| params.keywords.iter().all(|keyword| ext.keywords.contains(keyword)))
+            .cloned()
+            .collect();
+
+        match params.sort {
+            Some(SearchSort::Downloads) => results.sort_by_key(|ext| std::cmp::Reverse(ext.downloads)),
+            Some(SearchSort::Updated | SearchSort::Created) => {
+                results.sort_by_key(|ext| std::cmp::Reverse(ext.updated_at))
+            }
+            Some(SearchSort::Name) => results.sort_by(|a, b| a.name.cmp(&b.name)),
+            None => {}
+        }
+
+        results
+            .into_iter()
+            .skip(params.offset.unwrap_or(0) as usize)
+            .take(params.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect()
+    }
+}
+
+/// `~/.config/pm/cache/registry_<name>.json`
+pub fn get_registry_cache_path(registry: &str) -> Result<PathBuf> {
+    let file_name: String = registry
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let config_dir = crate::config::get_config_dir()?;
+    Ok(config_dir.join("cache").join(format!("registry_{}.json", file_name)))
+}
+
+/// Cached index of `registry`, if one was saved and can be read
+pub async fn load_registry_cache(registry: &str) -> Option<RegistryCache> {
+    let content = tokio::fs::read_to_string(get_registry_cache_path(registry).ok()?).await.ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub async fn save_registry_cache(registry: &str, cache: &RegistryCache) -> Result<()> {
+    let path = get_registry_cache_path(registry)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await
+            .context("Failed to create cache directory")?;
+    }
+    let content = serde_json::to_string_pretty(cache)?;
+    tokio::fs::write(&path, content).await
+        .context("Failed to write registry cache")?;
+    Ok(())
+}
+
+/// Add the results of a live search to the registry's cache
+pub async fn cache_search_results(registry: &str, extensions: &[SearchExtension]) -> Result<()> {
+    let mut cache = load_registry_cache(registry).await.unwrap_or_default();
+    cache.merge(extensions);
+    save_registry_cache(registry, &cache).await
 }
 
 /// Registry manager for handling multiple registries
@@ -422,6 +748,25 @@ impl RegistryManager {
         Ok(RegistryClient::new(config.clone()))
     }
     
+    /// Get a client for the registry an extension was downloaded from
+    ///
+    /// Matches the host of `source` (the recorded tarball URL) against the configured
+    /// registries and falls back to the default registry.
+    pub fn get_client_for_source(&self, source: &str) -> Result<RegistryClient> {
+        self.get_client(self.registry_name_for_source(source))
+    }
+
+    /// Name of the configured registry whose host matches a download URL
+    pub fn registry_name_for_source(&self, source: &str) -> Option<&str> {
+        let source_url = Url::parse(source).ok()?;
+        let host = source_url.host_str()?;
+
+        self.registries
+            .iter()
+            .find(|(_, config)| config.url.host_str() == Some(host))
+            .map(|(name, _)| name.as_str())
+    }
+    
     /// List all registries
     pub fn list_registries(&self) -> Vec<(&String, &RegistryConfig)> {
         self.registries.iter().collect()
@@ -478,6 +823,162 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
     
+    #[test]
+    fn test_registry_cache_search() {
+        let extension = |name: &str, downloads: u64, keywords: &[&str]| SearchExtension {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            description: format!("The {} extension", name),
+            author: "acme".to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            categories: vec!["git".to_string()],
+            downloads,
+            updated_at: chrono::Utc::now(),
+        };
+        let now = chrono::Utc::now();
+        let mut cache = RegistryCache::new(
+            vec![extension("hooks", 10, &["git"]), extension("deploy", 50, &["ci"])],
+            now - chrono::Duration::hours(30),
+        );
+        assert!(!cache.is_fresh(24, now));
+        assert!(cache.is_fresh(u64::MAX, now));
+
+        // Partial search results do not make a stale index fresh
+        cache.merge(&[extension("hooks", 99, &["git"]), extension("lint", 5, &["ci"])]);
+        assert!(!cache.is_fresh(24, now));
+        assert_eq!(cache.extensions.len(), 3);
+        assert!(!RegistryCache::default().is_fresh(u64::MAX, now));
+
+        let names = |params: &SearchParams| {
+            cache.search(params).into_iter().map(|ext| ext.name).collect::<Vec<_>>()
+        };
+        let ci = SearchParams {
+            query: Some("CI".to_string()),
+            sort: Some(SearchSort::Downloads),
+            ..Default::default()
+        };
+        assert_eq!(names(&ci), ["deploy", "lint"]);
+        let popular = SearchParams {
+            sort: Some(SearchSort::Downloads),
+            limit: Some(1),
+            author: Some("ACME".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(names(&popular), ["hooks"]);
+        let other_category = SearchParams {
+            category: Some("docs".to_string()),
+            ..Default::default()
+        };
+        assert!(names(&other_category).is_empty());
+    }
+
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_only_retries_server_errors() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RegistryClient::new(RegistryConfig {
+            name: "test".to_string(),
+            url: Url::parse(&server.uri()).unwrap(),
+            token: None,
+            default: true,
+        });
+        let response = client.get_with_retry(server.uri()).await.unwrap();
+        assert_eq!(response.status(), 404);
+
+        server.reset().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = client.with_retry(false);
+        assert!(client.get_with_retry(server.uri()).await.is_err());
+
+        // An invalid URL fails the same way on every attempt
+        let attempts = std::sync::atomic::AtomicUsize::new(0);
+        let result = retry_with_backoff(true, || async {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(reqwest::Client::new().get("not a url").send().await?)
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.into_inner(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_fetches_every_page() {
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let page = |names: std::ops::Range<u32>| SearchResult {
+            extensions: names
+                .map(|i| SearchExtension {
+                    name: format!("ext-{}", i),
+                    version: "1.0.0".to_string(),
+                    description: String::new(),
+                    author: "acme".to_string(),
+                    keywords: vec![],
+                    categories: vec![],
+                    downloads: u64::from(i),
+                    updated_at: chrono::Utc::now(),
+                })
+                .collect(),
+            total: u64::from(INDEX_PAGE_SIZE) + 1,
+            facets: SearchFacets {
+                categories: HashMap::new(),
+                licenses: HashMap::new(),
+                authors: HashMap::new(),
+            },
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("q", "ext"))
+            .and(query_param("offset", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page(0..INDEX_PAGE_SIZE)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("q", "ext"))
+            .and(query_param("offset", INDEX_PAGE_SIZE.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(page(INDEX_PAGE_SIZE..INDEX_PAGE_SIZE + 1)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RegistryClient::new(RegistryConfig {
+            name: "test".to_string(),
+            url: Url::parse(&server.uri()).unwrap(),
+            token: None,
+            default: true,
+        });
+        let params = SearchParams {
+            query: Some("ext".to_string()),
+            limit: Some(5),
+            ..Default::default()
+        };
+        let results = client.search_all(&params).await.unwrap();
+        assert_eq!(results.len(), INDEX_PAGE_SIZE as usize + 1);
+    }
+
     #[test]
     fn test_search_params() {
         let params = SearchParams {
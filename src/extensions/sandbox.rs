@@ -0,0 +1,385 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus};
+
+/// Sandbox permissions declared by an extension in `manifest.yml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SandboxPolicy {
+    /// Allow outgoing network connections
+    #[serde(default)]
+    pub allow_network: bool,
+    /// Paths (besides the extension and temp directories) the extension may write to
+    #[serde(default)]
+    pub allow_paths: Vec<String>,
+}
+
+impl SandboxPolicy {
+    /// Allowed write paths with `~` expanded
+    pub fn expanded_paths(&self) -> Vec<PathBuf> {
+        self.allow_paths
+            .iter()
+            .map(|path| PathBuf::from(shellexpand::tilde(path).into_owned()))
+            .collect()
+    }
+}
+
+/// A `std::process::Command` that runs an extension under OS-level restrictions
+///
+/// - Linux: a Landlock ruleset (kernel 5.13+) denies writes outside the extension, temp and
+///   allowed directories, and a seccomp filter blocks IPv4/IPv6 sockets unless
+///   `allow_network` is set. On kernels without Landlock, writes are not restricted.
+/// - macOS: the command runs under `sandbox-exec` with a profile that denies network
+///   access and writes outside the allowed paths.
+/// - Windows: the process is placed in a job object that blocks clipboard, desktop and
+///   system setting access; job objects cannot restrict network or file access.
+/// - Other platforms: runs unsandboxed with a warning.
+pub struct SandboxedCommand {
+    command: Command,
+    confined: bool,
+}
+
+impl SandboxedCommand {
+    /// Build a sandboxed command for `program`; `extension_dir` is always writable
+    pub fn new(program: impl AsRef<OsStr>, policy: &SandboxPolicy, extension_dir: &Path) -> Result<Self> {
+        let command = platform::sandboxed_command(program.as_ref(), policy, extension_dir)?;
+        Ok(Self { command, confined: true })
+    }
+
+    /// A plain command for `program`, used when sandboxing is disabled
+    pub fn unrestricted(program: impl AsRef<OsStr>) -> Self {
+        Self {
+            command: Command::new(program),
+            confined: false,
+        }
+    }
+
+    /// Access the wrapped command to add arguments and environment variables
+    pub fn command_mut(&mut self) -> &mut Command {
+        &mut self.command
+    }
+
+    /// Start the command without waiting for it
+    pub fn spawn(mut self) -> Result<Child> {
+        let child = self
+            .command
+            .spawn()
+            .context("Failed to run sandboxed extension")?;
+        if self.confined {
+            platform::confine_child(&child)?;
+        }
+        Ok(child)
+    }
+
+    /// Run the command and wait for it to finish
+    pub fn status(self) -> Result<ExitStatus> {
+        self.spawn()?
+            .wait()
+            .context("Failed to wait for sandboxed extension")
+    }
+}
+
+/// Print a sandbox limitation warning at most once per process
+#[cfg(not(target_os = "macos"))]
+fn warn_once(message: &str) {
+    static WARNED: std::sync::Once = std::sync::Once::new();
+    WARNED.call_once(|| crate::display::display_warning(message));
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::SandboxPolicy;
+    use anyhow::{Context, Result};
+    use landlock::{
+        path_beneath_rules, AccessFs, CompatLevel, Compatible, Ruleset, RulesetAttr, RulesetCreated,
+        RulesetCreatedAttr, ABI,
+    };
+    use seccompiler::{
+        BpfProgram, SeccompAction, SeccompCmpArgLen, SeccompCmpOp, SeccompCondition, SeccompFilter,
+        SeccompRule,
+    };
+    use std::collections::BTreeMap;
+    use std::ffi::OsStr;
+    use std::os::unix::process::CommandExt;
+    use std::path::{Path, PathBuf};
+    use std::process::{Child, Command};
+
+    pub fn sandboxed_command(program: &OsStr, policy: &SandboxPolicy, extension_dir: &Path) -> Result<Command> {
+        let mut command = Command::new(program);
+
+        let mut writable = vec![
+            extension_dir.to_path_buf(),
+            std::env::temp_dir(),
+            PathBuf::from("/dev"),
+        ];
+        writable.extend(policy.expanded_paths());
+        let mut ruleset = write_restriction(&writable);
+
+        let network_filter = if policy.allow_network {
+            None
+        } else {
+            Some(deny_network_filter()?)
+        };
+
+        // SAFETY: the closure only calls landlock's restrict_self and seccompiler::apply_filter,
+        // which issue prctl/landlock/seccomp syscalls on a ruleset and filter built before fork.
+        unsafe {
+            command.pre_exec(move || {
+                if let Some(ruleset) = ruleset.take() {
+                    ruleset.restrict_self().map_err(std::io::Error::other)?;
+                }
+                if let Some(program) = &network_filter {
+                    seccompiler::apply_filter(program).map_err(std::io::Error::other)?;
+                }
+                Ok(())
+            });
+        }
+
+        Ok(command)
+    }
+
+    /// The Landlock ruleset and seccomp filter are applied before exec
+    pub fn confine_child(_child: &Child) -> Result<()> {
+        Ok(())
+    }
+
+    /// Landlock ruleset that only allows writes beneath `writable`
+    ///
+    /// Returns `None` (after a warning) when the kernel does not support Landlock.
+    pub(super) fn write_restriction(writable: &[PathBuf]) -> Option<RulesetCreated> {
+        let ruleset = Ruleset::default()
+            .set_compatibility(CompatLevel::HardRequirement)
+            .handle_access(AccessFs::from_write(ABI::V1))
+            .and_then(|ruleset| {
+                ruleset
+                    .set_compatibility(CompatLevel::BestEffort)
+                    .handle_access(AccessFs::from_write(ABI::V5))
+            })
+            .and_then(|ruleset| ruleset.create())
+            .and_then(|ruleset| ruleset.add_rules(path_beneath_rules(writable, AccessFs::from_write(ABI::V5))));
+
+        match ruleset {
+            Ok(ruleset) => Some(ruleset),
+            Err(e) => {
+                super::warn_once(&format!(
+                    "Landlock is unavailable ({}); extension filesystem writes are not restricted",
+                    e
+                ));
+                None
+            }
+        }
+    }
+
+    /// Seccomp filter that makes socket(AF_INET | AF_INET6, ...) fail with EACCES
+    fn deny_network_filter() -> Result<BpfProgram> {
+        let family_rule = |family: i32| -> Result<SeccompRule> {
+            let condition = SeccompCondition::new(
+                0,
+                SeccompCmpArgLen::Dword,
+                SeccompCmpOp::Eq,
+                family as u64,
+            )?;
+            Ok(SeccompRule::new(vec![condition])?)
+        };
+
+        let mut rules = BTreeMap::new();
+        rules.insert(
+            libc::SYS_socket,
+            vec![family_rule(libc::AF_INET)?, family_rule(libc::AF_INET6)?],
+        );
+
+        let filter = SeccompFilter::new(
+            rules,
+            SeccompAction::Allow,
+            SeccompAction::Errno(libc::EACCES as u32),
+            std::env::consts::ARCH
+                .try_into()
+                .context("Seccomp is not supported on this architecture")?,
+        )?;
+
+        filter.try_into().context("Failed to compile seccomp filter")
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::SandboxPolicy;
+    use anyhow::Result;
+    use std::ffi::OsStr;
+    use std::path::Path;
+    use std::process::{Child, Command};
+
+    pub fn sandboxed_command(program: &OsStr, policy: &SandboxPolicy, extension_dir: &Path) -> Result<Command> {
+        let mut command = Command::new("sandbox-exec");
+        command.arg("-p").arg(sandbox_profile(policy, extension_dir)).arg(program);
+        Ok(command)
+    }
+
+    /// `sandbox-exec` applies the profile before exec
+    pub fn confine_child(_child: &Child) -> Result<()> {
+        Ok(())
+    }
+
+    /// Seatbelt profile: allow everything except network and writes outside allowed paths
+    fn sandbox_profile(policy: &SandboxPolicy, extension_dir: &Path) -> String {
+        let mut writable = vec![
+            extension_dir.to_path_buf(),
+            std::env::temp_dir(),
+            Path::new("/dev").to_path_buf(),
+        ];
+        writable.extend(policy.expanded_paths());
+
+        let mut profile = String::from("(version 1)\n(allow default)\n");
+        if !policy.allow_network {
+            profile.push_str("(deny network*)\n");
+        }
+        profile.push_str("(deny file-write*)\n(allow file-write*");
+        for path in writable {
+            let path = path.display().to_string().replace('\\', "\\\\").replace('"', "\\\"");
+            profile.push_str(&format!(" (subpath \"{}\")", path));
+        }
+        profile.push_str(")\n");
+        profile
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::SandboxPolicy;
+    use anyhow::Result;
+    use std::ffi::OsStr;
+    use std::os::windows::io::AsRawHandle;
+    use std::path::Path;
+    use std::process::{Child, Command};
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectBasicUIRestrictions, SetInformationJobObject,
+        JOBOBJECT_BASIC_UI_RESTRICTIONS, JOB_OBJECT_UILIMIT_DESKTOP, JOB_OBJECT_UILIMIT_DISPLAYSETTINGS,
+        JOB_OBJECT_UILIMIT_EXITWINDOWS, JOB_OBJECT_UILIMIT_GLOBALATOMS, JOB_OBJECT_UILIMIT_HANDLES,
+        JOB_OBJECT_UILIMIT_READCLIPBOARD, JOB_OBJECT_UILIMIT_SYSTEMPARAMETERS, JOB_OBJECT_UILIMIT_WRITECLIPBOARD,
+    };
+
+    pub fn sandboxed_command(program: &OsStr, policy: &SandboxPolicy, _extension_dir: &Path) -> Result<Command> {
+        if !policy.allow_network || !policy.allow_paths.is_empty() {
+            super::warn_once(
+                "Extension sandbox on Windows only restricts desktop access; network and filesystem are not restricted",
+            );
+        }
+        Ok(Command::new(program))
+    }
+
+    /// Place `child` in a job object with UI restrictions
+    ///
+    /// The job handle is closed right away; the restrictions stay in place for as long as
+    /// the job has processes, and children of the extension inherit the job.
+    pub fn confine_child(child: &Child) -> Result<()> {
+        // SAFETY: plain Win32 calls on a job handle owned here and the child's process handle,
+        // which stays valid for the lifetime of `child`.
+        unsafe {
+            let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+            if job.is_null() {
+                return Err(std::io::Error::last_os_error().into());
+            }
+
+            let restrictions = JOBOBJECT_BASIC_UI_RESTRICTIONS {
+                UIRestrictionsClass: JOB_OBJECT_UILIMIT_DESKTOP
+                    | JOB_OBJECT_UILIMIT_DISPLAYSETTINGS
+                    | JOB_OBJECT_UILIMIT_EXITWINDOWS
+                    | JOB_OBJECT_UILIMIT_GLOBALATOMS
+                    | JOB_OBJECT_UILIMIT_HANDLES
+                    | JOB_OBJECT_UILIMIT_READCLIPBOARD
+                    | JOB_OBJECT_UILIMIT_SYSTEMPARAMETERS
+                    | JOB_OBJECT_UILIMIT_WRITECLIPBOARD,
+            };
+            let confined = SetInformationJobObject(
+                job,
+                JobObjectBasicUIRestrictions,
+                &restrictions as *const JOBOBJECT_BASIC_UI_RESTRICTIONS as *const std::ffi::c_void,
+                std::mem::size_of::<JOBOBJECT_BASIC_UI_RESTRICTIONS>() as u32,
+            ) != 0
+                && AssignProcessToJobObject(job, child.as_raw_handle() as _) != 0;
+            let error = std::io::Error::last_os_error();
+            CloseHandle(job);
+
+            if !confined {
+                return Err(anyhow::Error::new(error).context("Failed to confine extension in a job object"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+mod platform {
+    use super::SandboxPolicy;
+    use anyhow::Result;
+    use std::ffi::OsStr;
+    use std::path::Path;
+    use std::process::{Child, Command};
+
+    pub fn sandboxed_command(program: &OsStr, _policy: &SandboxPolicy, _extension_dir: &Path) -> Result<Command> {
+        super::warn_once("Extension sandboxing is not supported on this platform; running without restrictions");
+        Ok(Command::new(program))
+    }
+
+    pub fn confine_child(_child: &Child) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_policy_defaults_deny_network() {
+        let policy: SandboxPolicy = serde_yaml::from_str("allow_paths: [\"~/notes\"]").unwrap();
+        assert!(!policy.allow_network);
+        assert_eq!(policy.allow_paths, vec!["~/notes".to_string()]);
+        assert!(!policy.expanded_paths()[0].starts_with("~"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_network_blocked_in_sandbox() {
+        let policy = SandboxPolicy::default();
+        let temp = tempfile::tempdir().unwrap();
+        let mut command = SandboxedCommand::new("python3", &policy, temp.path()).unwrap();
+        command.command_mut().args([
+            "-c",
+            "import socket,sys\ntry:\n socket.socket(socket.AF_INET)\nexcept OSError:\n sys.exit(3)",
+        ]);
+
+        let status = command
+            .status()
+            .expect("python3 is required to run this test");
+        assert_eq!(status.code(), Some(3));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_writes_outside_allowed_paths_blocked_in_sandbox() {
+        if platform::write_restriction(&[]).is_none() {
+            return; // Landlock is not available on this kernel
+        }
+        let extension_dir = tempfile::tempdir().unwrap();
+        // Outside the temp dir, which the sandbox always allows
+        let outside = tempfile::tempdir_in(env!("CARGO_MANIFEST_DIR")).unwrap();
+
+        let run = |target: &Path| {
+            let mut command =
+                SandboxedCommand::new("sh", &SandboxPolicy::default(), extension_dir.path()).unwrap();
+            command
+                .command_mut()
+                .args(["-c", "echo ok > \"$1\"", "sh"])
+                .arg(target.join("file"))
+                .stderr(std::process::Stdio::null());
+            command.status().unwrap().success()
+        };
+
+        assert!(run(extension_dir.path()));
+        assert!(!run(outside.path()));
+        assert!(!outside.path().join("file").exists());
+    }
+}
@@ -0,0 +1,298 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Manifest file describing a remote extension template
+pub const TEMPLATE_MANIFEST_FILE: &str = "pm-template.yml";
+
+/// Template manifest (`pm-template.yml`) at the root of a template repository
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateManifest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Variables substituted into `{{variable}}` placeholders
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
+}
+
+/// A template variable the user is prompted for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+    /// Prompt text (defaults to the variable name)
+    pub prompt: Option<String>,
+    /// Default value; may itself reference other variables
+    pub default: Option<String>,
+}
+
+/// Replace `{{variable}}` placeholders with values from `variables`
+///
+/// Whitespace inside the braces is ignored. Unknown placeholders are left as-is so
+/// templates can contain unrelated `{{ }}` syntax (e.g. GitHub Actions expressions).
+pub fn render(template: &str, variables: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            output.push_str(&rest[start..]);
+            return output;
+        };
+
+        let key = after_open[..end].trim();
+        match variables.get(key) {
+            Some(value) => output.push_str(value),
+            None => output.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after_open[end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Options for creating an extension from a remote template
+pub struct RemoteTemplateOptions {
+    pub url: String,
+    pub name: String,
+    pub directory: Option<PathBuf>,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    pub non_interactive: bool,
+}
+
+/// Clone a template repository and render it into the target directory, returning that directory
+pub async fn create_from_template(options: RemoteTemplateOptions) -> Result<PathBuf> {
+    let target_dir = options
+        .directory
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default().join(&options.name));
+    if target_dir.exists() {
+        return Err(anyhow::anyhow!("Directory already exists: {}", target_dir.display()));
+    }
+
+    println!("📥 Cloning template: {}", options.url);
+    let checkout = tempfile::tempdir().context("Failed to create temporary directory")?;
+    git2::Repository::clone(&options.url, checkout.path())
+        .with_context(|| format!("Failed to clone template repository: {}", options.url))?;
+
+    let manifest_path = checkout.path().join(TEMPLATE_MANIFEST_FILE);
+    let manifest = load_template_manifest(&manifest_path)?;
+    if let Some(name) = &manifest.name {
+        println!("🧩 Template: {}", name);
+    }
+    if let Some(description) = &manifest.description {
+        println!("   {}", description);
+    }
+    println!();
+
+    let variables = collect_variables(&manifest, &options)?;
+    let files = render_directory(checkout.path(), &target_dir, &variables)?;
+
+    println!();
+    println!("✅ Extension '{}' created from template!", options.name);
+    println!("📁 Created in: {}", target_dir.display());
+    println!("📝 Files generated: {}", files.len());
+    println!();
+    println!("🎯 Next steps:");
+    println!("  1. cd {}", target_dir.display());
+    println!("  2. pm ext install . --local         # Install locally for testing");
+
+    Ok(target_dir)
+}
+
+fn load_template_manifest(path: &Path) -> Result<TemplateManifest> {
+    let content = std::fs::read_to_string(path).with_context(|| {
+        format!("Template repository has no {} at its root", TEMPLATE_MANIFEST_FILE)
+    })?;
+    serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse template manifest: {}", path.display()))
+}
+
+/// Built-in variables plus the template's own, prompting for values unless non-interactive
+fn collect_variables(
+    manifest: &TemplateManifest,
+    options: &RemoteTemplateOptions,
+) -> Result<HashMap<String, String>> {
+    let mut variables = HashMap::new();
+    variables.insert("project_name".to_string(), options.name.clone());
+    variables.insert("name".to_string(), options.name.clone());
+    if let Some(author) = &options.author {
+        variables.insert("author".to_string(), author.clone());
+    }
+    if let Some(description) = &options.description {
+        variables.insert("description".to_string(), description.clone());
+    }
+
+    for variable in &manifest.variables {
+        if variables.contains_key(&variable.name) {
+            continue;
+        }
+
+        let default = match (&variable.default, variable.name.as_str()) {
+            (Some(default), _) => render(default, &variables),
+            (None, "author") => super::creation::get_git_name().unwrap_or_default(),
+            (None, _) => String::new(),
+        };
+
+        let value = if options.non_interactive {
+            default
+        } else {
+            let prompt = variable.prompt.as_deref().unwrap_or(&variable.name);
+            if default.is_empty() {
+                print!("{}: ", prompt);
+            } else {
+                print!("{} ({}): ", prompt, default);
+            }
+            let input = super::creation::read_user_input()?;
+            let input = input.trim();
+            if input.is_empty() {
+                default
+            } else {
+                input.to_string()
+            }
+        };
+
+        variables.insert(variable.name.clone(), value);
+    }
+
+    Ok(variables)
+}
+
+/// Copy `source` into `target`, rendering file contents and path names
+///
+/// The `.git` directory and the template manifest are skipped. Binary files are copied unchanged.
+/// Symlinks are rejected so a template cannot pull files from outside its own directory.
+fn render_directory(
+    source: &Path,
+    target: &Path,
+    variables: &HashMap<String, String>,
+) -> Result<Vec<PathBuf>> {
+    let mut created = Vec::new();
+
+    let entries = WalkDir::new(source)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git");
+
+    for entry in entries {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        if relative == Path::new(TEMPLATE_MANIFEST_FILE) {
+            continue;
+        }
+        if entry.path_is_symlink() {
+            anyhow::bail!(
+                "Template contains a symbolic link, which is not allowed: {}",
+                relative.display()
+            );
+        }
+
+        let rendered = PathBuf::from(render(&relative.to_string_lossy(), variables));
+        if !rendered.components().all(|c| matches!(c, Component::Normal(_))) {
+            anyhow::bail!(
+                "Template path '{}' renders to '{}', which is outside the target directory",
+                relative.display(),
+                rendered.display()
+            );
+        }
+        let destination = target.join(rendered);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&destination)
+                .with_context(|| format!("Failed to create directory: {}", destination.display()))?;
+            continue;
+        }
+
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let bytes = std::fs::read(entry.path())
+            .with_context(|| format!("Failed to read template file: {}", entry.path().display()))?;
+        let content = match String::from_utf8(bytes) {
+            Ok(text) => render(&text, variables).into_bytes(),
+            Err(e) => e.into_bytes(),
+        };
+        std::fs::write(&destination, content)
+            .with_context(|| format!("Failed to write file: {}", destination.display()))?;
+
+        // Keep executable bits on scripts
+        #[cfg(unix)]
+        std::fs::set_permissions(&destination, entry.metadata()?.permissions())?;
+
+        println!("  - {}", destination.strip_prefix(target).unwrap_or(&destination).display());
+        created.push(destination);
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_replaces_known_placeholders() {
+        let variables = HashMap::from([
+            ("project_name".to_string(), "pm-docker".to_string()),
+            ("author".to_string(), "Jane".to_string()),
+        ]);
+
+        assert_eq!(
+            render("# {{project_name}} by {{ author }}", &variables),
+            "# pm-docker by Jane"
+        );
+        assert_eq!(render("${{ github.token }}", &variables), "${{ github.token }}");
+        assert_eq!(render("unterminated {{author", &variables), "unterminated {{author");
+    }
+
+    #[test]
+    fn test_render_directory_renders_names_and_skips_manifest() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(source.path().join("src")).unwrap();
+        std::fs::write(source.path().join("src/{{name}}.sh"), "echo {{name}}\n").unwrap();
+        std::fs::write(source.path().join(TEMPLATE_MANIFEST_FILE), "variables: []\n").unwrap();
+
+        let variables = HashMap::from([("name".to_string(), "hello".to_string())]);
+        let created = render_directory(source.path(), &target.path().join("out"), &variables).unwrap();
+
+        assert_eq!(created.len(), 1);
+        let rendered = std::fs::read_to_string(target.path().join("out/src/hello.sh")).unwrap();
+        assert_eq!(rendered, "echo hello\n");
+        assert!(!target.path().join("out").join(TEMPLATE_MANIFEST_FILE).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_render_directory_rejects_symlinks() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let secret = tempfile::tempdir().unwrap();
+        std::fs::write(secret.path().join("id_rsa"), "secret").unwrap();
+        std::os::unix::fs::symlink(secret.path(), source.path().join("ssh")).unwrap();
+
+        let result = render_directory(source.path(), &target.path().join("out"), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("symbolic link"));
+        assert!(!target.path().join("out/ssh/id_rsa").exists());
+    }
+
+    #[test]
+    fn test_render_directory_rejects_paths_outside_target() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("{{name}}.sh"), "echo hi\n").unwrap();
+
+        let variables = HashMap::from([("name".to_string(), "../escape".to_string())]);
+        let result = render_directory(source.path(), &target.path().join("out"), &variables);
+
+        assert!(result.unwrap_err().to_string().contains("outside the target directory"));
+        assert!(!target.path().join("escape.sh").exists());
+    }
+}
@@ -12,6 +12,7 @@ pub mod constants;
 pub mod display;
 pub mod error;
 pub mod extensions;
+pub mod scaffold;
 pub mod shell_integration;
 pub mod tag_commands;
 pub mod utils;
@@ -20,11 +21,11 @@ pub mod validation;
 pub use error::PmError;
 
 pub use commands::config::ExportFormat;
-pub use commands::{backup as backup_cmd, config as config_cmd, init, project, status, tag};
+pub use commands::{backup as backup_cmd, config as config_cmd, import, init, project, status, tag};
 pub use config::load_config;
 pub use constants::*;
 pub use display::display_error;
-pub use error::handle_error;
+pub use error::{handle_error, is_cancellation};
 
 pub fn handle_config_error(e: anyhow::Error) -> ! {
     if e.to_string().contains("Configuration file not found") {
@@ -53,17 +54,75 @@ pub enum Commands {
     /// Add a new project to manage (alias: a)
     #[command(alias = "a")]
     Add {
-        /// Path to the project directory
-        path: PathBuf,
+        /// Path to the project directory (the clone target with --from-url)
+        #[arg(required_unless_present_any = ["batch", "from_url"])]
+        path: Option<PathBuf>,
 
         #[arg(short, long)]
         name: Option<String>,
 
+        /// Tags for the new project (skips the interactive tag prompt)
         #[arg(short, long, value_delimiter = ',')]
         tags: Vec<String>,
 
         #[arg(short, long)]
         description: Option<String>,
+
+        /// Use the first heading or paragraph of README.md as the description
+        #[arg(long, conflicts_with = "description")]
+        detect_description: bool,
+
+        /// Add projects listed in a file, one per line: path[<TAB>name[<TAB>tag1,tag2]]
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["path", "name", "tags", "description", "detect_description"])]
+        batch: Option<PathBuf>,
+
+        /// Succeed without changes when the path is already tracked (for scripts)
+        #[arg(long, conflicts_with = "batch")]
+        if_not_exists: bool,
+
+        /// With --if-not-exists, add any new --tags to the already tracked project
+        #[arg(long, requires = "if_not_exists")]
+        update_tags: bool,
+
+        /// Project priority from 1 to 5 (5 is most important, default 3)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=5), conflicts_with_all = ["batch", "scan_depth"])]
+        priority: Option<u8>,
+
+        /// Scan the path up to this many levels deep and add every project found
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..), conflicts_with_all = ["name", "description", "batch", "if_not_exists"])]
+        scan_depth: Option<u32>,
+
+        /// Clone a Git repository (HTTPS or SSH URL) and add it (default target: ./<owner>/<repo>)
+        #[arg(long, value_name = "URL", conflicts_with_all = ["batch", "scan_depth", "if_not_exists", "description", "detect_description"])]
+        from_url: Option<String>,
+
+        /// Where to take the project name from (default: settings.default_name_strategy)
+        #[arg(long, value_name = "STRATEGY", alias = "infer-name", conflicts_with_all = ["name", "batch", "from_url"])]
+        infer_name_from: Option<utils::NameStrategy>,
+
+        /// Tag the project with its detected languages (default: settings.auto_detect_language)
+        #[arg(long, conflicts_with_all = ["batch", "from_url"])]
+        detect_language: bool,
+    },
+
+    /// Create a new project directory, optionally initialize Git, and add it to PM
+    Create {
+        /// Path of the directory to create (must not exist)
+        path: PathBuf,
+
+        #[arg(short, long)]
+        name: Option<String>,
+
+        #[arg(short, long, value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Initialize a Git repository in the new directory
+        #[arg(long)]
+        git: bool,
+
+        /// Scaffold the project from a template (basic, rust, node, python)
+        #[arg(long)]
+        template: Option<String>,
     },
 
     /// Clone repositories from GitHub (interactive browse or direct clone) (alias: cl)
@@ -75,6 +134,26 @@ pub enum Commands {
         /// Target directory (defaults to <current_dir>/<owner>/<repo>)
         #[arg(short, long)]
         directory: Option<PathBuf>,
+
+        /// Clone only, without adding the repository to PM
+        #[arg(long, conflicts_with_all = ["name", "tags"])]
+        no_add: bool,
+
+        /// Tags for the added project (comma-separated, defaults to 'github')
+        #[arg(short, long, value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Project name (defaults to the repository name)
+        #[arg(short, long, requires = "repo")]
+        name: Option<String>,
+
+        /// Clone from GitLab (settings.gitlab_url, defaults to gitlab.com)
+        #[arg(long)]
+        gitlab: bool,
+
+        /// GitLab group to browse instead of your own projects
+        #[arg(long, requires = "gitlab", conflicts_with = "repo")]
+        org: Option<String>,
     },
 
     /// List managed projects (alias: ls)
@@ -88,22 +167,345 @@ pub enum Commands {
         #[arg(long, value_delimiter = ',')]
         tags_any: Vec<String>,
 
-        /// Show only projects updated within the last time period (e.g., 7d, 2w, 1m, 1y)
+        /// Show only projects updated within the last time period or since a date (e.g., 7d, 2w, 1y, 2025-01-01)
         #[arg(short = 'r', long)]
         recent: Option<String>,
 
+        /// Show only projects NOT accessed within the time period or since a date, including never-accessed ones (e.g., 30d, 2025-01-01)
+        #[arg(long)]
+        since_last_access: Option<String>,
+
+        /// Show only projects active within the time period or since a date, using the last commit for Git repositories and the directory mtime otherwise (e.g., 7d, 2025-01-01)
+        #[arg(long)]
+        since_modified: Option<String>,
+
+        /// Show only projects without a Git commit in more than DAYS days (or none at all), oldest first
+        #[arg(long, value_name = "DAYS", conflicts_with = "recent")]
+        stale: Option<u32>,
+
+        /// Show only Git repositories
+        #[arg(long, conflicts_with = "no_git")]
+        git_only: bool,
+
+        /// Show only projects that are not Git repositories
+        #[arg(long)]
+        no_git: bool,
+
+        /// Show only projects whose directory exists
+        #[arg(long, conflicts_with = "not_exists")]
+        exists: bool,
+
+        /// Show only projects whose directory is missing
+        #[arg(long)]
+        not_exists: bool,
+
+        /// Show only projects without a description
+        #[arg(long)]
+        missing_description: bool,
+
+        /// Show only projects without tags
+        #[arg(long)]
+        missing_tags: bool,
+
         /// Limit the number of results
         #[arg(short = 'l', long)]
         limit: Option<usize>,
 
+        /// Skip the first N results (use with --limit for pagination)
+        #[arg(long, conflicts_with = "page")]
+        offset: Option<usize>,
+
+        /// Page number starting at 1 (shorthand for --offset (page-1)*limit)
+        #[arg(long, requires = "limit")]
+        page: Option<usize>,
+
         /// Show verbose information
         #[arg(short = 'v', long)]
         verbose: bool,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: project::ListFormat,
+
+        /// Pick a project interactively and open it in your editor
+        #[arg(short = 'i', long)]
+        interactive: bool,
+
+        /// Group projects in the output
+        #[arg(long, value_enum)]
+        group_by: Option<project::GroupBy>,
+
+        /// Sort order (priority: highest first, then by last commit)
+        #[arg(long, value_enum, default_value = "updated")]
+        sort: project::ListSort,
+
+        /// Print only the number of matching projects
+        #[arg(long, conflicts_with_all = ["format", "interactive", "group_by"])]
+        count: bool,
+
+        /// Print only project names, one per line (a JSON array with --format json)
+        #[arg(long, conflicts_with_all = ["verbose", "interactive", "group_by", "count"])]
+        brief: bool,
+
+        /// Show one entry per filesystem path (the most recently updated)
+        #[arg(long, conflicts_with = "show_duplicates")]
+        unique_paths: bool,
+
+        /// Show only projects whose path is registered more than once
+        #[arg(long)]
+        show_duplicates: bool,
+
+        /// Show each project's short ID (use with `pm info --id`)
+        #[arg(long)]
+        show_id: bool,
+
+        /// Show each project's Git remote URL
+        #[arg(long)]
+        show_remote_url: bool,
+
+        /// Show only the last N components of each path (1 shows just the directory name)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+        path_depth: Option<u64>,
+
+        /// Show only projects with this access frequency
+        #[arg(long, value_enum)]
+        access_frequency: Option<utils::AccessFrequency>,
+
+        /// Keep running and redraw the list whenever the configuration changes
+        #[arg(long, conflicts_with_all = ["interactive", "count"])]
+        watch: bool,
+
+        /// While watching, also check for changes every N seconds (for NFS/FUSE)
+        #[arg(long, value_name = "SECONDS", requires = "watch")]
+        interval: Option<u64>,
+    },
+
+    /// Copy a project's path (or remote URL / name) to the clipboard
+    CopyPath {
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
+
+        /// Copy the Git remote URL (origin) instead of the path
+        #[arg(long, conflicts_with = "copy_name")]
+        url: bool,
+
+        /// Copy the project name instead of the path
+        #[arg(long = "name")]
+        copy_name: bool,
+    },
+
+    /// Watch a project's Git repository and report commits, branch changes and dirty state
+    Watch {
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
+
+        /// Events to report (comma-separated, default: all)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        events: Vec<project::WatchEvent>,
+    },
+
+    /// Show full details of a project
+    ///
+    /// Includes the last 5 access times on this machine. PM does not record sessions, notes
+    /// or which extensions act on a project, so those are not shown.
+    Info {
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
+
+        /// Look the project up by UUID or a unique prefix of it instead of by name
+        #[arg(long, conflicts_with = "name")]
+        id: Option<String>,
+
+        /// Output format (text, json)
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+
+    /// Find projects registered twice (same path or same name) and resolve them
+    Dedup {
+        /// Show duplicate groups without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Print a project's Git remote URL
+    Url {
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
+
+        /// URL style
+        #[arg(long, value_enum, default_value = "https")]
+        format: utils::UrlFormat,
+
+        /// Remote to show (defaults to origin, or every remote if there is no origin)
+        #[arg(long)]
+        remote: Option<String>,
     },
 
     /// Switch to a project directory (alias: sw)
     #[command(alias = "sw")]
-    Switch { name: String },
+    Switch {
+        /// Project name (optional, opens an interactive picker when omitted)
+        #[arg(conflicts_with_all = ["back", "forward", "history"])]
+        name: Option<String>,
+
+        /// Go back to the previously switched-to project
+        #[arg(long, conflicts_with_all = ["forward", "history"])]
+        back: bool,
+
+        /// Go forward again after --back
+        #[arg(long, conflicts_with = "history")]
+        forward: bool,
+
+        /// Pick one of the last switched-to projects
+        #[arg(long)]
+        history: bool,
+    },
+
+    /// Open a project in an IDE or your editor
+    Open {
+        /// Project name (defaults to the project containing the current directory)
+        name: Option<String>,
+
+        /// IDE to open the project in (defaults to settings.default_ide, then your editor)
+        #[arg(long, value_enum)]
+        ide: Option<utils::Ide>,
+    },
+
+    /// Mark a project as updated and accessed without switching to it
+    Touch {
+        /// Project name (defaults to the project containing the current directory)
+        #[arg(conflicts_with = "all_in_cwd")]
+        name: Option<String>,
+
+        /// Touch every project at, above or below the current directory (mono-repos)
+        #[arg(long)]
+        all_in_cwd: bool,
+    },
+
+    /// Print a project's path (for use in shell aliases)
+    Path {
+        /// Project name
+        name: String,
+
+        /// Print the path relative to the current directory
+        #[arg(long, conflicts_with = "home")]
+        relative: bool,
+
+        /// Abbreviate the home directory to ~
+        #[arg(long)]
+        home: bool,
+
+        /// Print the parent directory instead of the project directory
+        #[arg(long)]
+        parent: bool,
+    },
+
+    /// Set or edit a project's description (alias: desc)
+    #[command(alias = "desc")]
+    Description {
+        #[command(subcommand)]
+        action: DescriptionAction,
+    },
+
+    /// Set a project's priority (1-5, 5 is most important)
+    Priority {
+        /// Project name
+        name: String,
+
+        /// New priority
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=5))]
+        priority: u8,
+    },
+
+    /// Set or clear a project's color label
+    Color {
+        /// Project name
+        name: String,
+
+        /// Color name (black, red, green, yellow, blue, magenta, cyan, white) or hex code (#ff6600)
+        #[arg(required_unless_present = "clear")]
+        color: Option<String>,
+
+        /// Remove the color label
+        #[arg(long, conflicts_with = "color")]
+        clear: bool,
+    },
+
+    /// Add a tag to every project whose path matches a glob pattern
+    TagFromPath {
+        /// Glob pattern matched against project paths (e.g. "*/work/*")
+        pattern: String,
+
+        /// Tag to add to matching projects
+        tag: String,
+
+        /// Show affected projects without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Add `kw:` tags from the keywords in a project's package.json, Cargo.toml or pyproject.toml
+    TagSync {
+        /// Project name (defaults to the project containing the current directory)
+        #[arg(conflicts_with = "all")]
+        name: Option<String>,
+
+        /// Sync every project
+        #[arg(long)]
+        all: bool,
+
+        /// Show the tags that would be added without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Check whether projects are behind their `origin` branch (exits 1 if any are)
+    CheckUpdate {
+        /// Project name (defaults to the project containing the current directory)
+        #[arg(conflicts_with = "all")]
+        name: Option<String>,
+
+        /// Check every Git project
+        #[arg(long)]
+        all: bool,
+
+        /// Fetch from origin before comparing
+        #[arg(long)]
+        fetch: bool,
+
+        /// Seconds to wait for each fetch before giving up
+        #[arg(long, value_name = "SECS", default_value_t = 10, requires = "fetch")]
+        timeout: u64,
+    },
+
+    /// Run a shell command in every project's directory in parallel
+    Exec {
+        /// Command to run: one argument is passed to `sh -c` (or `cmd /C` on Windows) as written,
+        /// several arguments are quoted individually
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+
+        /// Only run in projects with these tags (comma-separated, all tags must match)
+        #[arg(short = 't', long, value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Maximum number of commands running at once
+        #[arg(short = 'j', long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+
+        /// Show a live status line per project (default when stdout is a terminal)
+        #[arg(long, conflicts_with = "no_progress")]
+        progress: bool,
+
+        /// Print each project's output under a header, in name order
+        #[arg(long)]
+        no_progress: bool,
+    },
 
     /// Scan for Git repositories and add them to PM (alias: sc)
     #[command(alias = "sc")]
@@ -117,6 +519,12 @@ pub enum Commands {
         show_all: bool,
     },
 
+    /// Import projects from other tools
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+
     /// Manage project tags (alias: t)
     #[command(alias = "t")]
     Tag {
@@ -124,13 +532,21 @@ pub enum Commands {
         action: TagAction,
     },
 
-    /// Remove projects from PM (alias: rm)
+    /// Stop tracking projects in PM; files on disk are kept unless --delete-directory (alias: rm)
     #[command(alias = "rm")]
     Remove {
         /// Project name (optional for interactive mode)
         project: Option<String>,
 
-        /// Skip confirmation prompt
+        /// Remove all projects that have these tags (comma-separated, all tags must match)
+        #[arg(short = 't', long, value_delimiter = ',', conflicts_with = "project")]
+        tags: Vec<String>,
+
+        /// Also delete the project directory from disk (always asks for confirmation twice)
+        #[arg(long, conflicts_with = "tags")]
+        delete_directory: bool,
+
+        /// Skip confirmation prompt (does not apply to --delete-directory)
         #[arg(short = 'y', long)]
         yes: bool,
     },
@@ -158,6 +574,18 @@ pub enum Commands {
         /// Replace existing config with backup (non-interactive)
         #[arg(long)]
         replace: bool,
+
+        /// Migrate an existing config to the current schema version
+        #[arg(long, conflicts_with_all = ["skip", "replace"])]
+        update: bool,
+
+        /// Show which PM process holds the config lock, then exit
+        #[arg(long, conflicts_with_all = ["skip", "replace", "update"])]
+        force_unlock: bool,
+
+        /// Download the config from an HTTPS URL or github:<user>/<repo>/<path>
+        #[arg(long, value_name = "URL", conflicts_with_all = ["skip", "replace", "update", "force_unlock"])]
+        from: Option<String>,
     },
 
     /// Show current project status (for prompt integration)
@@ -169,6 +597,17 @@ pub enum Commands {
         /// Quiet mode for prompt integration
         #[arg(short, long)]
         quiet: bool,
+
+        /// Keep redrawing the status every N seconds and on file changes (default: 2)
+        #[arg(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "2")]
+        watch: Option<u64>,
+    },
+
+    /// Print the JSON Schema for projects in PM's JSON output
+    JsonSchema {
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: ExportFormat,
     },
 
     /// Manage extensions (alias: ext)
@@ -194,6 +633,25 @@ pub enum Commands {
 }
 
 
+#[derive(Subcommand)]
+pub enum ImportSource {
+    /// Recent projects of JetBrains IDEs (IntelliJ IDEA, GoLand, CLion, ...)
+    #[command(name = "jetbrains")]
+    JetBrains {
+        /// Only this product, e.g. goland or IntelliJIdea2024.1 (defaults to every installed product)
+        #[arg(long)]
+        product: Option<String>,
+
+        /// Show the projects that would be imported without adding them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum BackupAction {
     /// List all available backups
@@ -220,8 +678,32 @@ pub enum BackupAction {
         force: bool,
     },
 
+    /// Delete backups according to a retention policy
+    Rotate {
+        /// Retention policy and value: keep-n <n>, keep-days <days> or keep-size <mb>
+        /// (defaults to settings.backup_retention_policy)
+        #[arg(long, num_args = 2, value_names = ["POLICY", "VALUE"])]
+        policy: Option<Vec<String>>,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'f', long)]
+        force: bool,
+    },
+
     /// Show backup system status
-    Status,
+    Status {
+        /// Show disk space used by backups, the config file and extensions
+        #[arg(long)]
+        size: bool,
+
+        /// Show how much space `backup clean` would free
+        #[arg(long)]
+        cleanup_preview: bool,
+
+        /// Number of backups `backup clean` would keep
+        #[arg(long, default_value_t = 5, requires = "cleanup_preview")]
+        keep: usize,
+    },
 }
 
 #[derive(Subcommand)]
@@ -245,11 +727,61 @@ pub enum TagAction {
     },
     /// List all unique tags and their counts
     #[command(alias = "ls")]
-    List {},
-    /// Show tags for a specific project
+    List {
+        /// Sort order
+        #[arg(long, value_enum, default_value = "count")]
+        sort: tag_commands::TagSort,
+        /// Reverse the sort order
+        #[arg(long)]
+        reverse: bool,
+        /// Hide tags used by fewer than N projects
+        #[arg(long, value_name = "N")]
+        min_count: Option<u32>,
+        /// Hide tags used by more than N projects
+        #[arg(long, value_name = "N")]
+        max_count: Option<u32>,
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: tag::TagListFormat,
+    },
+    /// Show tags for a specific project, or a project/tag matrix with --all-projects
     Show {
         /// The name of the project (optional, defaults to current directory's project)
+        #[arg(conflicts_with = "all_projects")]
         project_name: Option<String>,
+        /// Show every project against every tag as a matrix
+        #[arg(long)]
+        all_projects: bool,
+        /// Only include tags used by at least N projects in the matrix
+        #[arg(long, value_name = "N", default_value_t = 2, requires = "all_projects")]
+        min_count: u32,
+        /// Matrix output format
+        #[arg(long, value_enum, default_value = "text", requires = "all_projects")]
+        format: tag::TagMatrixFormat,
+    },
+    /// Tag projects using rules from a YAML file
+    AutoAssign {
+        /// Rule file (list of `tag` + `condition` entries)
+        rules_file: PathBuf,
+        /// Show which projects would be tagged without changing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DescriptionAction {
+    /// Set a one-line description (an empty string clears it)
+    Set {
+        /// The name of the project
+        name: String,
+        /// The new description
+        text: String,
+    },
+    /// Edit the description in $EDITOR (markdown supported)
+    Edit {
+        /// The name of the project
+        name: String,
     },
 }
 
@@ -264,6 +796,13 @@ pub enum ConfigCommands {
     /// Validate configuration file
     Validate {},
 
+    /// Print the JSON Schema for the configuration file
+    Schema {
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: ExportFormat,
+    },
+
     /// Reset configuration to defaults
     Reset {},
 
@@ -339,6 +878,15 @@ pub enum BackupCommands {
         /// Backup name (optional, defaults to timestamp)
         #[arg(long)]
         name: Option<String>,
+        /// Note describing why the backup was made
+        #[arg(long)]
+        description: Option<String>,
+        /// Tag to classify the backup (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+        /// Store the backup gzip-compressed (.yml.gz)
+        #[arg(long)]
+        compress: bool,
     },
     /// Restore configuration from backup
     Restore {
@@ -346,18 +894,48 @@ pub enum BackupCommands {
         name: String,
     },
     /// List all available backups
-    List {},
+    List {
+        /// Only show backups with this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
     /// Delete a backup
     Delete {
         /// Backup name
         name: String,
     },
+    /// Delete all but the most recent backups
+    Clean {
+        /// Number of most recent backups to keep
+        #[arg(long, default_value = "5")]
+        keep: usize,
+        /// Never delete backups with this tag (repeatable)
+        #[arg(long, value_name = "TAG")]
+        keep_tagged: Vec<String>,
+        /// Skip confirmation prompt
+        #[arg(short = 'f', long)]
+        force: bool,
+    },
+    /// Gzip-compress a backup, or all uncompressed backups
+    Compress {
+        /// Backup name (optional, defaults to all uncompressed backups)
+        name: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum TemplateCommands {
     /// List available templates
-    List {},
+    List {
+        /// Output format (text, json)
+        #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+        format: String,
+    },
+    /// Show a template's settings and a preview of the changes it would make
+    Info {
+        /// Template name
+        name: String,
+    },
     /// Apply a template
     Apply {
         /// Template name
@@ -383,6 +961,7 @@ pub enum ExtensionType {
     Bash,
     Python,
     Rust,
+    Deno,
 }
 
 #[derive(Subcommand)]
@@ -391,7 +970,7 @@ pub enum ExtensionAction {
     Create {
         /// Extension name
         name: String,
-        /// Extension type (bash, python, rust)
+        /// Extension type (bash, python, rust, deno)
         #[arg(short = 't', long, value_enum)]
         ext_type: Option<ExtensionType>,
         /// Target directory (defaults to current directory)
@@ -406,6 +985,26 @@ pub enum ExtensionAction {
         /// Skip interactive prompts (use defaults)
         #[arg(long)]
         non_interactive: bool,
+        /// Scaffold from a template repository (Git URL) containing pm-template.yml
+        #[arg(long, value_name = "TEMPLATE_URL", conflicts_with = "ext_type")]
+        from_template: Option<String>,
+        /// Create a GitHub repository for the extension and push the initial commit
+        /// (requires the create-github-repo feature and an authenticated GitHub CLI)
+        #[arg(long)]
+        publish: bool,
+        /// Make the published repository private
+        #[arg(long, requires = "publish")]
+        private: bool,
+    },
+    /// Add a command stub to an existing extension
+    AddCommand {
+        /// Extension source directory (containing extension.yml)
+        extension: PathBuf,
+        /// Name of the new command
+        command_name: String,
+        /// Help text for the command
+        #[arg(short = 's', long)]
+        description: Option<String>,
     },
     /// Install an extension
     Install {
@@ -426,6 +1025,9 @@ pub enum ExtensionAction {
         /// Force reinstallation if already installed
         #[arg(long)]
         force: bool,
+        /// Fail on the first network error instead of retrying
+        #[arg(long)]
+        no_retry: bool,
     },
     /// Uninstall an extension (alias: remove, rm)
     #[command(alias = "remove", alias = "rm")]
@@ -442,16 +1044,32 @@ pub enum ExtensionAction {
         /// Show all available extensions (not just installed)
         #[arg(long)]
         all: bool,
+        /// Only show installed extensions with newer versions in their registry
+        #[arg(long, conflicts_with = "all")]
+        outdated: bool,
+        /// Output format (table, json)
+        #[arg(long, default_value = "table", value_parser = ["table", "json"], conflicts_with = "outdated")]
+        format: String,
+        /// Show each command's help, aliases and arguments
+        #[arg(short, long)]
+        verbose: bool,
     },
     /// Show extension information
     Info {
         /// Extension name
         name: String,
+        /// Output format (table, json)
+        #[arg(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
     },
     /// Update extensions
     Update {
-        /// Extension name (update all if not specified)
+        /// Extension name
+        #[arg(required_unless_present = "all")]
         name: Option<String>,
+        /// Update every extension that has a newer registry version
+        #[arg(long, conflicts_with = "name")]
+        all: bool,
     },
     /// Search for extensions
     Search {
@@ -466,12 +1084,27 @@ pub enum ExtensionAction {
         /// Filter by author
         #[arg(long)]
         author: Option<String>,
-        /// Sort results by (downloads, updated, created, name)
-        #[arg(long)]
-        sort: Option<String>,
+        /// Sort results
+        #[arg(long, value_enum)]
+        sort: Option<extensions::remote::SearchSort>,
         /// Maximum number of results
         #[arg(long)]
         limit: Option<u32>,
+        /// Hide extensions with fewer downloads than this
+        #[arg(long, value_name = "N")]
+        min_downloads: Option<u64>,
+        /// Only show results that are installed locally
+        #[arg(long, conflicts_with = "not_installed")]
+        installed: bool,
+        /// Only show results that are not installed yet
+        #[arg(long)]
+        not_installed: bool,
+        /// Fail on the first network error instead of retrying
+        #[arg(long)]
+        no_retry: bool,
+        /// Search the cached registry index instead of the network
+        #[arg(long)]
+        cached: bool,
     },
     /// Manage extension registries
     Registry {
@@ -509,10 +1142,43 @@ pub enum RegistryAction {
         /// Registry name
         name: String,
     },
-    /// Test registry connectivity
+    /// Test registry connectivity and response times
     Ping {
         /// Registry name (defaults to all registries)
         name: Option<String>,
+
+        /// Request timeout in milliseconds
+        #[arg(long, default_value = "5000")]
+        timeout: u64,
+
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add registries from a YAML file (a list of name/url/token/default entries)
+    Import {
+        /// YAML file to import
+        file: PathBuf,
+        /// Replace registries that already exist with the same name
+        #[arg(long)]
+        force: bool,
+        /// Test connectivity to each imported registry
+        #[arg(long)]
+        ping: bool,
+    },
+    /// Show or refresh the cached registry indexes used by `pm ext search --cached`
+    Cache {
+        /// Download the full index of every configured registry
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Write the configured registries as YAML (for `pm ext registry import`)
+    Export {
+        /// Output file (defaults to stdout)
+        file: Option<PathBuf>,
+        /// Include authentication tokens in the output
+        #[arg(long)]
+        include_tokens: bool,
     },
 }
 
@@ -530,6 +1196,20 @@ pub struct Project {
     #[serde(default)]
     #[schemars(description = "Whether this project is a Git repository")]
     pub is_git_repository: bool,
+    #[serde(default)]
+    #[schemars(description = "Color label (terminal color name or hex code like #ff6600)")]
+    pub color: Option<String>,
+    /// URL of the Git remote 'origin', looked up live by `pm list --show-remote-url`;
+    /// never stored, so it cannot go stale after `git remote set-url`
+    #[serde(skip)]
+    pub remote_url: Option<String>,
+    #[serde(default = "default_project_priority")]
+    #[schemars(description = "Importance from 1 to 5 (5 is highest), used by `pm list --sort priority`")]
+    pub priority: u8,
+}
+
+fn default_project_priority() -> u8 {
+    constants::DEFAULT_PROJECT_PRIORITY
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, JsonSchema)]
@@ -540,67 +1220,357 @@ pub struct Project {
 pub struct MachineMetadata {
     pub last_accessed: std::collections::HashMap<Uuid, DateTime<Utc>>,
     pub access_counts: std::collections::HashMap<Uuid, u32>,
+    #[serde(default)]
+    #[schemars(description = "Switched-to project IDs, most recent first")]
+    pub switch_history: Vec<Uuid>,
+    #[serde(default)]
+    #[schemars(description = "Index into switch_history of the current project (moved by --back/--forward)")]
+    pub switch_history_position: usize,
+    #[serde(default)]
+    #[schemars(description = "Most recent access times per project, newest first")]
+    pub recent_accesses: std::collections::HashMap<Uuid, Vec<DateTime<Utc>>>,
 }
 
 /// Common CLI handling logic
 pub async fn handle_command(command: &Commands) -> anyhow::Result<()> {
+    // `status` runs from shell prompts and `init` runs before any extension can exist
+    if !matches!(command, Commands::Status { .. } | Commands::Init { .. }) {
+        extensions::hooks::dispatch_hook(
+            extensions::hooks::HookEvent::PmStart,
+            &extensions::hooks::HookContext::new(),
+        )
+        .await;
+    }
+
     match command {
         Commands::Add {
             path,
             name,
             tags,
             description,
-        } => project::handle_add(path, name, tags, description).await,
-        Commands::Clone { repo, directory } => {
-            project::handle_clone(repo.as_deref(), directory.as_deref()).await
+            detect_description,
+            batch,
+            if_not_exists,
+            update_tags,
+            priority,
+            scan_depth,
+            from_url,
+            infer_name_from,
+            detect_language,
+        } => match (batch, from_url, path) {
+            (Some(batch_file), _, _) => project::handle_add_batch(batch_file).await,
+            (None, Some(url), path) => {
+                let add_config = project::AddConfig {
+                    add_to_pm: true,
+                    name: name.clone(),
+                    tags: (!tags.is_empty()).then(|| tags.clone()),
+                    priority: *priority,
+                };
+                project::handle_add_from_url(url, path.as_deref(), &add_config).await
+            }
+            (None, None, Some(path)) => match scan_depth {
+                Some(depth) => {
+                    project::handle_add_scan(path, *depth as usize, tags, *detect_description, *infer_name_from, *detect_language)
+                        .await
+                }
+                None => {
+                    let options = project::AddOptions {
+                        name: name.clone(),
+                        tags: tags.clone(),
+                        description: description.clone(),
+                        detect_description: *detect_description,
+                        priority: *priority,
+                        existing: project::ExistingProjectPolicy::from_flags(*if_not_exists, *update_tags),
+                        name_strategy: *infer_name_from,
+                        detect_language: *detect_language,
+                    };
+                    project::handle_add(path, &options).await
+                }
+            },
+            (None, None, None) => Err(PmError::ValidationFailed(
+                "Provide a path, --batch <FILE> or --from-url <URL>".to_string(),
+            )
+            .into()),
+        },
+        Commands::Create {
+            path,
+            name,
+            tags,
+            git,
+            template,
+        } => project::handle_create(path, name.as_deref(), tags, *git, template.as_deref()).await,
+        Commands::Clone {
+            repo,
+            directory,
+            no_add,
+            tags,
+            name,
+            gitlab,
+            org,
+        } => {
+            let add_config = project::AddConfig {
+                add_to_pm: !no_add,
+                name: name.clone(),
+                tags: (!tags.is_empty()).then(|| tags.clone()),
+                priority: None,
+            };
+            project::handle_clone(
+                repo.as_deref(),
+                directory.as_deref(),
+                *gitlab,
+                org.as_deref(),
+                &add_config,
+            )
+            .await
         }
         Commands::List {
             tags,
             tags_any,
             recent,
+            since_last_access,
+            since_modified,
+            stale,
+            git_only,
+            no_git,
+            exists,
+            not_exists,
+            missing_description,
+            missing_tags,
             limit,
+            offset,
+            page,
             verbose,
-        } => project::handle_list(tags, tags_any, recent, limit, *verbose).await,
-        Commands::Switch { name } => {
-            let mut config = load_config().await?;
-            project::handle_switch(&mut config, name).await
+            format,
+            interactive,
+            group_by,
+            sort,
+            count,
+            brief,
+            unique_paths,
+            show_duplicates,
+            show_id,
+            show_remote_url,
+            path_depth,
+            access_frequency,
+            watch,
+            interval,
+        } => {
+            let options = project::ListOptions {
+                tags: tags.clone(),
+                tags_any: tags_any.clone(),
+                recent: recent.clone(),
+                since_last_access: since_last_access.clone(),
+                since_modified: since_modified.clone(),
+                stale_threshold: stale.map(|days| chrono::Duration::days(days.into())),
+                git: match (*git_only, *no_git) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
+                exists: match (*exists, *not_exists) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
+                missing_description: *missing_description,
+                missing_tags: *missing_tags,
+                limit: *limit,
+                offset: *offset,
+                page: *page,
+                verbose: *verbose,
+                format: *format,
+                interactive: *interactive,
+                group_by: group_by.clone(),
+                sort: *sort,
+                count: *count,
+                brief: *brief,
+                unique_paths: *unique_paths,
+                show_duplicates: *show_duplicates,
+                show_id: *show_id,
+                show_remote_url: *show_remote_url,
+                path_depth: path_depth.map(|depth| depth as usize),
+                access_frequency: *access_frequency,
+                watch: *watch,
+                interval: *interval,
+            };
+            project::handle_list(&options).await
+        }
+        Commands::Switch {
+            name,
+            back,
+            forward,
+            history,
+        } => {
+            let target = project::SwitchTarget::from_args(name.as_deref(), *back, *forward, *history);
+            project::handle_switch_target(target).await
+        }
+        Commands::Info { name, id, format } => project::handle_info(name.as_deref(), id.as_deref(), format).await,
+        Commands::Dedup { dry_run } => project::handle_dedup(*dry_run).await,
+        Commands::Url {
+            name,
+            format,
+            remote,
+        } => project::handle_url(name.as_deref(), *format, remote.as_deref()).await,
+        Commands::CopyPath {
+            name,
+            url,
+            copy_name,
+        } => project::handle_copy_path(name.as_deref(), *url, *copy_name).await,
+        Commands::Watch { name, events } => project::handle_watch(name.as_deref(), events).await,
+        Commands::Touch { name, all_in_cwd } => {
+            project::handle_touch(name.as_deref(), *all_in_cwd).await
+        }
+        Commands::Path {
+            name,
+            relative,
+            home,
+            parent,
+        } => project::handle_path(name, *relative, *home, *parent).await,
+        Commands::Description { action } => match action {
+            DescriptionAction::Set { name, text } => {
+                project::handle_description_set(name, text).await
+            }
+            DescriptionAction::Edit { name } => project::handle_description_edit(name).await,
+        },
+        Commands::Open { name, ide } => project::handle_open(name.as_deref(), *ide).await,
+        Commands::Priority { name, priority } => project::handle_priority(name, *priority).await,
+        Commands::Color { name, color, clear } => {
+            project::handle_color(name, color.as_deref(), *clear).await
+        }
+        Commands::TagFromPath {
+            pattern,
+            tag,
+            dry_run,
+            yes,
+        } => project::handle_tag_from_path(pattern, tag, *dry_run, *yes).await,
+        Commands::TagSync { name, all, dry_run } => {
+            tag::handle_tag_sync(name.as_deref(), *all, *dry_run).await
+        }
+        Commands::CheckUpdate {
+            name,
+            all,
+            fetch,
+            timeout,
+        } => project::handle_check_update(name.as_deref(), *all, *fetch, *timeout).await,
+        Commands::Exec {
+            command,
+            tags,
+            jobs,
+            progress,
+            no_progress,
+        } => {
+            use std::io::IsTerminal;
+            let progress = *progress || (!*no_progress && std::io::stdout().is_terminal());
+            project::handle_exec(command, tags, *jobs as usize, progress).await
         }
         Commands::Scan {
             directory,
             show_all,
-        } => project::handle_scan(directory.as_deref(), *show_all)
-            .await
-            .map(|_| ()),
+        } => {
+            project::handle_scan(directory.as_deref(), *show_all).await?;
+            tag::apply_configured_tag_rules().await
+        }
+        Commands::Import { source } => match source {
+            ImportSource::JetBrains {
+                product,
+                dry_run,
+                yes,
+            } => {
+                import::handle_import_jetbrains(product.as_deref(), *dry_run, *yes).await?;
+                if *dry_run {
+                    return Ok(());
+                }
+                tag::apply_configured_tag_rules().await
+            }
+        },
         Commands::Tag { action } => match action {
             TagAction::Add { project_name, tags } => tag::handle_tag_add(project_name, tags).await,
             TagAction::Remove { project_name, tags } => {
                 tag::handle_tag_remove(project_name, tags).await
             }
-            TagAction::List {} => tag::handle_tag_list().await,
-            TagAction::Show { project_name } => tag::handle_tag_show(project_name.as_deref()).await,
+            TagAction::List {
+                sort,
+                reverse,
+                min_count,
+                max_count,
+                format,
+            } => {
+                let options = tag_commands::TagListOptions {
+                    sort: *sort,
+                    reverse: *reverse,
+                    min_count: *min_count,
+                    max_count: *max_count,
+                };
+                tag::handle_tag_list(&options, *format).await
+            }
+            TagAction::Show {
+                project_name,
+                all_projects,
+                min_count,
+                format,
+            } => {
+                if *all_projects {
+                    tag::handle_tag_matrix(*min_count, *format).await
+                } else {
+                    tag::handle_tag_show(project_name.as_deref()).await
+                }
+            }
+            TagAction::AutoAssign { rules_file, dry_run } => {
+                tag::handle_tag_auto_assign(rules_file, *dry_run).await
+            }
         },
-        Commands::Remove { project, yes } => project::handle_remove(project.as_deref(), *yes).await,
+        Commands::Remove {
+            project,
+            tags,
+            delete_directory,
+            yes,
+        } => project::handle_remove(project.as_deref(), tags, *delete_directory, *yes).await,
         Commands::Config { command } => {
             match command.as_ref().unwrap_or(&ConfigCommands::Show {}) {
                 ConfigCommands::Show {} => config_cmd::handle_show().await,
                 ConfigCommands::Edit {} => config_cmd::handle_edit().await,
                 ConfigCommands::Validate {} => config_cmd::handle_validate().await,
+                ConfigCommands::Schema { format } => config_cmd::handle_schema(format),
                 ConfigCommands::Reset {} => config_cmd::handle_reset().await,
                 ConfigCommands::Get { key } => config_cmd::handle_get(key).await,
                 ConfigCommands::Set { key, value } => config_cmd::handle_set(key, value).await,
                 ConfigCommands::List {} => config_cmd::handle_list().await,
                 ConfigCommands::Backup(backup_command) => match backup_command {
-                    BackupCommands::Create { name } => {
-                        config_cmd::handle_backup_create(name.as_deref()).await
+                    BackupCommands::Create {
+                        name,
+                        description,
+                        tags,
+                        compress,
+                    } => {
+                        config_cmd::handle_backup_create(
+                            name.as_deref(),
+                            description.as_deref(),
+                            tags,
+                            *compress,
+                        )
+                        .await
                     }
                     BackupCommands::Restore { name } => {
                         config_cmd::handle_backup_restore(name).await
                     }
-                    BackupCommands::List {} => config_cmd::handle_backup_list().await,
+                    BackupCommands::List { tag } => {
+                        config_cmd::handle_backup_list(tag.as_deref()).await
+                    }
                     BackupCommands::Delete { name } => config_cmd::handle_backup_delete(name).await,
+                    BackupCommands::Clean {
+                        keep,
+                        keep_tagged,
+                        force,
+                    } => config_cmd::handle_backup_clean(*keep, keep_tagged, *force).await,
+                    BackupCommands::Compress { name } => {
+                        config_cmd::handle_backup_compress(name.as_deref()).await
+                    }
                 },
                 ConfigCommands::Template(template_command) => match template_command {
-                    TemplateCommands::List {} => config_cmd::handle_template_list().await,
+                    TemplateCommands::List { format } => {
+                        config_cmd::handle_template_list(format).await
+                    }
+                    TemplateCommands::Info { name } => config_cmd::handle_template_info(name).await,
                     TemplateCommands::Apply { name } => {
                         config_cmd::handle_template_apply(name).await
                     }
@@ -634,10 +1604,47 @@ pub async fn handle_command(command: &Commands) -> anyhow::Result<()> {
             BackupAction::Clean { keep, force } => {
                 backup_cmd::handle_backup_clean(*keep, *force).await
             }
-            BackupAction::Status => backup_cmd::handle_backup_status().await,
+            BackupAction::Rotate { policy, force } => {
+                let policy = match policy.as_deref() {
+                    Some([kind, value]) => Some(backup::RetentionPolicy::from_parts(kind, value)?),
+                    _ => None,
+                };
+                backup_cmd::handle_backup_rotate(policy, *force).await
+            }
+            BackupAction::Status {
+                size,
+                cleanup_preview,
+                keep,
+            } => {
+                backup_cmd::handle_backup_status(*size, cleanup_preview.then_some(*keep)).await
+            }
+        },
+        Commands::Init {
+            skip,
+            replace,
+            update,
+            force_unlock,
+            from,
+        } => {
+            if *force_unlock {
+                init::handle_init_force_unlock().await
+            } else if let Some(source) = from {
+                init::handle_init_from(source).await
+            } else if *update {
+                init::handle_init_update().await
+            } else {
+                init::handle_init(*skip, *replace, false).await
+            }
+        }
+        Commands::Status {
+            format,
+            quiet,
+            watch,
+        } => match watch {
+            Some(interval) => status::handle_status_watch(format, *quiet, *interval).await,
+            None => status::handle_status(format, *quiet).await,
         },
-        Commands::Init { skip, replace } => init::handle_init(*skip, *replace, false).await,
-        Commands::Status { format, quiet } => status::handle_status(format, *quiet).await,
+        Commands::JsonSchema { format } => project::handle_json_schema(format),
         Commands::Extension { action } => {
             // Handle extension management commands
             extensions::handle_extension_command(action).await
@@ -0,0 +1,135 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Built-in project templates for `pm create --template`
+pub const PROJECT_TEMPLATES: &[(&str, &str)] = &[
+    ("basic", "README.md and .gitignore"),
+    ("rust", "Cargo binary crate"),
+    ("node", "Node.js package with index.js"),
+    ("python", "Python package with pyproject.toml"),
+];
+
+/// Check that a template name is one of the built-in templates
+pub fn validate_template(template: &str) -> Result<(), String> {
+    if PROJECT_TEMPLATES.iter().any(|(name, _)| *name == template) {
+        Ok(())
+    } else {
+        let names: Vec<&str> = PROJECT_TEMPLATES.iter().map(|(name, _)| *name).collect();
+        Err(format!(
+            "Unknown template '{}'. Available templates: {}",
+            template,
+            names.join(", ")
+        ))
+    }
+}
+
+/// Write the files for `template` into `directory`. Returns the created files.
+pub fn scaffold_project(directory: &Path, project_name: &str, template: &str) -> Result<Vec<PathBuf>> {
+    validate_template(template).map_err(|e| anyhow::anyhow!(e))?;
+
+    let mut files: Vec<(PathBuf, String)> = vec![
+        (PathBuf::from("README.md"), format!("# {}\n", project_name)),
+        (PathBuf::from(".gitignore"), gitignore_for(template).to_string()),
+    ];
+
+    match template {
+        "rust" => {
+            files.push((
+                PathBuf::from("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+                    package_name(project_name, '-')
+                ),
+            ));
+            files.push((
+                PathBuf::from("src/main.rs"),
+                "fn main() {\n    println!(\"Hello, world!\");\n}\n".to_string(),
+            ));
+        }
+        "node" => {
+            files.push((
+                PathBuf::from("package.json"),
+                format!(
+                    "{{\n  \"name\": \"{}\",\n  \"version\": \"0.1.0\",\n  \"main\": \"index.js\",\n  \"scripts\": {{\n    \"start\": \"node index.js\"\n  }}\n}}\n",
+                    package_name(project_name, '-')
+                ),
+            ));
+            files.push((
+                PathBuf::from("index.js"),
+                "console.log('Hello, world!');\n".to_string(),
+            ));
+        }
+        "python" => {
+            let module = package_name(project_name, '_');
+            files.push((
+                PathBuf::from("pyproject.toml"),
+                format!(
+                    "[project]\nname = \"{}\"\nversion = \"0.1.0\"\nrequires-python = \">=3.8\"\n",
+                    package_name(project_name, '-')
+                ),
+            ));
+            files.push((PathBuf::from(format!("src/{}/__init__.py", module)), String::new()));
+        }
+        _ => {}
+    }
+
+    let mut created = Vec::new();
+    for (relative_path, content) in files {
+        let path = directory.join(relative_path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write file: {}", path.display()))?;
+        created.push(path);
+    }
+
+    Ok(created)
+}
+
+fn gitignore_for(template: &str) -> &'static str {
+    match template {
+        "rust" => "/target\n",
+        "node" => "node_modules/\n",
+        "python" => "__pycache__/\n*.py[cod]\n.venv/\n",
+        _ => ".DS_Store\n",
+    }
+}
+
+/// Lowercase package identifier with non-alphanumeric characters replaced by `separator`
+fn package_name(project_name: &str, separator: char) -> String {
+    project_name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { separator })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template("rust").is_ok());
+        assert!(validate_template("java").unwrap_err().contains("basic, rust, node, python"));
+    }
+
+    #[test]
+    fn test_scaffold_project_uses_package_names() {
+        let temp = tempfile::tempdir().unwrap();
+
+        let files = scaffold_project(temp.path(), "My App", "python").unwrap();
+        assert_eq!(files.len(), 4);
+        let pyproject = std::fs::read_to_string(temp.path().join("pyproject.toml")).unwrap();
+        assert!(pyproject.contains("name = \"my-app\""));
+        assert!(temp.path().join("src/my_app/__init__.py").exists());
+        assert_eq!(std::fs::read_to_string(temp.path().join("README.md")).unwrap(), "# My App\n");
+
+        let rust = temp.path().join("rust");
+        scaffold_project(&rust, "tool", "rust").unwrap();
+        assert!(rust.join("src/main.rs").exists());
+        assert_eq!(std::fs::read_to_string(rust.join(".gitignore")).unwrap(), "/target\n");
+    }
+}
@@ -5,6 +5,7 @@ use inquire::{Confirm, Select};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use crate::backup::{BackupEntry, BackupReason, create_multi_file_backup};
+use crate::error::handle_inquire_error;
 use crate::utils;
 
 #[derive(Debug, Clone)]
@@ -155,9 +156,9 @@ async fn handle_fish_function_conflict(existing_path: &Path) -> Result<ConflictA
         "Cancel installation",
     ];
 
-    let selection = Select::new("How would you like to proceed?", options)
+    let selection = handle_inquire_error(Select::new("How would you like to proceed?", options)
         .with_help_message("Choose how to handle the existing pm.fish function")
-        .prompt()?;
+        .prompt())?;
 
     match selection {
         "Backup existing and install PM integration" => Ok(ConflictAction::Backup),
@@ -221,7 +222,12 @@ function {}
     {}
     
     if test "$argv[1]" = "sw" -o "$argv[1]" = "switch"
-        set pm_output ($pm_bin $argv 2>&1)
+        if test (count $argv) -eq 1; or contains -- --history $argv
+            # Interactive picker renders on stderr, keep it on the terminal
+            set pm_output ($pm_bin $argv 2>/dev/tty)
+        else
+            set pm_output ($pm_bin $argv 2>&1)
+        end
         set pm_status $status
         
         if test $pm_status -eq 0
@@ -241,13 +247,26 @@ function {}
     else
         $pm_bin $argv
     end
+end
+
+# Navigate the switch history like a browser's back/forward buttons
+function {}-back
+    {} switch --back
+end
+
+function {}-forward
+    {} switch --forward
 end"#,
         env!("CARGO_PKG_VERSION"),
         shell_name,
         function_name,
         usage_info,
         function_name,
-        binary_logic
+        binary_logic,
+        function_name,
+        function_name,
+        function_name,
+        function_name
     )
 }
 
@@ -323,10 +342,10 @@ pub async fn setup_shell_integration_for_init() -> Result<()> {
         return Ok(());
     }
     
-    let should_setup = Confirm::new(&format!("Setup {} shell integration for directory switching?", shell_name))
+    let should_setup = handle_inquire_error(Confirm::new(&format!("Setup {} shell integration for directory switching?", shell_name))
         .with_default(true)
         .with_help_message(&format!("This will allow '{} sw' to change your shell's current directory", utils::get_binary_name()))
-        .prompt()?;
+        .prompt())?;
 
     if !should_setup {
         println!("⏭️  Skipped shell integration setup");
@@ -382,7 +401,12 @@ unset {} 2>/dev/null
     
     if [[ "$1" == "sw" || "$1" == "switch" ]]; then
         local pm_output
-        pm_output=$("$pm_bin" "$@" 2>&1)
+        if [[ $# -eq 1 || " $* " == *" --history "* ]]; then
+            # Interactive picker renders on stderr, keep it on the terminal
+            pm_output=$("$pm_bin" "$@" 2>/dev/tty)
+        else
+            pm_output=$("$pm_bin" "$@" 2>&1)
+        fi
         local pm_status=$?
         
         if [[ $pm_status -eq 0 ]]; then
@@ -403,6 +427,15 @@ unset {} 2>/dev/null
     else
         "$pm_bin" "$@"
     fi
+}}
+
+# Navigate the switch history like a browser's back/forward buttons
+{}-back() {{
+    {} switch --back
+}}
+
+{}-forward() {{
+    {} switch --forward
 }}{}"#,
         env!("CARGO_PKG_VERSION"),
         shell_name,
@@ -410,6 +443,10 @@ unset {} 2>/dev/null
         function_name,
         function_name,
         binary_logic,
+        function_name,
+        function_name,
+        function_name,
+        function_name,
         alias_section
     )
 }
@@ -433,9 +470,9 @@ async fn setup_zsh_integration() -> Result<()> {
         let existing_content = fs::read_to_string(&zsh_file_path).await?;
         if !existing_content.contains("# Generated by PM v") {
             // User-created file, ask for confirmation
-            let should_overwrite = Confirm::new("Existing pm.sh file found. Overwrite?")
+            let should_overwrite = handle_inquire_error(Confirm::new("Existing pm.sh file found. Overwrite?")
                 .with_default(false)
-                .prompt()?;
+                .prompt())?;
             
             if !should_overwrite {
                 println!("❌ Zsh integration setup cancelled");
@@ -509,9 +546,9 @@ async fn setup_bash_integration() -> Result<()> {
         let existing_content = fs::read_to_string(&bash_file_path).await?;
         if !existing_content.contains("# Generated by PM v") {
             // User-created file, ask for confirmation
-            let should_overwrite = Confirm::new("Existing pm.bash file found. Overwrite?")
+            let should_overwrite = handle_inquire_error(Confirm::new("Existing pm.bash file found. Overwrite?")
                 .with_default(false)
-                .prompt()?;
+                .prompt())?;
             
             if !should_overwrite {
                 println!("❌ Bash integration setup cancelled");
@@ -637,8 +674,8 @@ async fn handle_shell_integration_conflict(
     } else {
         // Interactive mode: skip/replace/cancel  
         let choices = vec!["Skip (keep existing)", "Replace (backup and recreate)", "Cancel"];
-        let choice = Select::new("Shell integration files already exist. What would you like to do?", choices)
-            .prompt()?;
+        let choice = handle_inquire_error(Select::new("Shell integration files already exist. What would you like to do?", choices)
+            .prompt())?;
         
         match choice {
             "Skip (keep existing)" => {
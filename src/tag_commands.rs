@@ -1,3 +1,4 @@
+use crate::commands::tag::parse_tag_hierarchy;
 use crate::config::Config;
 use anyhow::Result;
 use chrono::Utc;
@@ -37,7 +38,29 @@ pub async fn remove_tags(project_name: &str, tags: &[String], config: &mut Confi
     Ok(())
 }
 
-pub async fn list_tags(config: &Config) -> Result<()> {
+/// Ordering for `pm tag list`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default)]
+pub enum TagSort {
+    /// Most used tags first
+    #[default]
+    Count,
+    /// Alphabetical
+    Alpha,
+}
+
+/// Options controlling `pm tag list` ordering and filtering
+#[derive(Debug, Clone, Default)]
+pub struct TagListOptions {
+    pub sort: TagSort,
+    pub reverse: bool,
+    /// Hide tags used by fewer projects
+    pub min_count: Option<u32>,
+    /// Hide tags used by more projects
+    pub max_count: Option<u32>,
+}
+
+/// Tags with their project counts, sorted and filtered per `options`
+pub fn collect_tag_counts(config: &Config, options: &TagListOptions) -> Vec<(String, u32)> {
     let mut tag_counts: HashMap<String, u32> = HashMap::new();
 
     for project in config.projects.values() {
@@ -46,12 +69,62 @@ pub async fn list_tags(config: &Config) -> Result<()> {
         }
     }
 
-    let mut sorted_tags: Vec<(String, u32)> = tag_counts.into_iter().collect();
-    sorted_tags.sort_by(|a, b| b.1.cmp(&a.1));
+    let mut sorted_tags: Vec<(String, u32)> = tag_counts
+        .into_iter()
+        .filter(|(_, count)| options.min_count.is_none_or(|min| *count >= min))
+        .filter(|(_, count)| options.max_count.is_none_or(|max| *count <= max))
+        .collect();
 
-    println!("All Tags (by usage count):");
-    for (tag, count) in sorted_tags {
-        println!("  - {} ({} projects)", tag, count);
+    match options.sort {
+        TagSort::Count => sorted_tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))),
+        TagSort::Alpha => sorted_tags.sort(),
+    }
+    if options.reverse {
+        sorted_tags.reverse();
+    }
+
+    sorted_tags
+}
+
+/// A top-level tag with its own count (if used directly) and its sub-tags
+type TagGroup<'a> = (&'a str, Option<u32>, Vec<(&'a str, u32)>);
+
+pub async fn list_tags(config: &Config, options: &TagListOptions) -> Result<()> {
+    let sorted_tags = collect_tag_counts(config, options);
+
+    match options.sort {
+        TagSort::Count => println!("All Tags (by usage count):"),
+        TagSort::Alpha => println!("All Tags (alphabetical):"),
+    }
+    if sorted_tags.is_empty() {
+        println!("  (No tags)");
+    }
+
+    // Group sub-tags under their parent, keeping the order of first appearance
+    let mut groups: Vec<TagGroup> = Vec::new();
+    for (tag, count) in &sorted_tags {
+        let (parent, child) = parse_tag_hierarchy(tag);
+        let index = match groups.iter().position(|(name, _, _)| *name == parent) {
+            Some(index) => index,
+            None => {
+                groups.push((parent, None, Vec::new()));
+                groups.len() - 1
+            }
+        };
+        match child {
+            Some(child) => groups[index].2.push((child, *count)),
+            None => groups[index].1 = Some(*count),
+        }
+    }
+
+    for (parent, count, children) in groups {
+        match count {
+            Some(count) => println!("  - {} ({} projects)", parent, count),
+            None => println!("  - {}", parent),
+        }
+        for (child, count) in children {
+            println!("      └─ {} ({} projects)", child, count);
+        }
     }
     Ok(())
 }
@@ -78,3 +151,32 @@ pub async fn show_tags(project_name: Option<&str>, config: &Config) -> Result<()
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::project::config_with_projects;
+
+    #[test]
+    fn test_collect_tag_counts_sorts_and_filters() {
+        let config = config_with_projects(&[("web", &["work", "node"]), ("api", &["work", "rust"]), ("cli", &["rust", "work"])]);
+        let counts = |options: TagListOptions| collect_tag_counts(&config, &options);
+
+        assert_eq!(
+            counts(TagListOptions::default()),
+            [("work".to_string(), 3), ("rust".to_string(), 2), ("node".to_string(), 1)]
+        );
+        let alpha = counts(TagListOptions {
+            sort: TagSort::Alpha,
+            reverse: true,
+            ..Default::default()
+        });
+        assert_eq!(alpha.iter().map(|(tag, _)| tag.as_str()).collect::<Vec<_>>(), ["work", "rust", "node"]);
+        let filtered = counts(TagListOptions {
+            min_count: Some(2),
+            max_count: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(filtered, [("rust".to_string(), 2)]);
+    }
+}
@@ -1,7 +1,180 @@
+use crate::constants::PROJECT_FILE_COUNT_LIMIT;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Resolve the editor command: $VISUAL, then settings.editor, then $EDITOR, then DEFAULT_EDITOR
+pub fn resolve_editor(configured: Option<&str>) -> String {
+    let from_env = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+
+    from_env("VISUAL")
+        .or_else(|| {
+            configured
+                .filter(|editor| !editor.trim().is_empty())
+                .map(str::to_string)
+        })
+        .or_else(|| from_env("EDITOR"))
+        .unwrap_or_else(|| crate::constants::DEFAULT_EDITOR.to_string())
+}
+
+/// Build a command for an editor string that may include arguments (e.g. "code --wait")
+pub fn editor_command(editor: &str) -> Command {
+    let mut parts = editor.split_whitespace();
+    let mut command = Command::new(parts.next().unwrap_or(crate::constants::DEFAULT_EDITOR));
+    command.args(parts);
+    command
+}
+
+/// IDEs that `pm open --ide` and `settings.default_ide` can name
+#[derive(
+    clap::ValueEnum, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Ide {
+    Vscode,
+    Vim,
+    Emacs,
+    Idea,
+    Goland,
+    Xcode,
+}
+
+impl Ide {
+    /// Command that opens a directory in the IDE
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Vscode => "code",
+            Self::Vim => "vim",
+            Self::Emacs => "emacs",
+            Self::Idea => "idea",
+            Self::Goland => "goland",
+            // Xcode's command line launcher
+            Self::Xcode => "xed",
+        }
+    }
+}
+
+impl std::str::FromStr for Ide {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        <Self as clap::ValueEnum>::from_str(s, true).map_err(|_| {
+            format!(
+                "Unknown IDE '{}' (expected vscode, vim, emacs, idea, goland or xcode)",
+                s
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for Ide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = clap::ValueEnum::to_possible_value(self).map(|value| value.get_name().to_string());
+        write!(f, "{}", name.unwrap_or_default())
+    }
+}
+
+/// Where `pm add` takes a project's name from when `--name` is not given
+#[derive(
+    clap::ValueEnum, serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug, Clone, Copy, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum NameStrategy {
+    /// The directory's name
+    #[default]
+    Directory,
+    /// `[package].name` in `Cargo.toml`
+    CargoPackage,
+    /// `name` in `package.json`, without its `@scope/`
+    NodePackage,
+    /// Repository name in the `origin` remote URL
+    GitRepo,
+}
+
+impl std::str::FromStr for NameStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        <Self as clap::ValueEnum>::from_str(s, true).map_err(|_| {
+            format!(
+                "Unknown name strategy '{}' (expected directory, cargo-package, node-package or git-repo)",
+                s
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for NameStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = clap::ValueEnum::to_possible_value(self).map(|value| value.get_name().to_string());
+        write!(f, "{}", name.unwrap_or_default())
+    }
+}
+
+/// Project name for `path` according to `strategy`
+///
+/// Returns None when the strategy finds nothing (no manifest, no `name`, no `origin`
+/// remote); callers fall back to the directory name.
+pub fn infer_project_name(path: &Path, strategy: NameStrategy) -> Option<String> {
+    let read = |name: &str| std::fs::read_to_string(path.join(name)).ok();
+    let name = match strategy {
+        NameStrategy::Directory => path.file_name()?.to_str()?.to_string(),
+        NameStrategy::CargoPackage => {
+            let manifest = read("Cargo.toml")?.parse::<toml::Table>().ok()?;
+            manifest.get("package")?.get("name")?.as_str()?.to_string()
+        }
+        NameStrategy::NodePackage => {
+            let manifest: serde_json::Value = serde_json::from_str(&read("package.json")?).ok()?;
+            let name = manifest.get("name")?.as_str()?;
+            name.rsplit('/').next()?.to_string()
+        }
+        NameStrategy::GitRepo => {
+            let url = get_git_remote_url(path).ok()??;
+            repo_name_from_remote_url(&url)?
+        }
+    };
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Last path segment of a remote URL without `.git`, e.g. `repo` for `git@host:owner/repo.git`
+fn repo_name_from_remote_url(url: &str) -> Option<String> {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    let name = url.rsplit(['/', ':']).next()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// First executable called `program` in the `PATH` directories
+pub fn find_in_path(program: &str) -> Option<PathBuf> {
+    let extensions: &[&str] = if cfg!(windows) { &["exe", "cmd", "bat"] } else { &[] };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .flat_map(|dir| {
+            let candidate = dir.join(program);
+            let with_extensions: Vec<PathBuf> =
+                extensions.iter().map(|extension| candidate.with_extension(extension)).collect();
+            std::iter::once(candidate).chain(with_extensions)
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+/// Command for `ide`, falling back to `default_ide` (`settings.default_ide`)
+///
+/// Returns `Ok(None)` when neither is set, and an error when the IDE's command is not in `PATH`.
+pub fn resolve_ide_command(ide: Option<Ide>, default_ide: Option<Ide>) -> Result<Option<String>> {
+    let Some(ide) = ide.or(default_ide) else {
+        return Ok(None);
+    };
+    let command = ide.command();
+    if find_in_path(command).is_none() {
+        return Err(anyhow::anyhow!("{} command '{}' not found in PATH", ide, command));
+    }
+    Ok(Some(command.to_string()))
+}
 
 /// Detect if we're running in development mode based on binary name
 pub fn is_dev_mode() -> bool {
@@ -26,6 +199,35 @@ pub fn get_binary_name() -> &'static str {
     }
 }
 
+/// In-process cache of per-path lookups, each entry stamped with when it was computed
+type PathCache<V> = Mutex<HashMap<PathBuf, (V, Instant)>>;
+
+/// Return the cached value for `path` if younger than `ttl`, otherwise compute and store it
+fn cached_for_path<V: Copy, E>(
+    cache: &PathCache<V>,
+    path: &Path,
+    ttl: Duration,
+    compute: impl FnOnce() -> std::result::Result<V, E>,
+) -> std::result::Result<V, E> {
+    if let Some((value, cached_at)) = cache.lock().unwrap().get(path) {
+        if cached_at.elapsed() < ttl {
+            return Ok(*value);
+        }
+    }
+
+    let value = compute()?;
+    cache
+        .lock()
+        .unwrap()
+        .insert(path.to_path_buf(), (value, Instant::now()));
+    Ok(value)
+}
+
+fn dirty_state_cache() -> &'static PathCache<Option<bool>> {
+    static CACHE: OnceLock<PathCache<Option<bool>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
 pub fn get_last_git_commit_time(path: &Path) -> Result<Option<DateTime<Utc>>> {
     if !path.join(".git").exists() {
         return Ok(None);
@@ -56,7 +258,6 @@ pub fn get_last_git_commit_time(path: &Path) -> Result<Option<DateTime<Utc>>> {
     }
 }
 
-#[allow(dead_code)]
 pub fn get_git_remote_url(path: &Path) -> Result<Option<String>> {
     if !path.join(".git").exists() {
         return Ok(None);
@@ -84,6 +285,199 @@ pub fn get_git_remote_url(path: &Path) -> Result<Option<String>> {
     }
 }
 
+/// Remote URL styles understood by `convert_remote_url`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UrlFormat {
+    /// https://host/owner/repo.git
+    #[default]
+    Https,
+    /// git@host:owner/repo.git
+    Ssh,
+    /// git://host/owner/repo.git
+    Git,
+}
+
+/// Rewrite a Git remote URL into `format`
+///
+/// Accepts HTTPS, `ssh://`, scp-style (`git@host:owner/repo`) and `git://` URLs for any host,
+/// so GitHub Enterprise and other self-hosted servers work too. Returns None if the URL
+/// cannot be parsed (e.g. a local path).
+pub fn convert_remote_url(url: &str, format: UrlFormat) -> Option<String> {
+    let url = url.trim();
+    let (host, path) = if let Some((scheme, rest)) = url.split_once("://") {
+        if !matches!(scheme, "https" | "http" | "ssh" | "git" | "git+ssh") {
+            return None;
+        }
+        let (authority, path) = rest.split_once('/')?;
+        // Drop credentials and ports: user@host:port
+        let host = authority.rsplit('@').next()?;
+        let host = host.split(':').next()?;
+        (host, path)
+    } else {
+        // scp-style: [user@]host:path
+        let (authority, path) = url.split_once(':')?;
+        if authority.contains('/') {
+            return None;
+        }
+        (authority.rsplit('@').next()?, path)
+    };
+
+    let path = path.trim_matches('/').trim_end_matches(".git");
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+
+    Some(match format {
+        UrlFormat::Https => format!("https://{}/{}.git", host, path),
+        UrlFormat::Ssh => format!("git@{}:{}.git", host, path),
+        UrlFormat::Git => format!("git://{}/{}.git", host, path),
+    })
+}
+
+/// Current branch of the repository at `path`
+///
+/// A detached HEAD is reported as `HEAD:<short hash>`. Errors yield `None`.
+pub fn get_git_branch_name(path: &Path) -> Option<String> {
+    let repo = git2::Repository::open(path).ok()?;
+    git_branch_name(&repo)
+}
+
+/// Branch name for an already opened repository; see `get_git_branch_name`
+pub fn git_branch_name(repo: &git2::Repository) -> Option<String> {
+    let head = match repo.head() {
+        Ok(head) => head,
+        Err(_) => {
+            // Unborn branch (no commits yet): HEAD is a symbolic ref to a missing branch
+            let head = repo.find_reference("HEAD").ok()?;
+            let target = head.symbolic_target()?;
+            return Some(target.strip_prefix("refs/heads/").unwrap_or(target).to_string());
+        }
+    };
+
+    if head.is_branch() {
+        return head.shorthand().map(str::to_string);
+    }
+
+    if repo.head_detached().unwrap_or(false) {
+        let commit = head.peel_to_commit().ok()?;
+        let short_id = commit.as_object().short_id().ok()?;
+        return short_id.as_str().map(|id| format!("HEAD:{}", id));
+    }
+
+    // HEAD resolved to a non-branch ref (e.g. a remote-tracking ref)
+    head.shorthand().map(str::to_string)
+}
+
+/// How a local branch compares with its `origin` counterpart
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateCheck {
+    pub branch: String,
+    /// Local commits not on `origin/<branch>`
+    pub ahead: usize,
+    /// Commits on `origin/<branch>` not merged locally
+    pub behind: usize,
+}
+
+/// Compare the checked-out branch with `origin/<branch>`
+///
+/// Uses the local remote-tracking ref unless `fetch` is set, in which case
+/// `git fetch origin <branch>` runs first (killed after `timeout`). Fetching only
+/// updates the remote-tracking ref; the working tree is never touched.
+pub fn check_project_update(path: &Path, fetch: bool, timeout: Duration) -> Result<UpdateCheck> {
+    let repo = git2::Repository::open(path).context("Not a Git repository")?;
+    let head = repo.head().context("Repository has no commits")?;
+    if !head.is_branch() {
+        return Err(anyhow::anyhow!("HEAD is detached"));
+    }
+    let branch = head.shorthand().context("Branch name is not valid UTF-8")?.to_string();
+    let local = head.target().context("HEAD does not point to a commit")?;
+
+    if fetch {
+        fetch_branch(path, &branch, timeout)?;
+    }
+
+    let remote_ref = format!("refs/remotes/origin/{}", branch);
+    let remote = repo
+        .refname_to_id(&remote_ref)
+        .with_context(|| format!("No origin/{} branch", branch))?;
+
+    let (ahead, behind) = repo
+        .graph_ahead_behind(local, remote)
+        .context("Failed to compare with origin")?;
+
+    Ok(UpdateCheck { branch, ahead, behind })
+}
+
+/// Run `git fetch origin <branch>`, killing it after `timeout`
+fn fetch_branch(path: &Path, branch: &str, timeout: Duration) -> Result<()> {
+    let mut child = Command::new("git")
+        .args(["fetch", "--quiet", "origin", branch])
+        .current_dir(path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        // Fail instead of prompting for credentials
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .spawn()
+        .context("Failed to run git fetch")?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return if status.success() {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("git fetch failed ({})", status))
+            };
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow::anyhow!("git fetch timed out after {}s", timeout.as_secs()));
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Whether tracked files in the repository at `path` have uncommitted changes
+///
+/// Untracked files are ignored. Returns `None` for non-repositories and on errors;
+/// results are cached in-process for `GIT_DIRTY_CACHE_SECS`.
+pub fn is_git_working_tree_dirty(path: &Path) -> Option<bool> {
+    let ttl = Duration::from_secs(crate::constants::GIT_DIRTY_CACHE_SECS);
+    cached_for_path(dirty_state_cache(), path, ttl, || {
+        Ok::<_, std::convert::Infallible>(read_working_tree_dirty(path))
+    })
+    .unwrap_or_default()
+}
+
+/// Forget the cached `is_git_working_tree_dirty` result for `path`, e.g. after a file change
+pub fn invalidate_git_dirty_cache(path: &Path) {
+    dirty_state_cache().lock().unwrap().remove(path);
+}
+
+/// Modification time of the directory at `path` itself
+///
+/// Returns `None` when the path is missing or the filesystem does not record mtimes.
+pub fn get_directory_modified_time(path: &Path) -> Option<DateTime<Utc>> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .map(DateTime::<Utc>::from)
+}
+
+fn read_working_tree_dirty(path: &Path) -> Option<bool> {
+    let repo = git2::Repository::open(path).ok()?;
+    let mut options = git2::StatusOptions::new();
+    options.include_untracked(false);
+    let statuses = repo.statuses(Some(&mut options)).ok()?;
+    Some(
+        statuses
+            .iter()
+            .any(|entry| entry.status() != git2::Status::CURRENT),
+    )
+}
+
 #[allow(dead_code)]
 pub fn get_git_current_branch(path: &Path) -> Result<Option<String>> {
     if !path.join(".git").exists() {
@@ -145,54 +539,687 @@ pub fn is_git_repository(path: &Path) -> bool {
 }
 
 
-#[allow(dead_code)]
-pub fn detect_project_language(path: &Path) -> Option<String> {
-    let files = std::fs::read_dir(path).ok()?;
-    let mut language_counts: std::collections::HashMap<&str, u32> =
-        std::collections::HashMap::new();
-
-    for entry in files.flatten() {
-        if let Some(extension) = entry.path().extension() {
-            if let Some(ext_str) = extension.to_str() {
-                let language = match ext_str {
-                    "rs" => "Rust",
-                    "js" | "jsx" => "JavaScript",
-                    "ts" | "tsx" => "TypeScript",
-                    "py" => "Python",
-                    "go" => "Go",
-                    "java" => "Java",
-                    "cpp" | "cc" | "cxx" => "C++",
-                    "c" => "C",
-                    "rb" => "Ruby",
-                    "php" => "PHP",
-                    "swift" => "Swift",
-                    "kt" => "Kotlin",
-                    "dart" => "Dart",
-                    "scala" => "Scala",
-                    "clj" => "Clojure",
-                    "hs" => "Haskell",
-                    "ml" => "OCaml",
-                    "fs" => "F#",
-                    "elm" => "Elm",
-                    "ex" | "exs" => "Elixir",
-                    "erl" => "Erlang",
-                    "lua" => "Lua",
-                    "r" => "R",
-                    "jl" => "Julia",
-                    "nim" => "Nim",
-                    "zig" => "Zig",
-                    "v" => "V",
-                    "cr" => "Crystal",
-                    "d" => "D",
-                    _ => continue,
-                };
-                *language_counts.entry(language).or_insert(0) += 1;
+/// Files whose presence identifies a project's main language, checked in order
+const LANGUAGE_INDICATOR_FILES: &[(&str, &str)] = &[
+    ("Cargo.toml", "Rust"),
+    ("go.mod", "Go"),
+    ("pyproject.toml", "Python"),
+    ("setup.py", "Python"),
+    ("requirements.txt", "Python"),
+    ("deno.json", "TypeScript"),
+    ("tsconfig.json", "TypeScript"),
+    ("package.json", "JavaScript"),
+    ("build.gradle.kts", "Kotlin"),
+    ("pom.xml", "Java"),
+    ("build.gradle", "Java"),
+    ("Gemfile", "Ruby"),
+    ("composer.json", "PHP"),
+    ("Package.swift", "Swift"),
+    ("pubspec.yaml", "Dart"),
+    ("mix.exs", "Elixir"),
+    ("build.sbt", "Scala"),
+];
+
+/// Directories skipped when sampling file extensions and counting project files
+const LANGUAGE_SAMPLE_SKIP_DIRS: &[&str] = &[
+    "node_modules", "target", "vendor", "dist", "build", "venv", "__pycache__",
+];
+
+/// How deep and how many files `detect_project_language` samples
+const LANGUAGE_SAMPLE_MAX_DEPTH: usize = 3;
+const LANGUAGE_SAMPLE_MAX_FILES: usize = 1000;
+
+/// Number of languages taken from the file extension sample
+const LANGUAGE_SAMPLE_TOP: usize = 3;
+
+fn language_for_extension(extension: &str) -> Option<&'static str> {
+    Some(match extension {
+        "rs" => "Rust",
+        "js" | "jsx" => "JavaScript",
+        "ts" | "tsx" => "TypeScript",
+        "py" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "cpp" | "cc" | "cxx" => "C++",
+        "c" => "C",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "swift" => "Swift",
+        "kt" => "Kotlin",
+        "dart" => "Dart",
+        "scala" => "Scala",
+        "clj" => "Clojure",
+        "hs" => "Haskell",
+        "ml" => "OCaml",
+        "fs" => "F#",
+        "elm" => "Elm",
+        "ex" | "exs" => "Elixir",
+        "erl" => "Erlang",
+        "lua" => "Lua",
+        "r" => "R",
+        "jl" => "Julia",
+        "nim" => "Nim",
+        "zig" => "Zig",
+        "v" => "V",
+        "cr" => "Crystal",
+        "d" => "D",
+        _ => return None,
+    })
+}
+
+/// Languages of the project at `path`, most likely first
+///
+/// Indicator files such as `Cargo.toml` or `package.json` come first, in the order of
+/// `LANGUAGE_INDICATOR_FILES`. They are followed by the three most common languages among
+/// file extensions sampled from the tree (hidden and dependency directories are skipped).
+pub fn detect_project_language(path: &Path) -> Vec<String> {
+    let mut languages: Vec<String> = Vec::new();
+    let mut push = |language: &str| {
+        if !languages.iter().any(|known| known == language) {
+            languages.push(language.to_string());
+        }
+    };
+
+    for (file, language) in LANGUAGE_INDICATOR_FILES {
+        if path.join(file).is_file() {
+            push(language);
+        }
+    }
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    walkdir::WalkDir::new(path)
+        .max_depth(LANGUAGE_SAMPLE_MAX_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| !is_skipped_sample_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .take(LANGUAGE_SAMPLE_MAX_FILES)
+        .filter_map(|entry| {
+            let extension = entry.path().extension()?.to_str()?.to_string();
+            language_for_extension(&extension)
+        })
+        .for_each(|language| *counts.entry(language).or_insert(0) += 1);
+
+    let mut sampled: Vec<(&str, usize)> = counts.into_iter().collect();
+    sampled.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    for (language, _) in sampled.into_iter().take(LANGUAGE_SAMPLE_TOP) {
+        push(language);
+    }
+
+    languages
+}
+
+/// Hidden and dependency/build directories below the walk root
+fn is_skipped_sample_dir(entry: &walkdir::DirEntry) -> bool {
+    let name = entry.file_name().to_string_lossy();
+    entry.depth() > 0
+        && entry.file_type().is_dir()
+        && (name.starts_with('.') || LANGUAGE_SAMPLE_SKIP_DIRS.contains(&name.as_ref()))
+}
+
+/// Tag for a detected language, e.g. `rust`, `cpp` for C++ and `fsharp` for F#
+pub fn language_tag(language: &str) -> String {
+    language
+        .to_lowercase()
+        .replace("++", "pp")
+        .replace('#', "sharp")
+}
+
+/// README file names checked by `detect_project_description`, in order
+const README_FILE_NAMES: &[&str] = &["README.md", "Readme.md", "readme.md"];
+
+/// First heading or paragraph of the project's README, for use as its description
+///
+/// Only the first `README_DESCRIPTION_READ_BYTES` are read. Returns `None` when there is
+/// no README or it is not UTF-8; the result is truncated to `MAX_DETECTED_DESCRIPTION_CHARS`.
+pub fn detect_project_description(path: &Path) -> Option<String> {
+    use std::io::Read;
+
+    let readme = README_FILE_NAMES
+        .iter()
+        .map(|name| path.join(name))
+        .find(|candidate| candidate.is_file())?;
+
+    let mut buffer = Vec::with_capacity(crate::constants::README_DESCRIPTION_READ_BYTES);
+    std::fs::File::open(readme)
+        .ok()?
+        .take(crate::constants::README_DESCRIPTION_READ_BYTES as u64)
+        .read_to_end(&mut buffer)
+        .ok()?;
+
+    let text = match std::str::from_utf8(&buffer) {
+        Ok(text) => text,
+        // The read limit may cut a multi-byte character in half
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&buffer[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+
+    let description = extract_readme_description(text)?;
+    Some(crate::display::truncate_with_ellipsis(
+        &description,
+        crate::constants::MAX_DETECTED_DESCRIPTION_CHARS,
+    ))
+}
+
+/// First `#` heading or paragraph of Markdown text, skipping badges and HTML
+fn extract_readme_description(text: &str) -> Option<String> {
+    let mut paragraph: Vec<&str> = Vec::new();
+
+    for line in text.lines().map(str::trim) {
+        let is_decoration = line.starts_with("![") || line.starts_with("[![") || line.starts_with('<');
+        if line.is_empty() || is_decoration {
+            if !paragraph.is_empty() {
+                break;
+            }
+            continue;
+        }
+
+        if line.starts_with('#') {
+            if !paragraph.is_empty() {
+                break;
+            }
+            let heading = line.trim_start_matches('#').trim();
+            if !heading.is_empty() {
+                return Some(heading.to_string());
+            }
+            continue;
+        }
+
+        paragraph.push(line);
+    }
+
+    (!paragraph.is_empty()).then(|| paragraph.join(" "))
+}
+
+/// Keywords declared in the project's `package.json`, `Cargo.toml` and `pyproject.toml`
+///
+/// Manifests that are missing or fail to parse are ignored. Keywords keep their
+/// first-seen order and duplicates across manifests are dropped.
+pub fn read_manifest_keywords(path: &Path) -> Vec<String> {
+    let read = |name: &str| std::fs::read_to_string(path.join(name)).ok();
+    let mut keywords: Vec<String> = Vec::new();
+
+    if let Some(value) = read("package.json").and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok()) {
+        keywords.extend(json_string_array(value.get("keywords")));
+    }
+    if let Some(value) = read("Cargo.toml").and_then(|s| s.parse::<toml::Table>().ok()) {
+        // `keywords.workspace = true` is a table, not a list, and is skipped
+        keywords.extend(toml_string_array(value.get("package").and_then(|p| p.get("keywords"))));
+    }
+    if let Some(value) = read("pyproject.toml").and_then(|s| s.parse::<toml::Table>().ok()) {
+        keywords.extend(toml_string_array(value.get("project").and_then(|p| p.get("keywords"))));
+        let poetry = value.get("tool").and_then(|tool| tool.get("poetry"));
+        keywords.extend(toml_string_array(poetry.and_then(|p| p.get("keywords"))));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    keywords.retain(|keyword| seen.insert(keyword.clone()));
+    keywords
+}
+
+fn json_string_array(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|value| value.as_array())
+        .map(|items| items.iter().filter_map(|item| item.as_str()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn toml_string_array(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(|value| value.as_array())
+        .map(|items| items.iter().filter_map(|item| item.as_str()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Express `path` relative to `base` (both should be absolute), e.g. `../other/project`
+pub fn relative_path(path: &Path, base: &Path) -> std::path::PathBuf {
+    let path_components: Vec<_> = path.components().collect();
+    let base_components: Vec<_> = base.components().collect();
+
+    let common = path_components
+        .iter()
+        .zip(&base_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = std::path::PathBuf::new();
+    for _ in common..base_components.len() {
+        relative.push("..");
+    }
+    for component in &path_components[common..] {
+        relative.push(component);
+    }
+
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
+
+/// Canonical form of `path` for comparing project locations (falls back to `path` if it cannot be resolved)
+///
+/// Uses `dunce` so Windows paths stay as `C:\...` instead of UNC `\\?\C:\...` paths.
+pub fn canonical_path(path: &Path) -> std::path::PathBuf {
+    dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Key for comparing paths: lowercased on Windows, where paths are case-insensitive
+pub fn path_comparison_key(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        PathBuf::from(path.to_string_lossy().to_lowercase())
+    }
+    #[cfg(not(windows))]
+    {
+        path.to_path_buf()
+    }
+}
+
+/// Abbreviate the home directory prefix of `path` to `~`
+pub fn abbreviate_home(path: &Path) -> String {
+    if let Some(home) = dirs::home_dir() {
+        if let Ok(stripped) = path.strip_prefix(&home) {
+            if stripped.as_os_str().is_empty() {
+                return "~".to_string();
             }
+            return format!("~/{}", stripped.display());
         }
     }
+    path.display().to_string()
+}
+
+/// Format file size in human-readable format
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    const THRESHOLD: u64 = 1024;
+    
+    if bytes < THRESHOLD {
+        return format!("{} B", bytes);
+    }
+    
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+    
+    while size >= THRESHOLD as f64 && unit_index < UNITS.len() - 1 {
+        size /= THRESHOLD as f64;
+        unit_index += 1;
+    }
+    
+    format!("{:.1} {}", size, UNITS[unit_index])
+}
+
+/// Format a count compactly: `999`, `1.2k`, `34k`, `1.5M`
+pub fn format_number(n: u64) -> String {
+    const UNITS: &[(u64, &str)] = &[(1_000_000_000, "B"), (1_000_000, "M"), (1_000, "k")];
 
-    language_counts
+    for &(scale, suffix) in UNITS {
+        if n >= scale {
+            let value = n as f64 / scale as f64;
+            // One decimal below 10 (1.2k), whole numbers above (34k)
+            return if value < 10.0 {
+                let formatted = format!("{:.1}", (value * 10.0).floor() / 10.0);
+                format!("{}{}", formatted.trim_end_matches(".0"), suffix)
+            } else {
+                format!("{}{}", value.floor(), suffix)
+            };
+        }
+    }
+
+    n.to_string()
+}
+
+/// Last `depth` components of `path`, or the whole path if it has no more than that
+///
+/// `/home/user/code/work/myapp` at depth 2 becomes `work/myapp`.
+pub fn truncate_path_to_depth(path: &Path, depth: usize) -> String {
+    let components: Vec<_> = path.components().collect();
+    if components.len() <= depth {
+        return path.display().to_string();
+    }
+    components[components.len() - depth..]
+        .iter()
+        .collect::<PathBuf>()
+        .display()
+        .to_string()
+}
+
+/// Total size of all files below `path` (unreadable entries are ignored)
+pub fn calculate_directory_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
         .into_iter()
-        .max_by_key(|(_, count)| *count)
-        .map(|(lang, _)| lang.to_string())
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Number of files below `path`, stopping at `PROJECT_FILE_COUNT_LIMIT`
+///
+/// Hidden and dependency/build directories (`node_modules`, `target`, ...) are skipped, like
+/// `detect_project_language` does.
+pub fn count_project_files(path: &Path) -> usize {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|entry| !is_skipped_sample_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .take(PROJECT_FILE_COUNT_LIMIT)
+        .count()
+}
+
+/// How often a project is opened, for `pm list --access-frequency`
+#[derive(clap::ValueEnum, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessFrequency {
+    /// More than 5 accesses per week
+    Daily,
+    /// 1-5 accesses per week
+    Weekly,
+    /// 1-4 accesses per month
+    Monthly,
+    /// Less than monthly, or never accessed
+    Rarely,
+}
+
+/// Days a project must have been accessed within to count as anything but `rarely`
+const ACCESS_FREQUENCY_WINDOW_DAYS: i64 = 30;
+
+/// Classify a project's access frequency
+///
+/// PM only stores a total access count and the last access time, so the rate is averaged
+/// over the time since the project was added (at least a week). A project not accessed
+/// within the last 30 days is `rarely` regardless of its average.
+pub fn compute_access_frequency(
+    access_count: u32,
+    last_accessed: Option<DateTime<Utc>>,
+    tracked_since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> AccessFrequency {
+    let Some(last_accessed) = last_accessed else {
+        return AccessFrequency::Rarely;
+    };
+    if access_count == 0 || (now - last_accessed).num_days() > ACCESS_FREQUENCY_WINDOW_DAYS {
+        return AccessFrequency::Rarely;
+    }
+
+    let tracked_days = (now - tracked_since).num_days().max(7) as f64;
+    let per_week = access_count as f64 / (tracked_days / 7.0);
+    let per_month = per_week * 30.0 / 7.0;
+
+    if per_week > 5.0 {
+        AccessFrequency::Daily
+    } else if per_week >= 1.0 {
+        AccessFrequency::Weekly
+    } else if per_month >= 1.0 {
+        AccessFrequency::Monthly
+    } else {
+        AccessFrequency::Rarely
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(999), "999");
+        assert_eq!(format_number(1_000), "1k");
+        assert_eq!(format_number(1_234), "1.2k");
+        assert_eq!(format_number(9_999), "9.9k");
+        assert_eq!(format_number(34_567), "34k");
+        assert_eq!(format_number(999_999), "999k");
+        assert_eq!(format_number(1_500_000), "1.5M");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_path_comparison_key_ignores_case_on_windows() {
+        assert_eq!(
+            path_comparison_key(Path::new(r"C:\Users\Me\Code")),
+            path_comparison_key(Path::new(r"c:\users\me\code"))
+        );
+        assert_ne!(
+            path_comparison_key(Path::new(r"C:\Code")),
+            path_comparison_key(Path::new(r"D:\Code"))
+        );
+        assert_eq!(canonical_path(Path::new(r"D:\does-not-exist")), Path::new(r"D:\does-not-exist"));
+    }
+
+    #[test]
+    fn test_ide_names_and_commands() {
+        assert_eq!("VSCode".parse::<Ide>().unwrap(), Ide::Vscode);
+        assert_eq!(Ide::Vscode.command(), "code");
+        assert_eq!(Ide::Goland.to_string(), "goland");
+        assert!("notepad".parse::<Ide>().is_err());
+        assert_eq!(serde_yaml::to_string(&Ide::Xcode).unwrap().trim(), "xcode");
+
+        assert_eq!(resolve_ide_command(None, None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_truncate_path_to_depth() {
+        let path = Path::new("/home/user/code/work/myapp");
+        assert_eq!(truncate_path_to_depth(path, 2), "work/myapp");
+        assert_eq!(truncate_path_to_depth(path, 1), "myapp");
+        assert_eq!(truncate_path_to_depth(path, 10), "/home/user/code/work/myapp");
+        assert_eq!(truncate_path_to_depth(Path::new("work/myapp"), 2), "work/myapp");
+    }
+
+    #[test]
+    fn test_compute_access_frequency() {
+        let now = Utc::now();
+        let days_ago = |days: i64| now - chrono::Duration::days(days);
+
+        assert_eq!(compute_access_frequency(0, None, days_ago(100), now), AccessFrequency::Rarely);
+        assert_eq!(compute_access_frequency(50, Some(days_ago(1)), days_ago(28), now), AccessFrequency::Daily);
+        assert_eq!(compute_access_frequency(8, Some(days_ago(2)), days_ago(28), now), AccessFrequency::Weekly);
+        assert_eq!(compute_access_frequency(3, Some(days_ago(10)), days_ago(60), now), AccessFrequency::Monthly);
+        assert_eq!(compute_access_frequency(2, Some(days_ago(5)), days_ago(365), now), AccessFrequency::Rarely);
+        // Frequent in the past but not within the window
+        assert_eq!(compute_access_frequency(500, Some(days_ago(45)), days_ago(60), now), AccessFrequency::Rarely);
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let base = Path::new("/work/pm");
+        assert_eq!(relative_path(Path::new("/work/pm/src"), base), PathBuf::from("src"));
+        assert_eq!(relative_path(Path::new("/work/api"), base), PathBuf::from("../api"));
+        assert_eq!(relative_path(Path::new("/other"), base), PathBuf::from("../../other"));
+        assert_eq!(relative_path(base, base), PathBuf::from("."));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_abbreviate_home() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(abbreviate_home(&home), "~");
+        assert_eq!(abbreviate_home(&home.join("code").join("pm")), "~/code/pm");
+        assert_eq!(abbreviate_home(Path::new("/definitely/not/home")), "/definitely/not/home");
+    }
+
+    #[test]
+    fn test_get_git_branch_name() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(get_git_branch_name(dir.path()), None);
+
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        repo.set_head("refs/heads/trunk").unwrap();
+        // Unborn branch before the first commit
+        assert_eq!(get_git_branch_name(dir.path()).as_deref(), Some("trunk"));
+
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let oid = repo
+            .commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        assert_eq!(get_git_branch_name(dir.path()).as_deref(), Some("trunk"));
+
+        repo.set_head_detached(oid).unwrap();
+        let branch = get_git_branch_name(dir.path()).unwrap();
+        assert!(branch.starts_with("HEAD:"));
+        assert!(oid.to_string().starts_with(&branch["HEAD:".len()..]));
+    }
+
+    #[test]
+    fn test_check_project_update_counts_commits_behind_origin() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let base = repo
+            .commit(Some("HEAD"), &signature, &signature, "base", &tree, &[])
+            .unwrap();
+
+        assert!(check_project_update(dir.path(), false, Duration::from_secs(1)).is_err());
+
+        // origin/main is two commits ahead of the local branch
+        let mut parent = repo.find_commit(base).unwrap();
+        for message in ["one", "two"] {
+            let oid = repo
+                .commit(None, &signature, &signature, message, &tree, &[&parent])
+                .unwrap();
+            parent = repo.find_commit(oid).unwrap();
+        }
+        repo.reference("refs/remotes/origin/main", parent.id(), true, "test")
+            .unwrap();
+
+        let check = check_project_update(dir.path(), false, Duration::from_secs(1)).unwrap();
+        assert_eq!(
+            check,
+            UpdateCheck {
+                branch: "main".to_string(),
+                ahead: 0,
+                behind: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_working_tree_dirty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_working_tree_dirty(dir.path()), None);
+
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        std::fs::write(dir.path().join("README.md"), "hello\n").unwrap();
+        // Untracked files do not count
+        assert_eq!(read_working_tree_dirty(dir.path()), Some(false));
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        index.write().unwrap();
+        assert_eq!(read_working_tree_dirty(dir.path()), Some(true));
+
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        assert_eq!(read_working_tree_dirty(dir.path()), Some(false));
+
+        std::fs::write(dir.path().join("README.md"), "changed\n").unwrap();
+        assert_eq!(read_working_tree_dirty(dir.path()), Some(true));
+    }
+
+    #[test]
+    fn test_detect_project_language() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("Cargo.toml"), "").unwrap();
+        std::fs::create_dir_all(root.join("scripts")).unwrap();
+        for file in ["a.py", "b.py", "c.sh"] {
+            std::fs::write(root.join("scripts").join(file), "").unwrap();
+        }
+        std::fs::write(root.join("build.rs"), "").unwrap();
+        std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        for file in ["a.js", "b.js", "c.js"] {
+            std::fs::write(root.join("node_modules/pkg").join(file), "").unwrap();
+        }
+
+        assert_eq!(detect_project_language(root), ["Rust", "Python"]);
+        assert_eq!(language_tag("C++"), "cpp");
+        assert_eq!(language_tag("F#"), "fsharp");
+        assert_eq!(language_tag("TypeScript"), "typescript");
+    }
+
+    #[test]
+    fn test_infer_project_name() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("checkout");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(infer_project_name(&dir, NameStrategy::Directory).as_deref(), Some("checkout"));
+        assert_eq!(infer_project_name(&dir, NameStrategy::CargoPackage), None);
+        assert_eq!(infer_project_name(&dir, NameStrategy::GitRepo), None);
+
+        std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"my-crate\"\n").unwrap();
+        std::fs::write(dir.join("package.json"), r#"{"name": "@acme/web-app"}"#).unwrap();
+        assert_eq!(infer_project_name(&dir, NameStrategy::CargoPackage).as_deref(), Some("my-crate"));
+        assert_eq!(infer_project_name(&dir, NameStrategy::NodePackage).as_deref(), Some("web-app"));
+
+        assert_eq!(repo_name_from_remote_url("git@github.com:owner/repo.git").as_deref(), Some("repo"));
+        assert_eq!(repo_name_from_remote_url("https://gitlab.com/group/sub/tool/").as_deref(), Some("tool"));
+    }
+
+    #[test]
+    fn test_convert_remote_url() {
+        let https = "https://github.com/user/repo.git";
+        assert_eq!(
+            convert_remote_url(https, UrlFormat::Ssh).as_deref(),
+            Some("git@github.com:user/repo.git")
+        );
+        assert_eq!(
+            convert_remote_url("git@github.example.com:org/team/repo", UrlFormat::Https).as_deref(),
+            Some("https://github.example.com/org/team/repo.git")
+        );
+        assert_eq!(
+            convert_remote_url("ssh://git@ghe.corp:2222/user/repo.git", UrlFormat::Git).as_deref(),
+            Some("git://ghe.corp/user/repo.git")
+        );
+        assert_eq!(convert_remote_url(https, UrlFormat::Https).as_deref(), Some(https));
+        assert_eq!(convert_remote_url("/srv/git/repo.git", UrlFormat::Ssh), None);
+        assert_eq!(convert_remote_url("file:///srv/git/repo.git", UrlFormat::Ssh), None);
+    }
+
+    #[test]
+    fn test_detect_project_description() {
+        assert_eq!(
+            extract_readme_description("[![CI](badge.svg)](ci)\n\n# My Tool\n\nDoes things.\n"),
+            Some("My Tool".to_string())
+        );
+        assert_eq!(
+            extract_readme_description("\nA small CLI\nfor notes.\n\n## Usage\n"),
+            Some("A small CLI for notes.".to_string())
+        );
+        assert_eq!(extract_readme_description("#\n\n<p align=center>\n"), None);
+
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(detect_project_description(temp.path()), None);
+        std::fs::write(temp.path().join("README.md"), b"\xff\xfe# Broken\n").unwrap();
+        assert_eq!(detect_project_description(temp.path()), None);
+        std::fs::write(temp.path().join("README.md"), format!("# {}\n", "x".repeat(300))).unwrap();
+        let description = detect_project_description(temp.path()).unwrap();
+        assert_eq!(description.chars().count(), 200);
+    }
+
+    #[test]
+    fn test_read_manifest_keywords() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(read_manifest_keywords(temp.path()).is_empty());
+
+        std::fs::write(temp.path().join("package.json"), r#"{"keywords": ["cli", "notes"]}"#).unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"x\"\nkeywords = [\"cli\", \"terminal\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("pyproject.toml"),
+            "[tool.poetry]\nkeywords = [\"scripting\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(read_manifest_keywords(temp.path()), vec!["cli", "notes", "terminal", "scripting"]);
+    }
 }
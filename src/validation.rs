@@ -1,9 +1,20 @@
 use crate::constants::*;
+use crate::error::PmError;
 use anyhow::{Context, Result};
-use chrono::Duration;
-use std::path::{Path, PathBuf};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// Longest file name most filesystems accept, in bytes
+pub const MAX_PATH_COMPONENT_BYTES: usize = 255;
+
+/// Validate a project directory and return its canonical path
+///
+/// Besides existence, rejects paths PM cannot manage reliably; see `check_path_format`.
+pub fn validate_path(path: &Path, allow_unicode: bool) -> Result<PathBuf> {
+    check_path_format(path, allow_unicode)?;
 
-pub fn validate_path(path: &Path) -> Result<PathBuf> {
     if !path.exists() {
         anyhow::bail!(
             "Path does not exist: {}\n\n💡 Suggestions:\n  - {}\n  - Create the directory first: mkdir -p {}",
@@ -20,11 +31,117 @@ pub fn validate_path(path: &Path) -> Result<PathBuf> {
         );
     }
 
-    path.canonicalize()
-        .with_context(|| format!("Failed to resolve absolute path for: {}", path.display()))
+    let canonical = dunce::canonicalize(path)
+        .with_context(|| format!("Failed to resolve absolute path for: {}", path.display()))?;
+    // `.` or `..` may resolve to a root, and parent directories may add non-ASCII names
+    check_path_format(&canonical, allow_unicode)?;
+    Ok(canonical)
+}
+
+/// Checks that don't touch the filesystem: NUL bytes, component length, blank
+/// components, filesystem/drive roots and (optionally) non-ASCII names
+pub fn check_path_format(path: &Path, allow_unicode: bool) -> Result<(), PmError> {
+    if path.as_os_str().as_encoded_bytes().contains(&0) {
+        return Err(PmError::PathContainsNul);
+    }
+
+    // A root has no parent: `/` on Unix, `C:\` on Windows
+    if path.has_root() && path.parent().is_none() {
+        return Err(PmError::PathIsRoot(path.display().to_string()));
+    }
+
+    for component in path.components() {
+        let Component::Normal(name) = component else {
+            continue;
+        };
+        let lossy = name.to_string_lossy();
+
+        if name.as_encoded_bytes().len() > MAX_PATH_COMPONENT_BYTES {
+            return Err(PmError::PathComponentTooLong(lossy.into_owned()));
+        }
+        if lossy.trim().is_empty() {
+            return Err(PmError::PathComponentBlank);
+        }
+        if !allow_unicode && !lossy.is_ascii() {
+            return Err(PmError::PathNotAscii(lossy.into_owned()));
+        }
+    }
+
+    Ok(())
+}
+
+/// A `--recent`-style time filter: a relative duration or an absolute point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsedTimeFilter {
+    /// Relative duration counted back from now (e.g. `7d`)
+    Duration(Duration),
+    /// Absolute timestamp (e.g. `2025-01-01` or `2025-01-01T10:00:00Z`)
+    Since(DateTime<Utc>),
+}
+
+impl ParsedTimeFilter {
+    /// The earliest timestamp that still passes the filter
+    pub fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            ParsedTimeFilter::Duration(duration) => now - *duration,
+            ParsedTimeFilter::Since(timestamp) => *timestamp,
+        }
+    }
+}
+
+/// Parse a relative duration (`7d`, `2w`) or an ISO 8601 date / datetime
+///
+/// Dates are taken as midnight UTC; datetimes must carry a timezone (`Z` or `+09:00`)
+/// and are converted to UTC. Absolute dates in the future are rejected.
+pub fn parse_time_filter(value: &str) -> Result<ParsedTimeFilter, String> {
+    parse_time_filter_at(value, Utc::now())
+}
+
+fn parse_time_filter_at(value: &str, now: DateTime<Utc>) -> Result<ParsedTimeFilter, String> {
+    let Some(timestamp) = parse_absolute_time(value)? else {
+        return parse_time_duration_relative(value).map(ParsedTimeFilter::Duration);
+    };
+
+    if timestamp > now {
+        return Err(format!("Date is in the future: {}", value));
+    }
+    Ok(ParsedTimeFilter::Since(timestamp))
+}
+
+/// `Ok(None)` when `value` does not look like a date at all
+fn parse_absolute_time(value: &str) -> Result<Option<DateTime<Utc>>, String> {
+    let looks_like_date = value.len() >= 10
+        && value.as_bytes()[4] == b'-'
+        && value.as_bytes()[..4].iter().all(u8::is_ascii_digit);
+    if !looks_like_date {
+        return Ok(None);
+    }
+
+    if value.len() == 10 {
+        let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}': {}", value, e))?;
+        return Ok(Some(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()));
+    }
+
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| Some(timestamp.with_timezone(&Utc)))
+        .map_err(|e| {
+            format!(
+                "Invalid datetime '{}': {} (use e.g. 2025-01-01T10:00:00Z)",
+                value, e
+            )
+        })
 }
 
+/// Parse a time filter and return how far back it reaches from now
+///
+/// Accepts everything `parse_time_filter` does; absolute dates become the duration since that date.
 pub fn parse_time_duration(duration_str: &str) -> Result<Duration, String> {
+    let now = Utc::now();
+    parse_time_filter_at(duration_str, now).map(|filter| now - filter.cutoff(now))
+}
+
+fn parse_time_duration_relative(duration_str: &str) -> Result<Duration, String> {
     if duration_str.is_empty() {
         return Err("Duration cannot be empty".to_string());
     }
@@ -96,11 +213,123 @@ pub fn validate_tags(tags: &[String]) -> Result<(), String> {
         if tag.chars().any(|c| c.is_whitespace()) {
             return Err(format!("Tag '{}' cannot contain whitespace", tag));
         }
+
+        // Hierarchical tags: `parent/child`, one level deep
+        if tag.matches('/').count() > 1 {
+            return Err(format!("Tag '{}' can only have one level of nesting (parent/child)", tag));
+        }
+
+        if tag.starts_with('/') || tag.ends_with('/') {
+            return Err(format!("Tag '{}' must have a name on both sides of '/'", tag));
+        }
     }
 
     Ok(())
 }
 
+/// How strictly new tag names are checked (`settings.tag_naming_policy`)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TagNamingPolicy {
+    /// Lowercase letters, digits, `-`, `_` and `/` only; no leading, trailing or doubled hyphens
+    Strict,
+    /// Lowercase tags automatically, otherwise like `permissive`
+    LowercaseOnly,
+    /// Only the basic rules of `validate_tags`
+    #[default]
+    Permissive,
+}
+
+impl std::str::FromStr for TagNamingPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "strict" => Ok(Self::Strict),
+            "lowercase-only" => Ok(Self::LowercaseOnly),
+            "permissive" => Ok(Self::Permissive),
+            _ => Err(format!(
+                "Unknown tag naming policy '{}' (expected strict, lowercase-only or permissive)",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for TagNamingPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Strict => write!(f, "strict"),
+            Self::LowercaseOnly => write!(f, "lowercase-only"),
+            Self::Permissive => write!(f, "permissive"),
+        }
+    }
+}
+
+/// Check a tag against `policy`, returning the tag as it should be stored
+pub fn validate_tag_name(tag: &str, policy: TagNamingPolicy) -> Result<String, PmError> {
+    let tag = match policy {
+        TagNamingPolicy::LowercaseOnly => tag.to_lowercase(),
+        _ => tag.to_string(),
+    };
+    validate_tags(std::slice::from_ref(&tag)).map_err(PmError::InvalidTagName)?;
+
+    if policy == TagNamingPolicy::Strict {
+        let invalid = |reason: &str| PmError::InvalidTagName(format!("Tag '{}' {}", tag, reason));
+
+        if !tag
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '/'))
+        {
+            return Err(invalid("may only contain lowercase letters, digits, '-', '_' and '/'"));
+        }
+        if tag.split('/').any(|part| part.starts_with('-') || part.ends_with('-')) {
+            return Err(invalid("cannot start or end with a hyphen"));
+        }
+        if tag.contains("--") {
+            return Err(invalid("cannot contain consecutive hyphens"));
+        }
+    }
+
+    Ok(tag)
+}
+
+/// `validate_tag_name` for several tags
+pub fn validate_tag_names(tags: &[String], policy: TagNamingPolicy) -> Result<Vec<String>, PmError> {
+    tags.iter().map(|tag| validate_tag_name(tag, policy)).collect()
+}
+
+/// Tags PM generates itself (detected languages, import sources) in the form `policy`
+/// stores them; tags the policy rejects are dropped instead of failing the command
+pub fn allowed_generated_tags(tags: impl IntoIterator<Item = String>, policy: TagNamingPolicy) -> Vec<String> {
+    tags.into_iter()
+        .filter_map(|tag| validate_tag_name(&tag, policy).ok())
+        .collect()
+}
+
+pub fn validate_color(color: &str) -> Result<(), String> {
+    if let Some(hex) = color.strip_prefix('#') {
+        let valid_length = hex.len() == 3 || hex.len() == 6;
+        if valid_length && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(());
+        }
+        return Err(format!(
+            "Invalid hex color '{}' (expected #rgb or #rrggbb)",
+            color
+        ));
+    }
+
+    if PROJECT_COLORS.contains(&color.to_lowercase().as_str()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unknown color '{}'. Valid colors: {} or a hex code like #ff6600",
+            color,
+            PROJECT_COLORS.join(", ")
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,6 +352,39 @@ mod tests {
         assert!(parse_time_duration("7x").is_err());
     }
 
+    #[test]
+    fn test_parse_time_filter_absolute_dates() {
+        let now = DateTime::parse_from_rfc3339("2025-03-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let at = |value: &str| parse_time_filter_at(value, now);
+
+        // Dates are midnight UTC
+        assert_eq!(
+            at("2025-01-01").unwrap(),
+            ParsedTimeFilter::Since("2025-01-01T00:00:00Z".parse().unwrap())
+        );
+        // Leap years
+        assert!(at("2024-02-29").is_ok());
+        assert!(at("2025-02-29").is_err());
+        assert!(at("2025-13-01").is_err());
+
+        // Offsets are normalized to UTC
+        assert_eq!(
+            at("2025-01-01T10:00:00+09:00").unwrap().cutoff(now),
+            "2025-01-01T01:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        // Datetimes without a timezone are ambiguous
+        assert!(at("2025-01-01T10:00:00").is_err());
+
+        // Past vs. future
+        assert_eq!(at("2025-03-01T12:00:00Z").unwrap().cutoff(now), now);
+        assert!(at("2025-03-02").is_err());
+
+        // Relative durations still work
+        assert_eq!(at("7d").unwrap(), ParsedTimeFilter::Duration(Duration::days(7)));
+        assert_eq!(at("7d").unwrap().cutoff(now), now - Duration::days(7));
+        assert!(at("2025-1-1").is_err());
+    }
+
     #[test]
     fn test_validate_project_name() {
         assert!(validate_project_name("valid-project").is_ok());
@@ -140,5 +402,86 @@ mod tests {
         assert!(validate_tags(&["a".repeat(51)]).is_err());
         assert!(validate_tags(&["invalid,tag".to_string()]).is_err());
         assert!(validate_tags(&["invalid tag".to_string()]).is_err());
+        assert!(validate_tags(&["work/rust".to_string()]).is_ok());
+        assert!(validate_tags(&["work/rust/cli".to_string()]).is_err());
+        assert!(validate_tags(&["work/".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_check_path_format() {
+        assert!(check_path_format(Path::new("/home/user/projects/app"), true).is_ok());
+        assert!(check_path_format(Path::new("relative/app"), true).is_ok());
+
+        assert!(matches!(check_path_format(Path::new("/"), true), Err(PmError::PathIsRoot(_))));
+        assert!(matches!(check_path_format(Path::new("/tmp/a\0b"), true), Err(PmError::PathContainsNul)));
+        assert!(matches!(check_path_format(Path::new("/tmp/   /app"), true), Err(PmError::PathComponentBlank)));
+
+        let long_name = format!("/tmp/{}", "a".repeat(MAX_PATH_COMPONENT_BYTES + 1));
+        assert!(matches!(check_path_format(Path::new(&long_name), true), Err(PmError::PathComponentTooLong(_))));
+        // Multi-byte characters count by bytes: 128 × 2 bytes = 256
+        let long_unicode = format!("/tmp/{}", "é".repeat(128));
+        assert!(matches!(check_path_format(Path::new(&long_unicode), true), Err(PmError::PathComponentTooLong(_))));
+        let max_name = format!("/tmp/{}", "a".repeat(MAX_PATH_COMPONENT_BYTES));
+        assert!(check_path_format(Path::new(&max_name), true).is_ok());
+
+        assert!(check_path_format(Path::new("/home/user/프로젝트"), true).is_ok());
+        assert!(matches!(check_path_format(Path::new("/home/user/프로젝트"), false), Err(PmError::PathNotAscii(_))));
+        assert!(check_path_format(Path::new("/home/user/project"), false).is_ok());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_check_path_format_drive_root() {
+        assert!(matches!(check_path_format(Path::new("C:\\"), true), Err(PmError::PathIsRoot(_))));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_validate_path_keeps_drive_letter() {
+        let dir = tempfile::tempdir().unwrap();
+        let canonical = validate_path(dir.path(), true).unwrap();
+        let display = canonical.display().to_string();
+        assert!(!display.starts_with(r"\\?\"), "UNC path: {}", display);
+        assert_eq!(display.as_bytes()[1], b':');
+    }
+
+    #[test]
+    fn test_validate_tag_name() {
+        use TagNamingPolicy::*;
+
+        assert_eq!(validate_tag_name("Work", Permissive).unwrap(), "Work");
+        assert_eq!(validate_tag_name("Work/Rust", LowercaseOnly).unwrap(), "work/rust");
+        assert_eq!(validate_tag_name("work/rust_cli-2", Strict).unwrap(), "work/rust_cli-2");
+
+        assert!(validate_tag_name("Work", Strict).is_err());
+        assert!(validate_tag_name("c++", Strict).is_err());
+        assert!(validate_tag_name("-work", Strict).is_err());
+        assert!(validate_tag_name("work/rust-", Strict).is_err());
+        assert!(validate_tag_name("work--rust", Strict).is_err());
+        assert!(validate_tag_name(&"a".repeat(51), Strict).is_err());
+        // Basic rules apply under every policy
+        assert!(matches!(validate_tag_name("a b", Permissive), Err(PmError::InvalidTagName(_))));
+        assert!(validate_tag_name("a/b/c", LowercaseOnly).is_err());
+
+        let generated = ["jetbrains/Android Studio".to_string(), "Rust".to_string()];
+        assert_eq!(allowed_generated_tags(generated.clone(), LowercaseOnly), vec!["rust"]);
+        assert!(allowed_generated_tags(generated, Strict).is_empty());
+    }
+
+    #[test]
+    fn test_validate_color() {
+        assert!(validate_color("red").is_ok());
+        assert!(validate_color("Cyan").is_ok());
+        assert!(validate_color("#ff6600").is_ok());
+        assert!(validate_color("#f60").is_ok());
+        assert!(validate_color("orange").is_err());
+        assert!(validate_color("#ff66").is_err());
+        assert!(validate_color("#gg0000").is_err());
+        assert!(validate_color("#€").is_err());
+
+        // Hand-edited configs bypass validate_color; parsing must not panic on them
+        assert!(crate::display::parse_project_color("#€").is_none());
+        assert!(crate::display::parse_project_color("#€€").is_none());
+        assert!(crate::display::parse_project_color("#f60").is_some());
     }
 }